@@ -0,0 +1,70 @@
+use apollo_compiler::{validate, ExecutableDocument, Schema};
+use criterion::*;
+
+const SCHEMA: &str = r#"
+type Query { user(id: ID!): User users(first: Int): [User!]! node(id: ID!): Node }
+interface Node { id: ID! }
+type User implements Node { id: ID! name: String friends(first: Int): [User!]! }
+type Group implements Node { id: ID! name: String members: [User!]! }
+"#;
+
+fn validate_query(schema: &Schema, query: &str) {
+    let document = ExecutableDocument::parse("query.graphql", query);
+    let diagnostics = validate(schema, &document);
+
+    if !diagnostics.is_empty() {
+        panic!("error validating query: {:?}", diagnostics);
+    }
+}
+
+/// A query where every fragment spreads the next one twice, once directly
+/// and once in a nested selection set. Expanding every spread produces
+/// 2^depth copies of the innermost fragment's fields. Expanding each
+/// fragment once per selection set is still quadratic, as the selection set
+/// at every level merges the fields of all fragments below it. Merging the
+/// fields of each fragment once keeps validation linear in the depth.
+fn nested_fragments_query(depth: usize) -> String {
+    let mut query = String::from("query { user(id: 1) { ...F0 } }\n");
+    for i in 0..depth {
+        query.push_str(&format!(
+            "fragment F{i} on User {{ id name friends {{ ...F{next} }} ...F{next} }}\n",
+            i = i,
+            next = i + 1
+        ));
+    }
+    query.push_str(&format!("fragment F{} on User {{ id name }}\n", depth));
+    query
+}
+
+/// A query that selects the same fields many times, on the same type and on
+/// abstract types.
+fn repeated_fields_query(count: usize) -> String {
+    let mut query = String::from("query { node(id: 1) {\n");
+    for _ in 0..count {
+        query.push_str("  id\n  ... on User { name friends(first: 0) { id name } }\n");
+        query.push_str("  ... on Group { name members { id } }\n  ... on Node { id }\n");
+    }
+    query.push_str("} }");
+    query
+}
+
+fn bench_nested_fragments(c: &mut Criterion) {
+    let schema = Schema::parse(SCHEMA);
+    let query = nested_fragments_query(100);
+
+    c.bench_function("validate_nested_fragments", move |b| {
+        b.iter(|| validate_query(&schema, &query))
+    });
+}
+
+fn bench_repeated_fields(c: &mut Criterion) {
+    let schema = Schema::parse(SCHEMA);
+    let query = repeated_fields_query(1000);
+
+    c.bench_function("validate_repeated_fields", move |b| {
+        b.iter(|| validate_query(&schema, &query))
+    });
+}
+
+criterion_group!(benches, bench_nested_fragments, bench_repeated_fields);
+criterion_main!(benches);
@@ -0,0 +1,231 @@
+use std::{fmt, slice::Iter};
+
+use apollo_parser::{
+    ast::{self, AstNode},
+    SyntaxTree,
+};
+
+use crate::{SourceId, Span};
+
+/// A problem found while building or validating a schema or an executable
+/// document.
+///
+/// The message describes the problem on its own. Labels point at the source
+/// spans involved, with the primary span first.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{DiagnosticKind, Schema};
+///
+/// let schema = Schema::parse("type Query { me: String } type Query { you: String }");
+///
+/// let diagnostic = schema.diagnostics().next().unwrap();
+/// assert_eq!(diagnostic.kind(), &DiagnosticKind::DuplicateDefinition);
+/// assert_eq!(diagnostic.message(), "the type `Query` is defined multiple times");
+/// assert_eq!(diagnostic.labels().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    kind: DiagnosticKind,
+    message: String,
+    labels: Vec<Label>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(kind: DiagnosticKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            labels: Vec::new(),
+        }
+    }
+
+    /// Add a label pointing at `span`.
+    pub(crate) fn label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    /// Get a reference to the diagnostic's kind.
+    pub fn kind(&self) -> &DiagnosticKind {
+        &self.kind
+    }
+
+    /// Get a reference to the diagnostic's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the labels pointing at the spans involved, with the primary one
+    /// first.
+    pub fn labels(&self) -> Iter<'_, Label> {
+        self.labels.iter()
+    }
+
+    /// Get the primary span of the diagnostic, if any.
+    pub fn span(&self) -> Option<Span> {
+        self.labels.first().map(|label| label.span)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+/// Create a diagnostic for an error reported by `apollo-parser`.
+pub(crate) fn syntax_error(source: SourceId, err: &apollo_parser::Error) -> Diagnostic {
+    Diagnostic::new(DiagnosticKind::SyntaxError, err.message())
+        .label(Span::new(source, err.span()), err.message())
+}
+
+/// Create diagnostics for the syntax errors of a tree parsed from `source`,
+/// and for its string values that cannot be decoded.
+///
+/// Strings with invalid escape sequences are usually already reported by the
+/// lexer, so decoding errors are only reported for strings that no syntax
+/// error points at.
+pub(crate) fn syntax_errors(source: SourceId, tree: &SyntaxTree) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<_> = tree.errors().map(|err| syntax_error(source, err)).collect();
+    let reported = |err: &apollo_parser::Error| {
+        let span = err.span();
+        tree.errors().any(|syntax_err| {
+            let reported = syntax_err.span();
+            reported.start < span.end && span.start < reported.end
+        })
+    };
+    diagnostics.extend(
+        tree.document()
+            .syntax()
+            .descendants()
+            .filter_map(ast::StringValue::cast)
+            .filter_map(|string| string.value().err())
+            .filter(|err| !reported(err))
+            .map(|err| syntax_error(source, &err)),
+    );
+    diagnostics
+}
+
+/// A source span that is part of a `Diagnostic`, and what it shows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    span: Span,
+    message: String,
+}
+
+impl Label {
+    /// Get the span this label points at.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Get a reference to the label's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// The kind of a [`Diagnostic`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// A lexical or syntactic error reported by `apollo-parser`.
+    SyntaxError,
+    /// A type, directive or schema definition that is defined more than
+    /// once, or a field, argument, enum value, interface or union member
+    /// that appears more than once in a definition.
+    DuplicateDefinition,
+    /// A definition of a type or directive that is built into every schema,
+    /// like `scalar String` or `directive @skip`.
+    BuiltInRedefinition,
+    /// A type extension for a type that is not defined.
+    UndefinedExtensionTarget,
+    /// A type extension of a different kind than the type it extends, for
+    /// example `extend enum Query` for an object type.
+    ExtensionKindMismatch,
+    /// A reference to a type that is not defined.
+    UndefinedType,
+    /// An object, interface, input object or enum type without fields or
+    /// values, or a union without member types.
+    EmptyType,
+    /// A name beginning with `__`, which is reserved for introspection.
+    ReservedName,
+    /// A field with an input object type, or an argument or input field with
+    /// an output type.
+    InvalidFieldType,
+    /// An object or interface type that does not correctly implement one of
+    /// its interfaces.
+    InvalidImplementation,
+    /// A union member type that is not an object type.
+    InvalidUnionMember,
+    /// A root operation type that is not an object type.
+    InvalidRootOperationType,
+    /// An input object that references itself through non-null fields, so
+    /// that no value of it can be provided.
+    InputObjectCycle,
+    /// An anonymous operation in a document with more than one operation.
+    AnonymousOperationNotAlone,
+    /// An operation of a type the schema has no root operation type for,
+    /// like a mutation for a schema without a `Mutation` type.
+    UnsupportedOperation,
+    /// A subscription that selects more than one root field.
+    MultipleSubscriptionRootFields,
+    /// A subscription whose root field is an introspection field.
+    IntrospectionSubscriptionRootField,
+    /// A selection of a field that is not defined on the selected type.
+    UndefinedField,
+    /// An argument that is not defined on its field.
+    UndefinedArgument,
+    /// An argument that is provided more than once.
+    DuplicateArgument,
+    /// A required argument that is not provided.
+    MissingArgument,
+    /// A field of an object, interface or union type without a selection
+    /// set.
+    MissingSubselection,
+    /// A field of a scalar or enum type with a selection set.
+    UnexpectedSubselection,
+    /// A spread of a fragment that is not defined.
+    UndefinedFragment,
+    /// A fragment that no operation uses.
+    UnusedFragment,
+    /// A fragment that spreads itself, directly or through other fragments.
+    FragmentCycle,
+    /// A type condition on a type that is not an object, interface or union
+    /// type.
+    InvalidTypeCondition,
+    /// A fragment spread that can never apply, because the fragment's type
+    /// and the parent type have no possible types in common.
+    ImpossibleFragmentSpread,
+    /// A use of a variable that the operation does not define.
+    UndefinedVariable,
+    /// A variable that the operation defines but never uses.
+    UnusedVariable,
+    /// A variable whose type is not an input type.
+    InvalidVariableType,
+    /// A use of a variable where a value of an incompatible type is
+    /// expected, like a nullable variable for a non-null argument without a
+    /// default value.
+    IncompatibleVariableUsage,
+    /// A use of a directive that is not defined.
+    UndefinedDirective,
+    /// A directive used in a location its definition does not list, like
+    /// `@deprecated` on a field selection.
+    MisplacedDirective,
+    /// A directive that is not `repeatable`, used more than once in the
+    /// same location.
+    RepeatedDirective,
+    /// A value that is not valid for the type of its argument, input field
+    /// or variable, like a string for an `Int` argument.
+    InvalidValue,
+    /// Fields with the same response name that cannot be merged, because
+    /// they select different fields, with different arguments, or with
+    /// different response shapes.
+    ConflictingFields,
+}
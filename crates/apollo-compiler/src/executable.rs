@@ -0,0 +1,117 @@
+//! Executable documents: operations and fragments.
+
+use std::slice::Iter;
+
+use apollo_parser::{
+    ast::{self, AstChildren, AstNode},
+    ParseMode, Parser,
+};
+
+use crate::{diagnostics::syntax_errors, Diagnostic, Source, Span};
+
+/// An executable document, holding the operations and fragments a client
+/// sends to a GraphQL service.
+///
+/// Operations are validated against a [`Schema`](crate::Schema) with
+/// [`validate`](crate::validate). Unlike a schema, an executable document
+/// keeps its syntax tree: validation works directly on the AST nodes, so
+/// that diagnostics can point at any part of the document.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::ExecutableDocument;
+///
+/// let document = ExecutableDocument::parse(
+///     "query.graphql",
+///     "query Me { me { ...User } } fragment User on User { name }",
+/// );
+/// assert_eq!(document.diagnostics().len(), 0);
+/// assert_eq!(document.operations().count(), 1);
+/// assert!(document.fragment("User").is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExecutableDocument {
+    source: Source,
+    document: ast::ExecutableDocument,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ExecutableDocument {
+    /// Parse an executable document. `name` identifies the document in
+    /// diagnostics, and is usually a file path.
+    ///
+    /// Type system definitions are not allowed in executable documents, and
+    /// are reported as syntax errors.
+    pub fn parse(name: impl Into<String>, input: impl Into<String>) -> Self {
+        let source = Source::new(name, input);
+        let tree = Parser::new(source.text())
+            .mode(ParseMode::Executable)
+            .parse();
+        let diagnostics = syntax_errors(source.id(), &tree);
+        Self {
+            document: tree.document().into(),
+            source,
+            diagnostics,
+        }
+    }
+
+    /// Get the source the document was parsed from.
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// Get the syntax errors found while parsing the document.
+    pub fn diagnostics(&self) -> Iter<'_, Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    /// Get the AST of the document.
+    pub fn document(&self) -> &ast::ExecutableDocument {
+        &self.document
+    }
+
+    /// Iterate over the operation definitions.
+    pub fn operations(&self) -> AstChildren<ast::OperationDefinition> {
+        self.document.operations()
+    }
+
+    /// Iterate over the fragment definitions.
+    pub fn fragments(&self) -> AstChildren<ast::FragmentDefinition> {
+        self.document.fragments()
+    }
+
+    /// Get the first fragment definition called `name`.
+    pub fn fragment(&self, name: &str) -> Option<ast::FragmentDefinition> {
+        self.fragments()
+            .find(|fragment| fragment_name(fragment).as_deref() == Some(name))
+    }
+
+    /// Get the span of a node of this document, without the whitespace,
+    /// commas and comments around it.
+    pub fn span(&self, node: &impl AstNode) -> Span {
+        Span::of(self.source.id(), node)
+    }
+}
+
+/// Get the name of a fragment definition, if it has one.
+pub(crate) fn fragment_name(fragment: &ast::FragmentDefinition) -> Option<String> {
+    Some(fragment.fragment_name()?.name()?.text().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{DiagnosticKind, ExecutableDocument};
+
+    #[test]
+    fn it_reports_descriptions_on_operations() {
+        let document = ExecutableDocument::parse("query.graphql", r#""a" query { b }"#);
+
+        let messages: Vec<_> = document.diagnostics().map(|d| d.message()).collect();
+        assert_eq!(messages, ["descriptions are not allowed on operations"]);
+        assert_eq!(
+            document.diagnostics().next().unwrap().kind(),
+            &DiagnosticKind::SyntaxError
+        );
+        assert_eq!(document.operations().count(), 1);
+    }
+}
@@ -0,0 +1,815 @@
+//! The items of the semantic model, built from the AST.
+//!
+//! Unlike AST nodes, items own their data and do not depend on the syntax
+//! tree they were built from. Items of a type are collected from its
+//! definition and all of its extensions, and every item carries the span of
+//! the definition it came from.
+
+use std::fmt;
+
+use crate::Span;
+
+/// The type of a root operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationType {
+    /// Get the keyword of the operation type, like `query`.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        }
+    }
+
+    /// Get the name of the root type used when a schema has no schema
+    /// definition, like `Query`.
+    pub fn default_type_name(&self) -> &'static str {
+        match self {
+            OperationType::Query => "Query",
+            OperationType::Mutation => "Mutation",
+            OperationType::Subscription => "Subscription",
+        }
+    }
+}
+
+impl fmt::Display for OperationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.keyword())
+    }
+}
+
+/// A reference to a type, like `[String!]!`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    Named(String),
+    List(Box<Type>),
+    NonNull(Box<Type>),
+}
+
+impl Type {
+    /// Get the name of the named type inside of any list and non-null
+    /// wrappers, like `String` for `[String!]!`.
+    pub fn name(&self) -> &str {
+        match self {
+            Type::Named(name) => name,
+            Type::List(ty) | Type::NonNull(ty) => ty.name(),
+        }
+    }
+
+    /// Check whether this is a non-null type.
+    pub fn is_non_null(&self) -> bool {
+        matches!(self, Type::NonNull(_))
+    }
+
+    /// Check whether this is a list type, or a non-null list type.
+    pub fn is_list(&self) -> bool {
+        match self {
+            Type::List(_) => true,
+            Type::NonNull(ty) => ty.is_list(),
+            Type::Named(_) => false,
+        }
+    }
+
+    /// Get the type of the items of a list type, or `None` if this is not a
+    /// list type.
+    pub fn item_type(&self) -> Option<&Type> {
+        match self {
+            Type::List(ty) => Some(ty),
+            Type::NonNull(ty) => ty.item_type(),
+            Type::Named(_) => None,
+        }
+    }
+
+    /// Get this type without its non-null wrapper, if any.
+    pub fn nullable(&self) -> &Type {
+        match self {
+            Type::NonNull(ty) => ty,
+            ty => ty,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Named(name) => f.write_str(name),
+            Type::List(ty) => write!(f, "[{}]", ty),
+            Type::NonNull(ty) => write!(f, "{}!", ty),
+        }
+    }
+}
+
+/// A constant or variable value, like the default value of an argument.
+///
+/// Ints and Floats are kept as written, so that values that are out of
+/// range for their type can still be reported.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Variable(String),
+    Int(String),
+    Float(String),
+    String(String),
+    Boolean(bool),
+    Null,
+    Enum(String),
+    List(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Variable(name) => write!(f, "${}", name),
+            Value::Int(text) | Value::Float(text) | Value::Enum(text) => f.write_str(text),
+            Value::String(value) => write!(f, "{:?}", value),
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::Null => f.write_str("null"),
+            Value::List(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, " {}: {}", name, value)?;
+                }
+                if !fields.is_empty() {
+                    f.write_str(" ")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// A directive applied to a definition, like `@deprecated(reason: "...")`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directive {
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<Argument>,
+    pub(crate) span: Span,
+}
+
+impl Directive {
+    /// Get the directive's name, without the `@`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the directive's arguments.
+    pub fn arguments(&self) -> &[Argument] {
+        &self.arguments
+    }
+
+    /// Get the value of the argument called `name`.
+    pub fn argument(&self, name: &str) -> Option<&Value> {
+        self.arguments
+            .iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| &arg.value)
+    }
+
+    /// Get the directive's span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// An argument of an applied directive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Argument {
+    pub(crate) name: String,
+    pub(crate) value: Value,
+    pub(crate) span: Span,
+}
+
+impl Argument {
+    /// Get the argument's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the argument's value.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Get the argument's span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The definition of a directive, like
+/// `directive @tag(name: String!) repeatable on FIELD_DEFINITION`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectiveDefinition {
+    pub(crate) description: Option<String>,
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<InputValueDefinition>,
+    pub(crate) repeatable: bool,
+    pub(crate) locations: Vec<DirectiveLocation>,
+    pub(crate) span: Span,
+    pub(crate) built_in: bool,
+}
+
+impl DirectiveDefinition {
+    /// Get the directive's description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the directive's name, without the `@`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the definitions of the directive's arguments.
+    pub fn arguments(&self) -> &[InputValueDefinition] {
+        &self.arguments
+    }
+
+    /// Get the definition of the argument called `name`.
+    pub fn argument(&self, name: &str) -> Option<&InputValueDefinition> {
+        self.arguments.iter().find(|arg| arg.name == name)
+    }
+
+    /// Check whether the directive may be applied more than once to the
+    /// same location.
+    pub fn is_repeatable(&self) -> bool {
+        self.repeatable
+    }
+
+    /// Get the locations the directive may be applied to.
+    pub fn locations(&self) -> &[DirectiveLocation] {
+        &self.locations
+    }
+
+    /// Check whether this is one of the directives defined by the
+    /// specification, like `@skip`.
+    pub fn is_built_in(&self) -> bool {
+        self.built_in
+    }
+
+    /// Get the definition's span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A location a directive may be applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirectiveLocation {
+    Query,
+    Mutation,
+    Subscription,
+    Field,
+    FragmentDefinition,
+    FragmentSpread,
+    InlineFragment,
+    VariableDefinition,
+    Schema,
+    Scalar,
+    Object,
+    FieldDefinition,
+    ArgumentDefinition,
+    Interface,
+    Union,
+    Enum,
+    EnumValue,
+    InputObject,
+    InputFieldDefinition,
+}
+
+impl DirectiveLocation {
+    /// Get the location's name as written in a directive definition, like
+    /// `FIELD_DEFINITION`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DirectiveLocation::Query => "QUERY",
+            DirectiveLocation::Mutation => "MUTATION",
+            DirectiveLocation::Subscription => "SUBSCRIPTION",
+            DirectiveLocation::Field => "FIELD",
+            DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+            DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+            DirectiveLocation::Schema => "SCHEMA",
+            DirectiveLocation::Scalar => "SCALAR",
+            DirectiveLocation::Object => "OBJECT",
+            DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+            DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+            DirectiveLocation::Interface => "INTERFACE",
+            DirectiveLocation::Union => "UNION",
+            DirectiveLocation::Enum => "ENUM",
+            DirectiveLocation::EnumValue => "ENUM_VALUE",
+            DirectiveLocation::InputObject => "INPUT_OBJECT",
+            DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+        }
+    }
+
+    /// Get the location called `name`, or `None` if there is no such
+    /// location.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "QUERY" => DirectiveLocation::Query,
+            "MUTATION" => DirectiveLocation::Mutation,
+            "SUBSCRIPTION" => DirectiveLocation::Subscription,
+            "FIELD" => DirectiveLocation::Field,
+            "FRAGMENT_DEFINITION" => DirectiveLocation::FragmentDefinition,
+            "FRAGMENT_SPREAD" => DirectiveLocation::FragmentSpread,
+            "INLINE_FRAGMENT" => DirectiveLocation::InlineFragment,
+            "VARIABLE_DEFINITION" => DirectiveLocation::VariableDefinition,
+            "SCHEMA" => DirectiveLocation::Schema,
+            "SCALAR" => DirectiveLocation::Scalar,
+            "OBJECT" => DirectiveLocation::Object,
+            "FIELD_DEFINITION" => DirectiveLocation::FieldDefinition,
+            "ARGUMENT_DEFINITION" => DirectiveLocation::ArgumentDefinition,
+            "INTERFACE" => DirectiveLocation::Interface,
+            "UNION" => DirectiveLocation::Union,
+            "ENUM" => DirectiveLocation::Enum,
+            "ENUM_VALUE" => DirectiveLocation::EnumValue,
+            "INPUT_OBJECT" => DirectiveLocation::InputObject,
+            "INPUT_FIELD_DEFINITION" => DirectiveLocation::InputFieldDefinition,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for DirectiveLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The definition of a field of an object or interface type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDefinition {
+    pub(crate) description: Option<String>,
+    pub(crate) name: String,
+    pub(crate) arguments: Vec<InputValueDefinition>,
+    pub(crate) ty: Type,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) span: Span,
+}
+
+impl FieldDefinition {
+    /// Get the field's description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the field's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the definitions of the field's arguments.
+    pub fn arguments(&self) -> &[InputValueDefinition] {
+        &self.arguments
+    }
+
+    /// Get the definition of the argument called `name`.
+    pub fn argument(&self, name: &str) -> Option<&InputValueDefinition> {
+        self.arguments.iter().find(|arg| arg.name == name)
+    }
+
+    /// Get the field's type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Get the directives applied to the field.
+    pub fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    /// Get the definition's span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The definition of an argument, or of a field of an input object type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputValueDefinition {
+    pub(crate) description: Option<String>,
+    pub(crate) name: String,
+    pub(crate) ty: Type,
+    pub(crate) default_value: Option<Value>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) span: Span,
+}
+
+impl InputValueDefinition {
+    /// Get the input value's description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the input value's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the input value's type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Get the input value's default value.
+    pub fn default_value(&self) -> Option<&Value> {
+        self.default_value.as_ref()
+    }
+
+    /// Check whether a value must be provided for this input value, because
+    /// it is non-null and has no default value.
+    pub fn is_required(&self) -> bool {
+        self.ty.is_non_null() && self.default_value.is_none()
+    }
+
+    /// Get the directives applied to the input value.
+    pub fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    /// Get the definition's span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// The definition of a value of an enum type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValueDefinition {
+    pub(crate) description: Option<String>,
+    pub(crate) value: String,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) span: Span,
+}
+
+impl EnumValueDefinition {
+    /// Get the enum value's description.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the enum value's name.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Get the directives applied to the enum value.
+    pub fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    /// Get the definition's span.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+/// A named type defined in a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeDefinition {
+    Scalar(ScalarType),
+    Object(ObjectType),
+    Interface(InterfaceType),
+    Union(UnionType),
+    Enum(EnumType),
+    InputObject(InputObjectType),
+}
+
+impl TypeDefinition {
+    /// Get the type's name.
+    pub fn name(&self) -> &str {
+        &self.common().name
+    }
+
+    /// Get the type's description.
+    pub fn description(&self) -> Option<&str> {
+        self.common().description.as_deref()
+    }
+
+    /// Get the directives applied to the type and its extensions.
+    pub fn directives(&self) -> &[Directive] {
+        &self.common().directives
+    }
+
+    /// Get the span of the type's definition.
+    pub fn span(&self) -> Span {
+        self.common().span
+    }
+
+    /// Get the spans of the type's extensions.
+    pub fn extensions(&self) -> &[Span] {
+        &self.common().extensions
+    }
+
+    /// Check whether this is one of the types defined by the specification:
+    /// a built-in scalar like `String`, or an introspection type like
+    /// `__Schema`.
+    pub fn is_built_in(&self) -> bool {
+        self.common().built_in
+    }
+
+    /// Get the fields of an object or interface type. Other types have no
+    /// fields.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        match self {
+            TypeDefinition::Object(ty) => &ty.fields,
+            TypeDefinition::Interface(ty) => &ty.fields,
+            _ => &[],
+        }
+    }
+
+    /// Get the interfaces an object or interface type implements.
+    pub fn implements_interfaces(&self) -> &[String] {
+        match self {
+            TypeDefinition::Object(ty) => &ty.implements_interfaces,
+            TypeDefinition::Interface(ty) => &ty.implements_interfaces,
+            _ => &[],
+        }
+    }
+
+    /// Get the keyword the type is defined with, like `type` or `input`.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            TypeDefinition::Scalar(_) => "scalar",
+            TypeDefinition::Object(_) => "type",
+            TypeDefinition::Interface(_) => "interface",
+            TypeDefinition::Union(_) => "union",
+            TypeDefinition::Enum(_) => "enum",
+            TypeDefinition::InputObject(_) => "input",
+        }
+    }
+
+    /// Check whether the type can be used as the type of an argument or an
+    /// input field: scalars, enums and input objects.
+    pub fn is_input_type(&self) -> bool {
+        matches!(
+            self,
+            TypeDefinition::Scalar(_) | TypeDefinition::Enum(_) | TypeDefinition::InputObject(_)
+        )
+    }
+
+    /// Check whether the type can be used as the type of a field: any type
+    /// but input objects.
+    pub fn is_output_type(&self) -> bool {
+        !matches!(self, TypeDefinition::InputObject(_))
+    }
+
+    /// Check whether the type is a leaf type: a scalar or an enum.
+    pub fn is_leaf_type(&self) -> bool {
+        matches!(self, TypeDefinition::Scalar(_) | TypeDefinition::Enum(_))
+    }
+
+    /// Check whether the type is a composite type, which is selected on
+    /// with a selection set: an object, an interface or a union.
+    pub fn is_composite_type(&self) -> bool {
+        matches!(
+            self,
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) | TypeDefinition::Union(_)
+        )
+    }
+
+    /// Check whether the type is an abstract type: an interface or a union.
+    pub fn is_abstract_type(&self) -> bool {
+        matches!(
+            self,
+            TypeDefinition::Interface(_) | TypeDefinition::Union(_)
+        )
+    }
+
+    fn common(&self) -> &TypeCommon {
+        match self {
+            TypeDefinition::Scalar(ty) => &ty.common,
+            TypeDefinition::Object(ty) => &ty.common,
+            TypeDefinition::Interface(ty) => &ty.common,
+            TypeDefinition::Union(ty) => &ty.common,
+            TypeDefinition::Enum(ty) => &ty.common,
+            TypeDefinition::InputObject(ty) => &ty.common,
+        }
+    }
+
+    pub(crate) fn common_mut(&mut self) -> &mut TypeCommon {
+        match self {
+            TypeDefinition::Scalar(ty) => &mut ty.common,
+            TypeDefinition::Object(ty) => &mut ty.common,
+            TypeDefinition::Interface(ty) => &mut ty.common,
+            TypeDefinition::Union(ty) => &mut ty.common,
+            TypeDefinition::Enum(ty) => &mut ty.common,
+            TypeDefinition::InputObject(ty) => &mut ty.common,
+        }
+    }
+}
+
+/// The parts that all type definitions have.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TypeCommon {
+    pub(crate) description: Option<String>,
+    pub(crate) name: String,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) span: Span,
+    pub(crate) extensions: Vec<Span>,
+    pub(crate) built_in: bool,
+}
+
+macro_rules! type_common {
+    ($ty:ident) => {
+        impl $ty {
+            /// Get the type's name.
+            pub fn name(&self) -> &str {
+                &self.common.name
+            }
+
+            /// Get the type's description.
+            pub fn description(&self) -> Option<&str> {
+                self.common.description.as_deref()
+            }
+
+            /// Get the directives applied to the type and its extensions.
+            pub fn directives(&self) -> &[Directive] {
+                &self.common.directives
+            }
+
+            /// Get the span of the type's definition.
+            pub fn span(&self) -> Span {
+                self.common.span
+            }
+
+            /// Get the spans of the type's extensions.
+            pub fn extensions(&self) -> &[Span] {
+                &self.common.extensions
+            }
+
+            /// Check whether this is one of the types defined by the
+            /// specification.
+            pub fn is_built_in(&self) -> bool {
+                self.common.built_in
+            }
+        }
+    };
+}
+
+/// A scalar type, like `scalar DateTime`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalarType {
+    pub(crate) common: TypeCommon,
+}
+
+type_common!(ScalarType);
+
+/// An object type, like `type User { name: String }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectType {
+    pub(crate) common: TypeCommon,
+    pub(crate) implements_interfaces: Vec<String>,
+    pub(crate) fields: Vec<FieldDefinition>,
+}
+
+type_common!(ObjectType);
+
+impl ObjectType {
+    /// Get the names of the interfaces the type implements.
+    pub fn implements_interfaces(&self) -> &[String] {
+        &self.implements_interfaces
+    }
+
+    /// Get the type's fields.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        &self.fields
+    }
+
+    /// Get the definition of the field called `name`.
+    pub fn field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// An interface type, like `interface Node { id: ID! }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceType {
+    pub(crate) common: TypeCommon,
+    pub(crate) implements_interfaces: Vec<String>,
+    pub(crate) fields: Vec<FieldDefinition>,
+}
+
+type_common!(InterfaceType);
+
+impl InterfaceType {
+    /// Get the names of the interfaces the type implements.
+    pub fn implements_interfaces(&self) -> &[String] {
+        &self.implements_interfaces
+    }
+
+    /// Get the type's fields.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        &self.fields
+    }
+
+    /// Get the definition of the field called `name`.
+    pub fn field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A union type, like `union SearchResult = Photo | Person`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnionType {
+    pub(crate) common: TypeCommon,
+    pub(crate) members: Vec<String>,
+}
+
+type_common!(UnionType);
+
+impl UnionType {
+    /// Get the names of the union's member types.
+    pub fn members(&self) -> &[String] {
+        &self.members
+    }
+}
+
+/// An enum type, like `enum Direction { NORTH SOUTH }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumType {
+    pub(crate) common: TypeCommon,
+    pub(crate) values: Vec<EnumValueDefinition>,
+}
+
+type_common!(EnumType);
+
+impl EnumType {
+    /// Get the definitions of the enum's values.
+    pub fn values(&self) -> &[EnumValueDefinition] {
+        &self.values
+    }
+
+    /// Get the definition of the value called `name`.
+    pub fn value(&self, name: &str) -> Option<&EnumValueDefinition> {
+        self.values.iter().find(|value| value.value == name)
+    }
+}
+
+/// An input object type, like `input Point { x: Float! y: Float! }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputObjectType {
+    pub(crate) common: TypeCommon,
+    pub(crate) fields: Vec<InputValueDefinition>,
+}
+
+type_common!(InputObjectType);
+
+impl InputObjectType {
+    /// Get the definitions of the input object's fields.
+    pub fn fields(&self) -> &[InputValueDefinition] {
+        &self.fields
+    }
+
+    /// Get the definition of the field called `name`.
+    pub fn field(&self, name: &str) -> Option<&InputValueDefinition> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Type, Value};
+
+    #[test]
+    fn it_displays_types_and_values() {
+        let ty = Type::NonNull(Box::new(Type::List(Box::new(Type::NonNull(Box::new(
+            Type::Named("String".into()),
+        ))))));
+        assert_eq!(ty.to_string(), "[String!]!");
+        assert_eq!(ty.name(), "String");
+        assert!(ty.is_list());
+        assert_eq!(ty.item_type().unwrap().to_string(), "String!");
+
+        let value = Value::Object(vec![
+            (
+                "a".into(),
+                Value::List(vec![Value::Int("1".into()), Value::Null]),
+            ),
+            ("b".into(), Value::String("x\"y".into())),
+            ("c".into(), Value::Variable("v".into())),
+        ]);
+        assert_eq!(value.to_string(), r#"{ a: [1, null], b: "x\"y", c: $v }"#);
+    }
+}
@@ -0,0 +1,113 @@
+//! <div align="center">
+//!   <h1><code>apollo-compiler</code></h1>
+//!
+//!   <p>
+//!     <strong>Semantic analysis and validation of GraphQL schemas and queries.</strong>
+//!   </p>
+//!   <p>
+//!     <a href="https://crates.io/crates/apollo-compiler">
+//!         <img src="https://img.shields.io/crates/v/apollo-compiler.svg?style=flat-square" alt="Crates.io" />
+//!     </a>
+//!     <a href="https://crates.io/crates/apollo-compiler">
+//!         <img src="https://img.shields.io/crates/d/apollo-compiler.svg?style=flat-square" alt="Download" />
+//!     </a>
+//!     <a href="https://docs.rs/apollo-compiler/">
+//!         <img src="https://img.shields.io/static/v1?label=docs&message=apollo-compiler&color=blue&style=flat-square" alt="docs.rs docs" />
+//!     </a>
+//!   </p>
+//! </div>
+//!
+//! `apollo-compiler` builds a semantic model on top of the syntax tree
+//! produced by [`apollo-parser`]. Rather than walking `ast::Definition`s to
+//! find out what fields a type has, you can build a [`Schema`] from one or
+//! more documents and look its types up by name.
+//!
+//! ## Features
+//! * Schema model built from one or more type system documents, with type
+//!   extensions merged into the types they extend
+//! * Lookups of types, fields, interface implementers, possible types of
+//!   abstract types, root operation types and directive definitions
+//! * Built-in scalars, directives and introspection types, and the
+//!   `__typename`, `__schema` and `__type` meta-fields
+//! * Printing of a schema back to SDL, without the built-ins
+//! * Type system validation, from interface implementations to input object
+//!   cycles
+//! * Validation of executable documents against a schema with [`validate`]:
+//!   fields, arguments, leaf selections, field merging, operations, variables
+//!   and fragments
+//! * Validation of argument, input field and default values against their
+//!   types, with pluggable checks for custom scalars
+//! * Validation of applied directives in both schemas and executable
+//!   documents: definitions, locations, repetition and arguments
+//! * Source spans for every item, and diagnostics pointing at them
+//!
+//! ## Getting started
+//! Add this to your `Cargo.toml` to start using `apollo-compiler`:
+//! ```toml
+//! # Just an example, change to the necessary package version.
+//! [dependencies]
+//! apollo-compiler = "0.1.0"
+//! ```
+//!
+//! Or using [cargo-edit]:
+//! ```bash
+//! cargo add apollo-compiler
+//! ```
+//!
+//! ## Example
+//! ```rust
+//! use apollo_compiler::Schema;
+//!
+//! let input = r#"
+//! type Query {
+//!   pets: [Pet]
+//! }
+//!
+//! interface Pet {
+//!   name: String
+//! }
+//!
+//! type Cat implements Pet {
+//!   name: String
+//!   lives: Int
+//! }
+//!
+//! extend type Cat {
+//!   favouriteSnack: String
+//! }
+//! "#;
+//! let schema = Schema::parse(input);
+//! assert_eq!(schema.diagnostics().len(), 0);
+//!
+//! let fields: Vec<_> = schema.fields_of("Cat").iter().map(|field| field.name()).collect();
+//! assert_eq!(fields, ["name", "lives", "favouriteSnack"]);
+//!
+//! let pets: Vec<_> = schema.possible_types("Pet").iter().map(|ty| ty.name()).collect();
+//! assert_eq!(pets, ["Cat"]);
+//! ```
+//!
+//! ## License
+//! Licensed under either of
+//!
+//! - Apache License, Version 2.0 ([LICENSE-APACHE] or <https://www.apache.org/licenses/LICENSE-2.0>)
+//! - MIT license ([LICENSE-MIT] or <https://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! [`apollo-parser`]: https://docs.rs/apollo-parser
+//! [cargo-edit]: https://github.com/killercup/cargo-edit
+//! [LICENSE-APACHE]: https://github.com/apollographql/apollo-rs/blob/main/crates/apollo-compiler/LICENSE-APACHE
+//! [LICENSE-MIT]:https://github.com/apollographql/apollo-rs/blob/main/crates/apollo-compiler/LICENSE-MIT
+
+mod diagnostics;
+mod executable;
+pub mod hir;
+mod schema;
+mod source;
+mod validation;
+
+pub use crate::diagnostics::{Diagnostic, DiagnosticKind, Label};
+pub use crate::executable::ExecutableDocument;
+pub use crate::schema::{Schema, SchemaBuilder};
+pub use crate::source::{Source, SourceId, Span};
+pub use crate::validation::validate;
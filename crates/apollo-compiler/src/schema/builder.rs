@@ -0,0 +1,712 @@
+//! Lowering of type system documents into a `Schema`.
+
+use std::{collections::HashMap, sync::Arc};
+
+use apollo_parser::{
+    ast::{self, AstNode},
+    ParseMode, Parser, SyntaxTree,
+};
+use indexmap::IndexMap;
+
+use crate::{
+    diagnostics::syntax_errors,
+    hir::{
+        Argument, Directive, DirectiveDefinition, DirectiveLocation, EnumType, EnumValueDefinition,
+        FieldDefinition, InputObjectType, InputValueDefinition, InterfaceType, ObjectType,
+        OperationType, ScalarType, Type, TypeCommon, TypeDefinition, UnionType, Value,
+    },
+    schema::ScalarValidators,
+    Diagnostic, DiagnosticKind, Schema, Source, SourceId, Span,
+};
+
+/// The built-in scalars, directives and introspection types that are part of
+/// every schema, and the meta-fields of composite types.
+const BUILT_IN: &str = include_str!("built_in.graphql");
+
+/// The name of the type in `BUILT_IN` that holds the meta-fields.
+const META_FIELDS: &str = "__MetaFields";
+
+/// Builds a `Schema` from one or more type system documents.
+///
+/// The built-in scalars like `String`, the built-in directives like `@skip`,
+/// and the introspection types like `__Schema` are always part of the
+/// schema. They are defined in a source named `built_in.graphql`.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::SchemaBuilder;
+///
+/// let mut builder = SchemaBuilder::new();
+/// builder.add_document("users.graphql", "type User { id: ID! }");
+/// builder.add_document("query.graphql", "type Query { users: [User] }");
+/// let schema = builder.build();
+///
+/// assert_eq!(schema.diagnostics().len(), 0);
+/// let user_types: Vec<_> = schema
+///     .types()
+///     .filter(|ty| !ty.is_built_in())
+///     .map(|ty| ty.name())
+///     .collect();
+/// assert_eq!(user_types, ["User", "Query"]);
+/// assert!(schema.type_by_name("ID").unwrap().is_built_in());
+/// ```
+#[derive(Debug, Default)]
+pub struct SchemaBuilder {
+    sources: Vec<Source>,
+    scalar_validators: ScalarValidators,
+}
+
+impl SchemaBuilder {
+    /// Create a new, empty `SchemaBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a type system document. `name` identifies the document in
+    /// diagnostics, and is usually a file path.
+    pub fn add_document(&mut self, name: impl Into<String>, input: impl Into<String>) {
+        self.sources.push(Source::new(name, input));
+    }
+
+    /// Check the values of the custom scalar called `name` with `validator`
+    /// during validation. The validator returns why a value is invalid, like
+    /// "expected a string". Values of custom scalars without a validator are
+    /// not checked.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_compiler::{hir::Value, DiagnosticKind, SchemaBuilder};
+    ///
+    /// let mut builder = SchemaBuilder::new();
+    /// builder.add_document(
+    ///     "schema.graphql",
+    ///     r#"
+    /// scalar FieldSet
+    /// directive @key(fields: FieldSet!) on OBJECT
+    /// type Query { me: User }
+    /// type User @key(fields: 123) { id: ID! }
+    /// "#,
+    /// );
+    /// builder.add_scalar_validator("FieldSet", |value| match value {
+    ///     Value::String(_) => Ok(()),
+    ///     _ => Err("a field set must be a string".to_string()),
+    /// });
+    /// let schema = builder.build();
+    ///
+    /// let diagnostics = schema.validate();
+    /// assert_eq!(diagnostics[0].kind(), &DiagnosticKind::InvalidValue);
+    /// assert_eq!(
+    ///     diagnostics[0].message(),
+    ///     "invalid value for the argument `fields` of `@key`: a field set must be a string"
+    /// );
+    /// ```
+    pub fn add_scalar_validator(
+        &mut self,
+        name: impl Into<String>,
+        validator: impl Fn(&Value) -> Result<(), String> + Send + Sync + 'static,
+    ) {
+        self.scalar_validators
+            .0
+            .insert(name.into(), Arc::new(validator));
+    }
+
+    /// Build the schema from all documents added so far.
+    pub fn build(self) -> Schema {
+        let built_in = Source::new("built_in.graphql", BUILT_IN);
+        let built_in_tree = Parser::new(BUILT_IN).parse();
+        debug_assert_eq!(built_in_tree.errors().len(), 0);
+
+        let mut diagnostics = Vec::new();
+        let trees: Vec<(SourceId, SyntaxTree)> = self
+            .sources
+            .iter()
+            .map(|source| {
+                let tree = Parser::new(source.text())
+                    .mode(ParseMode::TypeSystem)
+                    .parse();
+                diagnostics.extend(syntax_errors(source.id(), &tree));
+                (source.id(), tree)
+            })
+            .collect();
+
+        let mut schema = Schema {
+            sources: Vec::new(),
+            definition_span: None,
+            description: None,
+            directives: Vec::new(),
+            root_operations: IndexMap::new(),
+            types: IndexMap::new(),
+            directive_definitions: IndexMap::new(),
+            meta_fields: Vec::new(),
+            implementers: HashMap::new(),
+            scalar_validators: self.scalar_validators,
+            diagnostics,
+        };
+
+        let mut lowering = Lowering {
+            schema: &mut schema,
+            source: built_in.id(),
+            built_in: true,
+        };
+        for definition in built_in_tree.document().definitions() {
+            lowering.definition(definition);
+        }
+        if let Some(TypeDefinition::Object(meta)) = schema.types.shift_remove(META_FIELDS) {
+            schema.meta_fields = meta.fields;
+        }
+
+        // Extensions can come before the definition they extend, so all
+        // definitions are collected first.
+        for (source, tree) in &trees {
+            let mut lowering = Lowering {
+                schema: &mut schema,
+                source: *source,
+                built_in: false,
+            };
+            for definition in tree.document().definitions() {
+                lowering.definition(definition);
+            }
+        }
+        for (source, tree) in &trees {
+            let mut lowering = Lowering {
+                schema: &mut schema,
+                source: *source,
+                built_in: false,
+            };
+            for definition in tree.document().definitions() {
+                lowering.extension(definition);
+            }
+        }
+
+        schema.sources = std::iter::once(built_in).chain(self.sources).collect();
+        if schema.definition_span.is_none() && schema.root_operations.is_empty() {
+            for operation_type in [
+                OperationType::Query,
+                OperationType::Mutation,
+                OperationType::Subscription,
+            ] {
+                let name = operation_type.default_type_name();
+                if schema.object_type(name).is_some() {
+                    schema
+                        .root_operations
+                        .insert(operation_type, name.to_string());
+                }
+            }
+        }
+        for ty in schema.types.values() {
+            for interface in ty.implements_interfaces() {
+                schema
+                    .implementers
+                    .entry(interface.clone())
+                    .or_default()
+                    .push(ty.name().to_string());
+            }
+        }
+
+        schema
+    }
+}
+
+/// Create a diagnostic for a user definition of a built-in `item`, like
+/// "type `String`".
+fn built_in_redefinition(item: String, span: Span) -> Diagnostic {
+    Diagnostic::new(
+        DiagnosticKind::BuiltInRedefinition,
+        format!("the built-in {} cannot be redefined", item),
+    )
+    .label(span, format!("{} redefined here", item))
+}
+
+struct Lowering<'a> {
+    schema: &'a mut Schema,
+    /// The source of the document being lowered.
+    source: SourceId,
+    /// Whether the document being lowered is the built-in one.
+    built_in: bool,
+}
+
+impl Lowering<'_> {
+    fn span(&self, node: &impl AstNode) -> Span {
+        Span::of(self.source, node)
+    }
+
+    fn definition(&mut self, definition: ast::Definition) {
+        let ty = match definition {
+            ast::Definition::SchemaDefinition(def) => {
+                let span = self.span(&def);
+                if let Some(previous) = self.schema.definition_span {
+                    self.schema.diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticKind::DuplicateDefinition,
+                            "the schema is defined multiple times",
+                        )
+                        .label(span, "schema redefined here")
+                        .label(previous, "previous definition of the schema here"),
+                    );
+                    return;
+                }
+                self.schema.definition_span = Some(span);
+                self.schema.description = description(def.description());
+                self.schema_definition(def.directives(), def.root_operation_type_definitions());
+                return;
+            }
+            ast::Definition::DirectiveDefinition(def) => {
+                self.directive_definition(def);
+                return;
+            }
+            ast::Definition::ScalarTypeDefinition(def) => self
+                .common(def.description(), def.name(), def.directives(), &def)
+                .map(|common| TypeDefinition::Scalar(ScalarType { common })),
+            ast::Definition::ObjectTypeDefinition(def) => self
+                .common(def.description(), def.name(), def.directives(), &def)
+                .map(|common| {
+                    TypeDefinition::Object(ObjectType {
+                        common,
+                        implements_interfaces: self.implements(def.implements_interfaces()),
+                        fields: self.fields(def.fields_definition()),
+                    })
+                }),
+            ast::Definition::InterfaceTypeDefinition(def) => self
+                .common(def.description(), def.name(), def.directives(), &def)
+                .map(|common| {
+                    TypeDefinition::Interface(InterfaceType {
+                        common,
+                        implements_interfaces: self.implements(def.implements_interfaces()),
+                        fields: self.fields(def.fields_definition()),
+                    })
+                }),
+            ast::Definition::UnionTypeDefinition(def) => self
+                .common(def.description(), def.name(), def.directives(), &def)
+                .map(|common| {
+                    TypeDefinition::Union(UnionType {
+                        common,
+                        members: self.members(def.union_member_types()),
+                    })
+                }),
+            ast::Definition::EnumTypeDefinition(def) => self
+                .common(def.description(), def.name(), def.directives(), &def)
+                .map(|common| {
+                    TypeDefinition::Enum(EnumType {
+                        common,
+                        values: self.enum_values(def.enum_values_definition()),
+                    })
+                }),
+            ast::Definition::InputObjectTypeDefinition(def) => self
+                .common(def.description(), def.name(), def.directives(), &def)
+                .map(|common| {
+                    TypeDefinition::InputObject(InputObjectType {
+                        common,
+                        fields: self.input_fields(def.input_fields_definition()),
+                    })
+                }),
+            // Extensions are lowered once all definitions are known.
+            _ => None,
+        };
+
+        if let Some(ty) = ty {
+            if let Some(previous) = self.schema.types.get(ty.name()) {
+                if previous.is_built_in() {
+                    self.schema.diagnostics.push(built_in_redefinition(
+                        format!("type `{}`", ty.name()),
+                        ty.span(),
+                    ));
+                    return;
+                }
+                let diagnostic = Diagnostic::new(
+                    DiagnosticKind::DuplicateDefinition,
+                    format!("the type `{}` is defined multiple times", ty.name()),
+                )
+                .label(ty.span(), format!("`{}` redefined here", ty.name()))
+                .label(
+                    previous.span(),
+                    format!("previous definition of `{}` here", ty.name()),
+                );
+                self.schema.diagnostics.push(diagnostic);
+                return;
+            }
+            self.schema.types.insert(ty.name().to_string(), ty);
+        }
+    }
+
+    fn extension(&mut self, definition: ast::Definition) {
+        match definition {
+            ast::Definition::SchemaExtension(def) => {
+                self.schema_definition(def.directives(), def.root_operation_type_definitions());
+            }
+            ast::Definition::ScalarTypeExtension(def) => {
+                let directives = self.directives(def.directives());
+                if let Some(ty) = self.extend(def.name(), &def, "scalar") {
+                    ty.common_mut().directives.extend(directives);
+                }
+            }
+            ast::Definition::ObjectTypeExtension(def) => {
+                let directives = self.directives(def.directives());
+                let interfaces = self.implements(def.implements_interfaces());
+                let fields = self.fields(def.fields_definition());
+                if let Some(TypeDefinition::Object(ty)) = self.extend(def.name(), &def, "type") {
+                    ty.common.directives.extend(directives);
+                    ty.implements_interfaces.extend(interfaces);
+                    ty.fields.extend(fields);
+                }
+            }
+            ast::Definition::InterfaceTypeExtension(def) => {
+                let directives = self.directives(def.directives());
+                let interfaces = self.implements(def.implements_interfaces());
+                let fields = self.fields(def.fields_definition());
+                if let Some(TypeDefinition::Interface(ty)) =
+                    self.extend(def.name(), &def, "interface")
+                {
+                    ty.common.directives.extend(directives);
+                    ty.implements_interfaces.extend(interfaces);
+                    ty.fields.extend(fields);
+                }
+            }
+            ast::Definition::UnionTypeExtension(def) => {
+                let directives = self.directives(def.directives());
+                let members = self.members(def.union_member_types());
+                if let Some(TypeDefinition::Union(ty)) = self.extend(def.name(), &def, "union") {
+                    ty.common.directives.extend(directives);
+                    ty.members.extend(members);
+                }
+            }
+            ast::Definition::EnumTypeExtension(def) => {
+                let directives = self.directives(def.directives());
+                let values = self.enum_values(def.enum_values_definition());
+                if let Some(TypeDefinition::Enum(ty)) = self.extend(def.name(), &def, "enum") {
+                    ty.common.directives.extend(directives);
+                    ty.values.extend(values);
+                }
+            }
+            ast::Definition::InputObjectTypeExtension(def) => {
+                let directives = self.directives(def.directives());
+                let fields = self.input_fields(def.input_fields_definition());
+                if let Some(TypeDefinition::InputObject(ty)) =
+                    self.extend(def.name(), &def, "input")
+                {
+                    ty.common.directives.extend(directives);
+                    ty.fields.extend(fields);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record an extension of the type called `name` with the given keyword,
+    /// and return the type to add the extension's items to. Reports a
+    /// diagnostic and returns `None` if the extension does not apply.
+    fn extend(
+        &mut self,
+        name: Option<ast::Name>,
+        node: &impl AstNode,
+        keyword: &str,
+    ) -> Option<&mut TypeDefinition> {
+        let name = name?.text().to_string();
+        let span = self.span(node);
+        match self.schema.types.get_mut(&name) {
+            Some(ty) if ty.keyword() == keyword => {
+                ty.common_mut().extensions.push(span);
+                Some(ty)
+            }
+            Some(ty) => {
+                let diagnostic = Diagnostic::new(
+                    DiagnosticKind::ExtensionKindMismatch,
+                    format!(
+                        "`extend {}` cannot extend `{}`, which is defined with `{}`",
+                        keyword,
+                        name,
+                        ty.keyword()
+                    ),
+                )
+                .label(span, format!("extension of `{}` here", name))
+                .label(ty.span(), format!("`{}` defined here", name));
+                self.schema.diagnostics.push(diagnostic);
+                None
+            }
+            None => {
+                let diagnostic = Diagnostic::new(
+                    DiagnosticKind::UndefinedExtensionTarget,
+                    format!("cannot extend the undefined type `{}`", name),
+                )
+                .label(span, format!("extension of `{}` here", name));
+                self.schema.diagnostics.push(diagnostic);
+                None
+            }
+        }
+    }
+
+    fn schema_definition(
+        &mut self,
+        directives: Option<ast::Directives>,
+        root_operations: ast::AstChildren<ast::RootOperationTypeDefinition>,
+    ) {
+        let directives = self.directives(directives);
+        self.schema.directives.extend(directives);
+        for root_operation in root_operations {
+            let operation_type = root_operation.operation_type().and_then(operation_type);
+            let name = root_operation
+                .named_type()
+                .and_then(|ty| ty.name())
+                .map(|name| name.text().to_string());
+            if let (Some(operation_type), Some(name)) = (operation_type, name) {
+                if self.schema.root_operations.contains_key(&operation_type) {
+                    self.schema.diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticKind::DuplicateDefinition,
+                            format!(
+                                "the {} root operation type is defined multiple times",
+                                operation_type
+                            ),
+                        )
+                        .label(
+                            self.span(&root_operation),
+                            format!("{} root operation type redefined here", operation_type),
+                        ),
+                    );
+                    continue;
+                }
+                self.schema.root_operations.insert(operation_type, name);
+            }
+        }
+    }
+
+    fn directive_definition(&mut self, def: ast::DirectiveDefinition) {
+        let name = match def.name() {
+            Some(name) => name.text().to_string(),
+            None => return,
+        };
+        let span = self.span(&def);
+        if let Some(previous) = self.schema.directive_definitions.get(&name) {
+            if previous.built_in {
+                self.schema.diagnostics.push(built_in_redefinition(
+                    format!("directive `@{}`", name),
+                    span,
+                ));
+                return;
+            }
+            let diagnostic = Diagnostic::new(
+                DiagnosticKind::DuplicateDefinition,
+                format!("the directive `@{}` is defined multiple times", name),
+            )
+            .label(span, format!("`@{}` redefined here", name))
+            .label(
+                previous.span,
+                format!("previous definition of `@{}` here", name),
+            );
+            self.schema.diagnostics.push(diagnostic);
+            return;
+        }
+
+        let locations = def
+            .directive_locations()
+            .into_iter()
+            .flat_map(|locations| locations.directive_locations())
+            .filter_map(|location| {
+                let token = location.syntax().first_token()?;
+                DirectiveLocation::from_name(token.text())
+            })
+            .collect();
+        let definition = DirectiveDefinition {
+            description: description(def.description()),
+            name: name.clone(),
+            arguments: self.arguments_definition(def.arguments_definition()),
+            repeatable: def.repeatable_token().is_some(),
+            locations,
+            span,
+            built_in: self.built_in,
+        };
+        self.schema.directive_definitions.insert(name, definition);
+    }
+
+    fn common(
+        &self,
+        description: Option<ast::Description>,
+        name: Option<ast::Name>,
+        directives: Option<ast::Directives>,
+        node: &impl AstNode,
+    ) -> Option<TypeCommon> {
+        Some(TypeCommon {
+            description: self::description(description),
+            name: name?.text().to_string(),
+            directives: self.directives(directives),
+            span: self.span(node),
+            extensions: Vec::new(),
+            built_in: self.built_in,
+        })
+    }
+
+    fn implements(&self, implements: Option<ast::ImplementsInterfaces>) -> Vec<String> {
+        implements
+            .into_iter()
+            .flat_map(|implements| implements.named_types())
+            .filter_map(|ty| Some(ty.name()?.text().to_string()))
+            .collect()
+    }
+
+    fn members(&self, members: Option<ast::UnionMemberTypes>) -> Vec<String> {
+        members
+            .into_iter()
+            .flat_map(|members| members.named_types())
+            .filter_map(|ty| Some(ty.name()?.text().to_string()))
+            .collect()
+    }
+
+    fn fields(&self, fields: Option<ast::FieldsDefinition>) -> Vec<FieldDefinition> {
+        fields
+            .into_iter()
+            .flat_map(|fields| fields.field_definitions())
+            .filter_map(|field| {
+                Some(FieldDefinition {
+                    description: description(field.description()),
+                    name: field.name()?.text().to_string(),
+                    arguments: self.arguments_definition(field.arguments_definition()),
+                    ty: ty(field.ty()?)?,
+                    directives: self.directives(field.directives()),
+                    span: self.span(&field),
+                })
+            })
+            .collect()
+    }
+
+    fn arguments_definition(
+        &self,
+        arguments: Option<ast::ArgumentsDefinition>,
+    ) -> Vec<InputValueDefinition> {
+        arguments
+            .into_iter()
+            .flat_map(|arguments| arguments.input_value_definitions())
+            .filter_map(|def| self.input_value(def))
+            .collect()
+    }
+
+    fn input_fields(
+        &self,
+        fields: Option<ast::InputFieldsDefinition>,
+    ) -> Vec<InputValueDefinition> {
+        fields
+            .into_iter()
+            .flat_map(|fields| fields.input_value_definitions())
+            .filter_map(|def| self.input_value(def))
+            .collect()
+    }
+
+    fn input_value(&self, def: ast::InputValueDefinition) -> Option<InputValueDefinition> {
+        Some(InputValueDefinition {
+            description: description(def.description()),
+            name: def.name()?.text().to_string(),
+            ty: ty(def.ty()?)?,
+            default_value: def
+                .default_value()
+                .and_then(|default| value(default.value()?)),
+            directives: self.directives(def.directives()),
+            span: self.span(&def),
+        })
+    }
+
+    fn enum_values(&self, values: Option<ast::EnumValuesDefinition>) -> Vec<EnumValueDefinition> {
+        values
+            .into_iter()
+            .flat_map(|values| values.enum_value_definitions())
+            .filter_map(|def| {
+                Some(EnumValueDefinition {
+                    description: description(def.description()),
+                    value: def.enum_value()?.text().to_string(),
+                    directives: self.directives(def.directives()),
+                    span: self.span(&def),
+                })
+            })
+            .collect()
+    }
+
+    fn directives(&self, directives: Option<ast::Directives>) -> Vec<Directive> {
+        self::directives(self.source, directives)
+    }
+}
+
+/// Lower a description. Strings that cannot be decoded are reported when
+/// the document is parsed.
+fn description(description: Option<ast::Description>) -> Option<String> {
+    Some(description?.value().ok()?.into_owned())
+}
+
+pub(crate) fn operation_type(operation_type: ast::OperationType) -> Option<OperationType> {
+    if operation_type.query_token().is_some() {
+        Some(OperationType::Query)
+    } else if operation_type.mutation_token().is_some() {
+        Some(OperationType::Mutation)
+    } else if operation_type.subscription_token().is_some() {
+        Some(OperationType::Subscription)
+    } else {
+        None
+    }
+}
+
+/// Lower a type reference. Returns `None` if it is incomplete because of a
+/// syntax error.
+pub(crate) fn ty(ty: ast::Type) -> Option<Type> {
+    Some(match ty {
+        ast::Type::NamedType(named) => Type::Named(named.name()?.text().to_string()),
+        ast::Type::ListType(list) => Type::List(Box::new(self::ty(list.ty()?)?)),
+        ast::Type::NonNullType(non_null) => {
+            let inner = match (non_null.named_type(), non_null.list_type()) {
+                (Some(named), _) => ast::Type::NamedType(named),
+                (None, Some(list)) => ast::Type::ListType(list),
+                (None, None) => return None,
+            };
+            Type::NonNull(Box::new(self::ty(inner)?))
+        }
+    })
+}
+
+/// Lower the directives applied to a definition or selection in `source`.
+pub(crate) fn directives(source: SourceId, directives: Option<ast::Directives>) -> Vec<Directive> {
+    directives
+        .into_iter()
+        .flat_map(|directives| directives.directives())
+        .filter_map(|directive| {
+            let arguments = directive
+                .arguments()
+                .into_iter()
+                .flat_map(|arguments| arguments.arguments())
+                .filter_map(|arg| {
+                    Some(Argument {
+                        name: arg.name()?.text().to_string(),
+                        value: value(arg.value()?)?,
+                        span: Span::of(source, &arg),
+                    })
+                })
+                .collect();
+            Some(Directive {
+                name: directive.name()?.text().to_string(),
+                arguments,
+                span: Span::of(source, &directive),
+            })
+        })
+        .collect()
+}
+
+/// Lower a value. Returns `None` if it is incomplete because of a syntax
+/// error, or if it is a string that cannot be decoded, which is reported when
+/// the document is parsed.
+pub(crate) fn value(value: ast::Value) -> Option<Value> {
+    Some(match value {
+        ast::Value::Variable(var) => Value::Variable(var.name()?.text().to_string()),
+        ast::Value::IntValue(int) => Value::Int(int.text().to_string()),
+        ast::Value::FloatValue(float) => Value::Float(float.text().to_string()),
+        ast::Value::StringValue(string) => Value::String(string.value().ok()?.into_owned()),
+        ast::Value::BooleanValue(boolean) => Value::Boolean(boolean.into()),
+        ast::Value::NullValue(_) => Value::Null,
+        ast::Value::EnumValue(enum_value) => Value::Enum(enum_value.text().to_string()),
+        ast::Value::ListValue(list) => Value::List(list.values().filter_map(self::value).collect()),
+        ast::Value::ObjectValue(object) => Value::Object(
+            object
+                .object_fields()
+                .filter_map(|field| {
+                    Some((
+                        field.name()?.text().to_string(),
+                        self::value(field.value()?)?,
+                    ))
+                })
+                .collect(),
+        ),
+    })
+}
@@ -0,0 +1,540 @@
+pub(crate) mod builder;
+mod printer;
+
+use std::{collections::HashMap, fmt, slice::Iter, sync::Arc};
+
+use indexmap::IndexMap;
+
+use crate::{
+    hir::{
+        Directive, DirectiveDefinition, FieldDefinition, ObjectType, OperationType, Type,
+        TypeDefinition, Value,
+    },
+    Diagnostic, Source, SourceId, Span,
+};
+
+pub use builder::SchemaBuilder;
+
+/// The semantic model of a GraphQL schema.
+///
+/// A schema is built from one or more type system documents. Type extensions
+/// are merged into the types they extend, so that looking up a type returns
+/// all of its fields, values, members and directives, whichever document
+/// they were defined in.
+///
+/// Building a schema never fails. Definitions that cannot be part of the
+/// schema, like a second definition of a type, are reported as diagnostics
+/// and left out.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{hir::OperationType, SchemaBuilder};
+///
+/// let mut builder = SchemaBuilder::new();
+/// builder.add_document(
+///     "schema.graphql",
+///     "type Query { me: User } type User implements Node { id: ID! }",
+/// );
+/// builder.add_document(
+///     "extensions.graphql",
+///     "interface Node { id: ID! } extend type User { name: String }",
+/// );
+/// let schema = builder.build();
+/// assert_eq!(schema.diagnostics().len(), 0);
+///
+/// let query = schema.root_operation(OperationType::Query).unwrap();
+/// let me = query.field("me").unwrap();
+/// let user = schema.resolve_type(me.ty()).unwrap();
+///
+/// let fields: Vec<_> = schema.fields_of(user.name()).iter().map(|f| f.name()).collect();
+/// assert_eq!(fields, ["id", "name"]);
+/// assert_eq!(schema.possible_types("Node")[0].name(), "User");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub(crate) sources: Vec<Source>,
+    pub(crate) definition_span: Option<Span>,
+    pub(crate) description: Option<String>,
+    pub(crate) directives: Vec<Directive>,
+    pub(crate) root_operations: IndexMap<OperationType, String>,
+    pub(crate) types: IndexMap<String, TypeDefinition>,
+    pub(crate) directive_definitions: IndexMap<String, DirectiveDefinition>,
+    /// The definitions of `__typename`, `__schema` and `__type`.
+    pub(crate) meta_fields: Vec<FieldDefinition>,
+    /// The names of the object and interface types that implement each
+    /// interface.
+    pub(crate) implementers: HashMap<String, Vec<String>>,
+    pub(crate) scalar_validators: ScalarValidators,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+/// A function that checks a value of a custom scalar, and returns why it is
+/// invalid if it is.
+pub(crate) type ScalarValidator = dyn Fn(&Value) -> Result<(), String> + Send + Sync;
+
+/// The functions that check values of custom scalars, by scalar name.
+#[derive(Clone, Default)]
+pub(crate) struct ScalarValidators(pub(crate) HashMap<String, Arc<ScalarValidator>>);
+
+impl fmt::Debug for ScalarValidators {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+impl Schema {
+    /// Build a schema from a single type system document.
+    pub fn parse(input: &str) -> Self {
+        let mut builder = SchemaBuilder::new();
+        builder.add_document("schema.graphql", input);
+        builder.build()
+    }
+
+    /// Get the problems found while building the schema, including syntax
+    /// errors.
+    ///
+    /// These do not include the problems found by validation, see
+    /// [`Schema::validate`].
+    pub fn diagnostics(&self) -> Iter<'_, Diagnostic> {
+        self.diagnostics.iter()
+    }
+
+    /// Validate the schema against the type system rules of the
+    /// specification, and return all problems found, starting with the ones
+    /// found while building the schema.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_compiler::{DiagnosticKind, Schema};
+    ///
+    /// let schema = Schema::parse(
+    ///     "type Query implements Node { id: String } interface Node { id: ID! }",
+    /// );
+    /// assert_eq!(schema.diagnostics().len(), 0);
+    ///
+    /// let diagnostics = schema.validate();
+    /// assert_eq!(diagnostics[0].kind(), &DiagnosticKind::InvalidImplementation);
+    /// assert_eq!(
+    ///     diagnostics[0].message(),
+    ///     "the type of `Query.id` must be `ID!` or a subtype of it, but it is `String`"
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.diagnostics.clone();
+        diagnostics.extend(crate::validation::validate_schema(self));
+        diagnostics
+    }
+
+    /// Get the sources the schema was built from. The first source is the
+    /// one the built-in types and directives are defined in.
+    pub fn sources(&self) -> Iter<'_, Source> {
+        self.sources.iter()
+    }
+
+    /// Get the source with the given id, for example to compute the line and
+    /// column of a diagnostic's span.
+    pub fn source(&self, id: SourceId) -> Option<&Source> {
+        self.sources.iter().find(|source| source.id() == id)
+    }
+
+    /// Get the span of the schema definition, if there is one.
+    pub fn definition_span(&self) -> Option<Span> {
+        self.definition_span
+    }
+
+    /// Get the description of the schema definition, if there is one.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the directives applied to the schema definition and its
+    /// extensions.
+    pub fn schema_directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    /// Get all types, in the order they were defined in, starting with the
+    /// built-in types.
+    pub fn types(&self) -> impl Iterator<Item = &TypeDefinition> {
+        self.types.values()
+    }
+
+    /// Get the type called `name`.
+    pub fn type_by_name(&self, name: &str) -> Option<&TypeDefinition> {
+        self.types.get(name)
+    }
+
+    /// Get the object type called `name`, or `None` if there is no such type
+    /// or it is not an object type.
+    pub fn object_type(&self, name: &str) -> Option<&ObjectType> {
+        match self.types.get(name)? {
+            TypeDefinition::Object(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
+    /// Get the named type a type reference like `[User!]` points to.
+    pub fn resolve_type(&self, ty: &Type) -> Option<&TypeDefinition> {
+        self.type_by_name(ty.name())
+    }
+
+    /// Get the fields of the object or interface type called `type_name`,
+    /// including the fields of its extensions. Other types have no fields.
+    pub fn fields_of(&self, type_name: &str) -> &[FieldDefinition] {
+        self.type_by_name(type_name)
+            .map(TypeDefinition::fields)
+            .unwrap_or_default()
+    }
+
+    /// Get the definition of the field `field_name` of the type called
+    /// `type_name`.
+    ///
+    /// Unlike `fields_of`, this includes the meta-fields: `__typename` of
+    /// object, interface and union types, and `__schema` and `__type` of the
+    /// query root type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_compiler::Schema;
+    ///
+    /// let schema = Schema::parse("type Query { me: String } union U = Query");
+    ///
+    /// assert_eq!(schema.field("Query", "__schema").unwrap().ty().to_string(), "__Schema!");
+    /// assert!(schema.field("U", "__typename").is_some());
+    /// assert!(schema.field("U", "__schema").is_none());
+    /// ```
+    pub fn field(&self, type_name: &str, field_name: &str) -> Option<&FieldDefinition> {
+        if field_name.starts_with("__") {
+            return self.meta_field(type_name, field_name);
+        }
+        self.fields_of(type_name)
+            .iter()
+            .find(|field| field.name() == field_name)
+    }
+
+    fn meta_field(&self, type_name: &str, field_name: &str) -> Option<&FieldDefinition> {
+        let is_query_root = || self.root_operation_name(OperationType::Query) == Some(type_name);
+        let applies = match field_name {
+            "__typename" => self.type_by_name(type_name)?.is_composite_type(),
+            "__schema" | "__type" => is_query_root(),
+            _ => false,
+        };
+        if !applies {
+            return None;
+        }
+        self.meta_fields
+            .iter()
+            .find(|field| field.name() == field_name)
+    }
+
+    /// Get the object and interface types that directly implement the
+    /// interface called `interface`.
+    pub fn implementers_of(&self, interface: &str) -> impl Iterator<Item = &TypeDefinition> {
+        self.implementers
+            .get(interface)
+            .into_iter()
+            .flatten()
+            .filter_map(|name| self.types.get(name))
+    }
+
+    /// Get the object types a value of the type called `name` can have at
+    /// runtime: the members of a union, the object types implementing an
+    /// interface, or the object type itself.
+    pub fn possible_types(&self, name: &str) -> Vec<&ObjectType> {
+        match self.types.get(name) {
+            Some(TypeDefinition::Object(ty)) => vec![ty],
+            Some(TypeDefinition::Union(ty)) => ty
+                .members()
+                .iter()
+                .filter_map(|member| self.object_type(member))
+                .collect(),
+            Some(TypeDefinition::Interface(_)) => self
+                .implementers_of(name)
+                .filter_map(|ty| match ty {
+                    TypeDefinition::Object(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get the name of the root type of an operation type.
+    ///
+    /// This is declared by the schema definition if there is one, and is
+    /// otherwise the type named after the operation type, like `Query`, if
+    /// that type exists.
+    pub fn root_operation_name(&self, operation_type: OperationType) -> Option<&str> {
+        self.root_operations
+            .get(&operation_type)
+            .map(String::as_str)
+    }
+
+    /// Get the root type of an operation type.
+    pub fn root_operation(&self, operation_type: OperationType) -> Option<&ObjectType> {
+        self.object_type(self.root_operation_name(operation_type)?)
+    }
+
+    /// Get all directive definitions, in the order they were defined in,
+    /// starting with the built-in directives.
+    pub fn directive_definitions(&self) -> impl Iterator<Item = &DirectiveDefinition> {
+        self.directive_definitions.values()
+    }
+
+    /// Get the definition of the directive called `name`, without the `@`.
+    pub fn directive_definition(&self, name: &str) -> Option<&DirectiveDefinition> {
+        self.directive_definitions.get(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{hir::OperationType, DiagnosticKind, Schema, SchemaBuilder};
+
+    #[test]
+    fn it_merges_extensions() {
+        let schema = Schema::parse(
+            r#"
+extend type Query @tag(name: "extended") { b: Int }
+type Query implements Node { id: ID! a: Int }
+extend type Query implements Named { name: String }
+interface Node { id: ID! }
+interface Named { name: String }
+enum Color { RED }
+extend enum Color { GREEN }
+union Result = Query
+extend union Result = Other
+type Other { a: Int }
+input Point { x: Float }
+extend input Point { y: Float }
+scalar Date
+extend scalar Date @specifiedBy(url: "https://example.com")
+"#,
+        );
+        assert_eq!(schema.diagnostics().len(), 0);
+
+        let query = schema.object_type("Query").unwrap();
+        let fields: Vec<_> = query.fields().iter().map(|f| f.name()).collect();
+        assert_eq!(fields, ["id", "a", "b", "name"]);
+        assert_eq!(query.implements_interfaces(), ["Node", "Named"]);
+        assert_eq!(query.directives()[0].name(), "tag");
+        assert_eq!(query.extensions().len(), 2);
+
+        let ty = schema.type_by_name("Color").unwrap();
+        assert_eq!(ty.extensions().len(), 1);
+        assert_eq!(schema.possible_types("Result").len(), 2);
+        assert_eq!(schema.type_by_name("Date").unwrap().directives().len(), 1);
+        assert!(matches!(
+            schema.type_by_name("Point"),
+            Some(crate::hir::TypeDefinition::InputObject(ty)) if ty.fields().len() == 2
+        ));
+    }
+
+    #[test]
+    fn it_finds_root_operations() {
+        let schema = Schema::parse("type Query { a: Int } type Mutation { b: Int }");
+        assert_eq!(
+            schema.root_operation(OperationType::Query).unwrap().name(),
+            "Query"
+        );
+        assert!(schema.root_operation(OperationType::Subscription).is_none());
+
+        let schema = Schema::parse(
+            "schema { query: Root } extend schema { mutation: Root } type Root { a: Int } type Query { a: Int }",
+        );
+        assert_eq!(
+            schema.root_operation_name(OperationType::Query),
+            Some("Root")
+        );
+        assert_eq!(
+            schema.root_operation_name(OperationType::Mutation),
+            Some("Root")
+        );
+        assert!(schema.definition_span().is_some());
+    }
+
+    #[test]
+    fn it_finds_implementers_and_possible_types() {
+        let schema = Schema::parse(
+            r#"
+interface Node { id: ID! }
+interface Resource implements Node { id: ID! }
+type User implements Node { id: ID! }
+type File implements Resource & Node { id: ID! }
+"#,
+        );
+        let implementers: Vec<_> = schema.implementers_of("Node").map(|t| t.name()).collect();
+        assert_eq!(implementers, ["Resource", "User", "File"]);
+        let possible: Vec<_> = schema
+            .possible_types("Node")
+            .iter()
+            .map(|t| t.name())
+            .collect();
+        assert_eq!(possible, ["User", "File"]);
+        assert_eq!(schema.possible_types("User")[0].name(), "User");
+    }
+
+    #[test]
+    fn it_reports_invalid_definitions() {
+        let mut builder = SchemaBuilder::new();
+        builder.add_document("a.graphql", "type A { a: Int } extend type B { b: Int }");
+        builder.add_document(
+            "b.graphql",
+            "type A { b: Int } extend enum A { C } directive @a on FIELD directive @a on FIELD",
+        );
+        builder.add_document("c.graphql", "type C { c: Int");
+        let schema = builder.build();
+
+        let kinds: Vec<_> = schema.diagnostics().map(|d| d.kind().clone()).collect();
+        assert_eq!(
+            kinds,
+            [
+                DiagnosticKind::SyntaxError,
+                DiagnosticKind::DuplicateDefinition,
+                DiagnosticKind::DuplicateDefinition,
+                DiagnosticKind::UndefinedExtensionTarget,
+                DiagnosticKind::ExtensionKindMismatch,
+            ]
+        );
+        // The first definition wins.
+        assert_eq!(schema.fields_of("A")[0].name(), "a");
+
+        let duplicate = schema.diagnostics().nth(1).unwrap();
+        let span = duplicate.span().unwrap();
+        let source = schema.source(span.source()).unwrap();
+        assert_eq!(source.name(), "b.graphql");
+        assert_eq!(&source.text()[span.range()], "type A { b: Int }");
+    }
+
+    #[test]
+    fn it_reports_descriptions_on_operations() {
+        let schema = Schema::parse(r#""a" { b } type Query { a: Int }"#);
+
+        let messages: Vec<_> = schema.diagnostics().map(|d| d.message()).collect();
+        assert!(messages.contains(&"descriptions are not allowed on operations"));
+        assert!(schema.object_type("Query").is_some());
+    }
+
+    #[test]
+    fn it_builds_schemas_with_syntax_errors() {
+        let inputs = [
+            "schema { query: }",
+            "type { a: Int }",
+            "type Query { a(: Int): Int }",
+            "type Query { : Int }",
+            "type Query implements & { a: Int }",
+            "interface I { a: [ }",
+            "enum E { A @ }",
+            "union U = | ",
+            "input I { a: Int = { : 1 } }",
+            "type Query { a(b: [Int] = [$]): Int }",
+            "directive @ on FIELD",
+            "extend type { a: Int }",
+            "extend schema @ { query: }",
+            "scalar",
+        ];
+        for input in inputs {
+            let schema = Schema::parse(input);
+            assert!(
+                schema
+                    .diagnostics()
+                    .any(|d| d.kind() == &DiagnosticKind::SyntaxError),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn it_reports_strings_with_invalid_escape_sequences_once() {
+        let input = r#"
+"\uD800"
+type Query {
+  a(b: String = "\q"): Int
+  "\u{110000}" c: Int
+}
+"#;
+        let schema = Schema::parse(input);
+
+        let errors: Vec<_> = schema
+            .diagnostics()
+            .filter(|d| d.message().contains("escape"))
+            .map(|d| &input[d.span().unwrap().range()])
+            .collect();
+        assert_eq!(errors, [r#""\uD800""#, r#""\q""#, r#""\u{110000}""#]);
+        assert!(schema
+            .diagnostics()
+            .all(|d| d.kind() == &DiagnosticKind::SyntaxError));
+    }
+
+    #[test]
+    fn it_keeps_spans_of_items() {
+        let input = "type Query {\n  \"The user\"\n  me(id: ID = 1): User @deprecated\n}";
+        let schema = Schema::parse(input);
+        let me = schema.field("Query", "me").unwrap();
+        assert_eq!(me.description(), Some("The user"));
+        assert_eq!(
+            &input[me.span().range()],
+            "\"The user\"\n  me(id: ID = 1): User @deprecated"
+        );
+        assert_eq!(&input[me.arguments()[0].span().range()], "id: ID = 1");
+        assert_eq!(&input[me.directives()[0].span().range()], "@deprecated");
+    }
+
+    #[test]
+    fn it_provides_built_ins() {
+        let schema = Schema::parse("type Query { me: String } type User { id: ID! }");
+        assert_eq!(schema.diagnostics().len(), 0);
+
+        for name in [
+            "Int", "Float", "String", "Boolean", "ID", "__Schema", "__Type",
+        ] {
+            assert!(schema.type_by_name(name).unwrap().is_built_in(), "{}", name);
+        }
+        assert!(!schema.type_by_name("Query").unwrap().is_built_in());
+
+        let deprecated = schema.directive_definition("deprecated").unwrap();
+        assert!(deprecated.is_built_in());
+        assert_eq!(
+            deprecated.argument("reason").unwrap().default_value(),
+            Some(&crate::hir::Value::String("No longer supported".into()))
+        );
+        assert!(schema.directive_definition("specifiedBy").is_some());
+
+        assert!(schema.field("__Type", "specifiedByURL").is_some());
+        assert!(schema.field("Query", "__type").is_some());
+        assert!(schema.field("User", "__type").is_none());
+        assert!(schema.field("User", "__typename").is_some());
+        assert!(schema.field("String", "__typename").is_none());
+        // Meta-fields are not part of the fields of a type.
+        assert_eq!(schema.fields_of("Query").len(), 1);
+        assert!(schema.type_by_name("__MetaFields").is_none());
+    }
+
+    #[test]
+    fn it_reports_redefined_built_ins() {
+        let schema = Schema::parse(
+            "scalar String type __Schema { a: Int } directive @skip(if: Int) on FIELD type Query { a: String }",
+        );
+        let messages: Vec<_> = schema.diagnostics().map(|d| d.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "the built-in type `String` cannot be redefined",
+                "the built-in type `__Schema` cannot be redefined",
+                "the built-in directive `@skip` cannot be redefined",
+            ]
+        );
+        assert!(schema
+            .diagnostics()
+            .all(|d| d.kind() == &DiagnosticKind::BuiltInRedefinition));
+        // The built-in definitions are kept.
+        assert!(schema.type_by_name("String").unwrap().is_built_in());
+        assert_eq!(
+            schema.directive_definition("skip").unwrap().arguments()[0]
+                .ty()
+                .to_string(),
+            "Boolean!"
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! Printing of a `Schema` back to SDL.
+
+use std::fmt::{self, Write};
+
+use crate::{
+    hir::{
+        Directive, DirectiveDefinition, EnumValueDefinition, FieldDefinition, InputValueDefinition,
+        OperationType, TypeDefinition,
+    },
+    Schema,
+};
+
+const INDENT: &str = "  ";
+
+/// Prints the schema as a single type system document.
+///
+/// Type extensions are printed as part of the types they extend, and the
+/// built-in types and directives are left out. The schema definition is
+/// only printed if it is needed to describe the schema: when it has a
+/// description or directives, or when the root operation types do not have
+/// their default names.
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let mut separate = |f: &mut fmt::Formatter<'_>| {
+            if !std::mem::take(&mut first) {
+                f.write_char('\n')?;
+            }
+            Ok(())
+        };
+
+        if self.needs_schema_definition() {
+            separate(f)?;
+            description(f, "", self.description())?;
+            f.write_str("schema")?;
+            directives(f, &self.directives)?;
+            f.write_str(" {\n")?;
+            for (operation, name) in &self.root_operations {
+                writeln!(f, "{}{}: {}", INDENT, operation, name)?;
+            }
+            f.write_str("}\n")?;
+        }
+        for definition in self.directive_definitions() {
+            if !definition.is_built_in() {
+                separate(f)?;
+                directive_definition(f, definition)?;
+            }
+        }
+        for ty in self.types() {
+            if !ty.is_built_in() {
+                separate(f)?;
+                type_definition(f, ty)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Schema {
+    fn needs_schema_definition(&self) -> bool {
+        if !self.directives.is_empty() || self.description.is_some() {
+            return true;
+        }
+        if self.definition_span.is_none() {
+            return false;
+        }
+        let is_default = self
+            .root_operations
+            .iter()
+            .all(|(operation, name)| operation.default_type_name() == name);
+        // Without a schema definition, the root operation types are only found
+        // by name if all of them have their default names.
+        let has_all_defaults = [
+            OperationType::Query,
+            OperationType::Mutation,
+            OperationType::Subscription,
+        ]
+        .iter()
+        .all(|operation| {
+            self.root_operations.contains_key(operation)
+                || self.object_type(operation.default_type_name()).is_none()
+        });
+        !(is_default && has_all_defaults)
+    }
+}
+
+fn directive_definition(f: &mut fmt::Formatter<'_>, def: &DirectiveDefinition) -> fmt::Result {
+    description(f, "", def.description())?;
+    write!(f, "directive @{}", def.name())?;
+    arguments_definition(f, "", def.arguments())?;
+    if def.is_repeatable() {
+        f.write_str(" repeatable")?;
+    }
+    f.write_str(" on ")?;
+    for (i, location) in def.locations().iter().enumerate() {
+        if i > 0 {
+            f.write_str(" | ")?;
+        }
+        write!(f, "{}", location)?;
+    }
+    f.write_char('\n')
+}
+
+fn type_definition(f: &mut fmt::Formatter<'_>, ty: &TypeDefinition) -> fmt::Result {
+    description(f, "", ty.description())?;
+    write!(f, "{} {}", ty.keyword(), ty.name())?;
+    let interfaces = ty.implements_interfaces();
+    if !interfaces.is_empty() {
+        write!(f, " implements {}", interfaces.join(" & "))?;
+    }
+    directives(f, ty.directives())?;
+    match ty {
+        TypeDefinition::Scalar(_) => f.write_char('\n'),
+        TypeDefinition::Object(_) | TypeDefinition::Interface(_) => fields(f, ty.fields()),
+        TypeDefinition::Union(ty) => {
+            if !ty.members().is_empty() {
+                write!(f, " = {}", ty.members().join(" | "))?;
+            }
+            f.write_char('\n')
+        }
+        TypeDefinition::Enum(ty) => enum_values(f, ty.values()),
+        TypeDefinition::InputObject(ty) => input_fields(f, ty.fields()),
+    }
+}
+
+fn fields(f: &mut fmt::Formatter<'_>, fields: &[FieldDefinition]) -> fmt::Result {
+    if fields.is_empty() {
+        return f.write_char('\n');
+    }
+    f.write_str(" {\n")?;
+    for field in fields {
+        description(f, INDENT, field.description())?;
+        write!(f, "{}{}", INDENT, field.name())?;
+        arguments_definition(f, INDENT, field.arguments())?;
+        write!(f, ": {}", field.ty())?;
+        directives(f, field.directives())?;
+        f.write_char('\n')?;
+    }
+    f.write_str("}\n")
+}
+
+fn input_fields(f: &mut fmt::Formatter<'_>, fields: &[InputValueDefinition]) -> fmt::Result {
+    if fields.is_empty() {
+        return f.write_char('\n');
+    }
+    f.write_str(" {\n")?;
+    for field in fields {
+        description(f, INDENT, field.description())?;
+        f.write_str(INDENT)?;
+        input_value(f, field)?;
+        f.write_char('\n')?;
+    }
+    f.write_str("}\n")
+}
+
+fn enum_values(f: &mut fmt::Formatter<'_>, values: &[EnumValueDefinition]) -> fmt::Result {
+    if values.is_empty() {
+        return f.write_char('\n');
+    }
+    f.write_str(" {\n")?;
+    for value in values {
+        description(f, INDENT, value.description())?;
+        write!(f, "{}{}", INDENT, value.value())?;
+        directives(f, value.directives())?;
+        f.write_char('\n')?;
+    }
+    f.write_str("}\n")
+}
+
+/// Print arguments on a single line, unless one of them has a description.
+/// `indent` is the indentation of the definition the arguments belong to.
+fn arguments_definition(
+    f: &mut fmt::Formatter<'_>,
+    indent: &str,
+    arguments: &[InputValueDefinition],
+) -> fmt::Result {
+    if arguments.is_empty() {
+        return Ok(());
+    }
+    let multiline = arguments.iter().any(|arg| arg.description().is_some());
+    let arg_indent = format!("{}{}", indent, INDENT);
+    f.write_char('(')?;
+    for (i, arg) in arguments.iter().enumerate() {
+        if multiline {
+            f.write_char('\n')?;
+            description(f, &arg_indent, arg.description())?;
+            f.write_str(&arg_indent)?;
+        } else if i > 0 {
+            f.write_str(", ")?;
+        }
+        input_value(f, arg)?;
+    }
+    if multiline {
+        write!(f, "\n{}", indent)?;
+    }
+    f.write_char(')')
+}
+
+fn input_value(f: &mut fmt::Formatter<'_>, value: &InputValueDefinition) -> fmt::Result {
+    write!(f, "{}: {}", value.name(), value.ty())?;
+    if let Some(default) = value.default_value() {
+        write!(f, " = {}", default)?;
+    }
+    directives(f, value.directives())
+}
+
+fn directives(f: &mut fmt::Formatter<'_>, directives: &[Directive]) -> fmt::Result {
+    for directive in directives {
+        write!(f, " @{}", directive.name())?;
+        if !directive.arguments().is_empty() {
+            f.write_char('(')?;
+            for (i, arg) in directive.arguments().iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}: {}", arg.name(), arg.value())?;
+            }
+            f.write_char(')')?;
+        }
+    }
+    Ok(())
+}
+
+/// Print a description on its own line, as a block string if it spans
+/// multiple lines or contains quotes.
+fn description(f: &mut fmt::Formatter<'_>, indent: &str, description: Option<&str>) -> fmt::Result {
+    let description = match description {
+        Some(description) => description,
+        None => return Ok(()),
+    };
+    if description.contains('\n') || description.contains('"') {
+        writeln!(f, "{}\"\"\"", indent)?;
+        for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+            if line.is_empty() {
+                f.write_char('\n')?;
+            } else {
+                writeln!(f, "{}{}", indent, line)?;
+            }
+        }
+        writeln!(f, "{}\"\"\"", indent)
+    } else {
+        writeln!(f, "{}\"{}\"", indent, description)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    use crate::Schema;
+
+    #[test]
+    fn it_prints_a_schema_without_built_ins() {
+        let input = indoc! {r#"
+            """
+            The "root" query type.
+            """
+            type Query implements Node @tag(name: "root") {
+              "The node"
+              node(id: ID!, first: Int = 10): Node @deprecated
+              search(
+                "Search terms"
+                terms: [String!]! = ["a", "b"]
+              ): [Result]
+            }
+
+            interface Node {
+              id: ID!
+            }
+
+            union Result = Query | Other
+
+            directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+
+            extend type Query {
+              id: ID!
+            }
+
+            enum Color {
+              RED
+              GREEN @deprecated(reason: "Too green")
+            }
+
+            input Point {
+              x: Float = 0.5
+              y: Float
+            }
+
+            scalar Date @specifiedBy(url: "https://example.com")
+
+            type Other
+        "#};
+        let schema = Schema::parse(input);
+        assert_eq!(schema.diagnostics().len(), 0);
+        assert_eq!(
+            schema.to_string(),
+            indoc! {r#"
+                directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+
+                """
+                The "root" query type.
+                """
+                type Query implements Node @tag(name: "root") {
+                  "The node"
+                  node(id: ID!, first: Int = 10): Node @deprecated
+                  search(
+                    "Search terms"
+                    terms: [String!]! = ["a", "b"]
+                  ): [Result]
+                  id: ID!
+                }
+
+                interface Node {
+                  id: ID!
+                }
+
+                union Result = Query | Other
+
+                enum Color {
+                  RED
+                  GREEN @deprecated(reason: "Too green")
+                }
+
+                input Point {
+                  x: Float = 0.5
+                  y: Float
+                }
+
+                scalar Date @specifiedBy(url: "https://example.com")
+
+                type Other
+            "#}
+        );
+    }
+
+    #[test]
+    fn it_prints_the_schema_definition_when_needed() {
+        let schema = Schema::parse("schema { query: Query } type Query { a: Int }");
+        assert_eq!(schema.to_string(), "type Query {\n  a: Int\n}\n");
+
+        let schema = Schema::parse("schema { query: Root } type Root { a: Int }");
+        assert_eq!(
+            schema.to_string(),
+            "schema {\n  query: Root\n}\n\ntype Root {\n  a: Int\n}\n"
+        );
+
+        // `Mutation` would become a root operation type without the schema
+        // definition.
+        let schema =
+            Schema::parse("schema { query: Query } type Query { a: Int } type Mutation { b: Int }");
+        assert!(schema
+            .to_string()
+            .starts_with("schema {\n  query: Query\n}\n"));
+    }
+
+    #[test]
+    fn it_prints_the_schema_description() {
+        let input = "\"The API\"\nschema {\n  query: Query\n}\n\ntype Query {\n  a: Int\n}\n";
+        let schema = Schema::parse(input);
+        assert_eq!(schema.description(), Some("The API"));
+        assert_eq!(schema.to_string(), input);
+        assert_eq!(
+            Schema::parse(&schema.to_string()).description(),
+            Some("The API")
+        );
+    }
+}
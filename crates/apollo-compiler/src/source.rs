@@ -0,0 +1,164 @@
+use std::{
+    fmt,
+    ops::Range,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use apollo_parser::{ast::AstNode, LineCol, LineIndex};
+
+/// Identifies a `Source`. Ids are unique within a process, so a `Span` can
+/// always be traced back to the source it points into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(u32);
+
+impl SourceId {
+    fn next() -> Self {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A GraphQL document's text, and the name it is reported with in
+/// diagnostics, usually a file path.
+#[derive(Debug, Clone)]
+pub struct Source {
+    id: SourceId,
+    name: String,
+    text: String,
+    line_index: LineIndex,
+}
+
+impl Source {
+    /// Create a new source with a fresh `SourceId`.
+    pub fn new(name: impl Into<String>, text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            id: SourceId::next(),
+            name: name.into(),
+            line_index: LineIndex::new(&text),
+            text,
+        }
+    }
+
+    /// Get the source's id.
+    pub fn id(&self) -> SourceId {
+        self.id
+    }
+
+    /// Get the source's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the source's text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the start and end line and column of a span in this source.
+    ///
+    /// ## Panics
+    /// Panics if the span points into a different source.
+    pub fn location(&self, span: Span) -> Range<LineCol> {
+        assert_eq!(span.source, self.id, "span points into a different source");
+        self.line_index.range(span.range())
+    }
+}
+
+/// A range of bytes in a `Source`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    source: SourceId,
+    start: u32,
+    end: u32,
+}
+
+impl Span {
+    pub(crate) fn new(source: SourceId, range: Range<usize>) -> Self {
+        Self {
+            source,
+            start: range.start as u32,
+            end: range.end as u32,
+        }
+    }
+
+    /// Get the span of a syntax node, without the whitespace, commas and
+    /// comments around it.
+    pub(crate) fn of(source: SourceId, node: &impl AstNode) -> Self {
+        let syntax = node.syntax();
+        let range = syntax.text_range();
+        let (mut start, mut end) = (usize::from(range.start()), usize::from(range.end()));
+
+        let is_trivia = |text: &str| {
+            text.starts_with('#') || text.chars().all(|c| c.is_whitespace() || c == ',')
+        };
+        let mut first = syntax.first_token();
+        while let Some(token) = first.filter(|token| is_trivia(token.text()) && start < end) {
+            start += token.text().len();
+            first = token.next_token();
+        }
+        let mut last = syntax.last_token();
+        while let Some(token) = last.filter(|token| is_trivia(token.text()) && start < end) {
+            end -= token.text().len();
+            last = token.prev_token();
+        }
+
+        Self::new(source, start..end)
+    }
+
+    /// Get the id of the source this span points into.
+    pub fn source(&self) -> SourceId {
+        self.source
+    }
+
+    /// Get the byte offset the span starts at.
+    pub fn start(&self) -> usize {
+        self.start as usize
+    }
+
+    /// Get the byte offset the span ends at.
+    pub fn end(&self) -> usize {
+        self.end as usize
+    }
+
+    /// Get the span's byte range.
+    pub fn range(&self) -> Range<usize> {
+        self.start()..self.end()
+    }
+}
+
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}..{}", self.source.0, self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_parser::{LineCol, Parser};
+
+    use super::{Source, Span};
+
+    #[test]
+    fn it_trims_trivia_from_spans() {
+        let input = "type Query { a: Int, # a\n  b: Int }";
+        let source = Source::new("schema.graphql", input);
+        let ast = Parser::new(input).parse();
+        let document = ast.document();
+
+        let def = document.definitions().next().unwrap();
+        let span = Span::of(source.id(), &def);
+        assert_eq!(&input[span.range()], input);
+
+        if let apollo_parser::ast::Definition::ObjectTypeDefinition(def) = def {
+            let fields = def.fields_definition().unwrap();
+            let a = fields.field_definitions().next().unwrap();
+            let span = Span::of(source.id(), &a);
+            assert_eq!(&input[span.range()], "a: Int");
+            assert_eq!(
+                source.location(span),
+                LineCol { line: 0, col: 13 }..LineCol { line: 0, col: 19 }
+            );
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Rules for applied directives, in type system and executable documents.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Validation.Directives>.
+
+use std::collections::HashMap;
+
+use apollo_parser::ast;
+
+use crate::{
+    hir::{Directive, DirectiveLocation, TypeDefinition},
+    schema::builder,
+    validation::{values::invalid_value, ExecutableValidator},
+    Diagnostic, DiagnosticKind, Schema, Span,
+};
+
+impl ExecutableValidator<'_> {
+    /// Validate the directives applied to an operation, variable definition,
+    /// fragment, field or fragment spread.
+    pub(crate) fn directives(
+        &mut self,
+        directives: Option<ast::Directives>,
+        location: DirectiveLocation,
+    ) {
+        let directives = builder::directives(self.document.source().id(), directives);
+        self.diagnostics
+            .extend(validate_directives(self.schema, location, &directives));
+    }
+}
+
+/// Check that the directives applied at `location` are defined, allowed at
+/// that location, and not repeated unless they are repeatable, and that
+/// their arguments are defined, unique, provided when required and valid
+/// values.
+pub(crate) fn validate_directives(
+    schema: &Schema,
+    location: DirectiveLocation,
+    directives: &[Directive],
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<&str, Span> = HashMap::new();
+    for directive in directives {
+        let name = directive.name();
+        let span = directive.span();
+        let definition = match schema.directive_definition(name) {
+            Some(definition) => definition,
+            None => {
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UndefinedDirective,
+                        format!("the directive `@{}` is not defined", name),
+                    )
+                    .label(span, format!("`@{}` used here", name)),
+                );
+                continue;
+            }
+        };
+        if !definition.locations().contains(&location) {
+            diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::MisplacedDirective,
+                    format!(
+                        "the directive `@{}` cannot be used in the `{}` location",
+                        name, location
+                    ),
+                )
+                .label(span, format!("`@{}` used here", name))
+                .label(definition.span(), format!("`@{}` defined here", name)),
+            );
+        }
+        if let Some(previous) = seen.insert(name, span) {
+            if !definition.is_repeatable() {
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::RepeatedDirective,
+                        format!(
+                            "the directive `@{}` can only be used once in the `{}` location",
+                            name, location
+                        ),
+                    )
+                    .label(span, format!("`@{}` used again here", name))
+                    .label(previous, format!("`@{}` first used here", name)),
+                );
+            }
+        }
+
+        let mut arguments: HashMap<&str, Span> = HashMap::new();
+        for arg in directive.arguments() {
+            if let Some(previous) = arguments.insert(arg.name(), arg.span()) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::DuplicateArgument,
+                        format!("the argument `{}` is provided multiple times", arg.name()),
+                    )
+                    .label(arg.span(), format!("`{}` provided again here", arg.name()))
+                    .label(previous, format!("`{}` first provided here", arg.name())),
+                );
+                continue;
+            }
+            match definition.argument(arg.name()) {
+                Some(arg_definition) => {
+                    let what = format!("the argument `{}` of `@{}`", arg.name(), name);
+                    diagnostics.extend(invalid_value(
+                        schema,
+                        &what,
+                        arg_definition.ty(),
+                        arg.value(),
+                        arg.span(),
+                    ));
+                }
+                None => diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UndefinedArgument,
+                        format!(
+                            "the directive `@{}` does not have an argument `{}`",
+                            name,
+                            arg.name()
+                        ),
+                    )
+                    .label(
+                        arg.span(),
+                        format!("argument `{}` provided here", arg.name()),
+                    )
+                    .label(definition.span(), format!("`@{}` defined here", name)),
+                ),
+            }
+        }
+        for arg_definition in definition.arguments() {
+            if arg_definition.is_required() && !arguments.contains_key(arg_definition.name()) {
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::MissingArgument,
+                        format!(
+                            "the directive `@{}` requires the argument `{}`",
+                            name,
+                            arg_definition.name()
+                        ),
+                    )
+                    .label(span, format!("`@{}` used here", name))
+                    .label(
+                        arg_definition.span(),
+                        format!("argument `{}` defined here", arg_definition.name()),
+                    ),
+                );
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Get the location of the directives applied to a type definition.
+pub(crate) fn type_location(ty: &TypeDefinition) -> DirectiveLocation {
+    match ty {
+        TypeDefinition::Scalar(_) => DirectiveLocation::Scalar,
+        TypeDefinition::Object(_) => DirectiveLocation::Object,
+        TypeDefinition::Interface(_) => DirectiveLocation::Interface,
+        TypeDefinition::Union(_) => DirectiveLocation::Union,
+        TypeDefinition::Enum(_) => DirectiveLocation::Enum,
+        TypeDefinition::InputObject(_) => DirectiveLocation::InputObject,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{validation::operations::test::messages, Schema};
+
+    #[test]
+    fn it_reports_invalid_directives_in_executable_documents() {
+        assert_eq!(
+            messages(
+                r#"
+query Q @skip(if: true) {
+  me @deprecated { name @include(if: true) @include(if: false) }
+  pets @unknown { ...F @skip(if: true, unless: false) }
+  pet(id: 1) { ... on Cat @include { lives } }
+}
+fragment F on Pet @include(if: true, if: false) { name }
+"#
+            ),
+            [
+                "the directive `@skip` cannot be used in the `QUERY` location",
+                "the directive `@deprecated` cannot be used in the `FIELD` location",
+                "the directive `@include` can only be used once in the `FIELD` location",
+                "the directive `@unknown` is not defined",
+                "the directive `@skip` does not have an argument `unless`",
+                "the directive `@include` requires the argument `if`",
+                "the directive `@include` cannot be used in the `FRAGMENT_DEFINITION` location",
+                "the argument `if` is provided multiple times",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_directives_on_variable_definitions() {
+        assert_eq!(
+            messages("query($id: ID! @unknown @skip(if: 1)) { pet(id: $id) { name } }"),
+            [
+                "the directive `@unknown` is not defined",
+                "the directive `@skip` cannot be used in the `VARIABLE_DEFINITION` location",
+                "invalid value for the argument `if` of `@skip`: expected a value of type `Boolean`, found `1`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_directives_in_schemas() {
+        let schema = Schema::parse(
+            r#"
+directive @tag(name: String!) repeatable on OBJECT | FIELD_DEFINITION
+directive @key(fields: String!) on OBJECT
+schema @tag(name: "schema") { query: Query }
+type Query @key(fields: "id") @key(fields: "name") @tag(name: "a") @tag(name: "b") {
+  me(id: ID @deprecated): User @tag(name: 1) @specifiedBy(url: "")
+}
+type User @key { name: String @deprecated(reason: "no", since: "now") }
+enum Color { RED @deprecated @unknown }
+"#,
+        );
+        let messages: Vec<_> = schema
+            .validate()
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "the directive `@tag` cannot be used in the `SCHEMA` location",
+                "the directive `@key` can only be used once in the `OBJECT` location",
+                "invalid value for the argument `name` of `@tag`: expected a value of type `String`, found `1`",
+                "the directive `@specifiedBy` cannot be used in the `FIELD_DEFINITION` location",
+                "the directive `@key` requires the argument `fields`",
+                "the directive `@deprecated` does not have an argument `since`",
+                "the directive `@unknown` is not defined",
+            ]
+        );
+    }
+}
@@ -0,0 +1,744 @@
+//! The "Field Selection Merging" rule.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Field-Selection-Merging>.
+//!
+//! A naive implementation compares every pair of fields with the same
+//! response name, and expands fragments again for every comparison, which is
+//! quadratic in the number of fields and exponential in the depth of nested
+//! fragment spreads. Instead, the fields selected with the same response
+//! name are grouped, and each field of a group is compared to the first one
+//! only, as fields that can merge with the same field can merge with each
+//! other. The sub-selections of all fields of a group are then merged and
+//! checked together.
+//!
+//! Fragments are not expanded into the fields that spread them. The fields
+//! of a fragment are grouped and checked once, and each group of a fragment
+//! is then represented by its first field wherever the fragment is spread.
+//! Merged selections are identified by their selection sets and the names of
+//! the fragments they spread, leaving out the fragments that one of the
+//! other fragments already spreads, so that the selections of a fragment
+//! that is spread at every level of a query are only checked once.
+//!
+//! Two checks are made for every group of fields:
+//! * All fields must have the same response shape, even if they apply to
+//!   different parent types. Otherwise, the shape of the response would
+//!   depend on the runtime type of the parent.
+//! * Fields whose parent types can be the same object type at runtime must
+//!   select the same field with the same arguments, so that they can be
+//!   merged into one field.
+
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use apollo_parser::ast;
+use indexmap::IndexMap;
+
+use crate::{
+    executable::fragment_name,
+    hir::{FieldDefinition, Type, TypeDefinition, Value},
+    schema::builder::value,
+    validation::{operation_type_of, response_name, type_condition_name, ExecutableValidator},
+    Diagnostic, DiagnosticKind, Schema, Span,
+};
+
+impl ExecutableValidator<'_> {
+    /// Check that the fields of every operation and fragment can be merged.
+    pub(crate) fn overlapping_fields(&mut self) {
+        let schema = self.schema;
+        let document = self.document;
+        // The fields of fragments that operations use are checked as part of
+        // the operations, so only unused fragments are checked on their own.
+        let used = self.used_fragments();
+        let mut merging = FieldMerging::new(self);
+        for operation in document.operations() {
+            let root = schema
+                .root_operation(operation_type_of(&operation))
+                .map(|root| root.name());
+            if let Some(selection_set) = operation.selection_set() {
+                let selections = merging.selections(vec![(root, selection_set)]);
+                merging.fields_can_merge(&selections);
+            }
+        }
+        for fragment in document.fragments() {
+            let name = match fragment_name(&fragment) {
+                Some(name) if !used.contains(&name) => name,
+                _ => continue,
+            };
+            if let Some(selections) = merging.fragment_selections(&name) {
+                merging.fields_can_merge(&selections);
+            }
+        }
+    }
+}
+
+/// A field in a group of fields with the same response name.
+#[derive(Clone)]
+struct MergedField<'a> {
+    field: ast::Field,
+    /// The definition of the field, if it is known.
+    definition: Option<&'a FieldDefinition>,
+}
+
+/// Selections whose fields are merged: selection sets with the name of the
+/// type they select fields of, and the names of the fragments they spread.
+///
+/// Selection sets that only spread fragments are left out, and so are the
+/// fragments that another one of the fragments spreads, as their fields are
+/// already merged through it.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+struct Selections<'a> {
+    sets: Vec<(Option<&'a str>, ast::SelectionSet)>,
+    fragments: Vec<String>,
+}
+
+impl Selections<'_> {
+    fn is_empty(&self) -> bool {
+        self.sets.is_empty() && self.fragments.is_empty()
+    }
+}
+
+/// A group of fields with the same response name. The fields of a fragment
+/// are checked on their own, so a group of fields of a fragment that is
+/// merged into this group is only represented by its first field.
+#[derive(Default)]
+struct Group<'a> {
+    /// The fields whose definitions are known, to compare their types.
+    typed: Vec<MergedField<'a>>,
+    /// The fields by the object type they are selected on, or by `None` for
+    /// abstract or unknown types.
+    by_parent: IndexMap<Option<&'a str>, ParentGroup<'a>>,
+}
+
+/// The fields of a group that are selected on the same type.
+#[derive(Default)]
+struct ParentGroup<'a> {
+    fields: Vec<MergedField<'a>>,
+    /// The merged sub-selections of the fields.
+    selections: Selections<'a>,
+}
+
+/// The groups of fields of merged selections, by response name.
+type Summary<'a> = IndexMap<String, Group<'a>>;
+
+struct FieldMerging<'v, 'a> {
+    validator: &'v mut ExecutableValidator<'a>,
+    /// The index of every fragment in `reachable`.
+    fragment_indices: HashMap<String, usize>,
+    /// For each fragment, a bit set of the fragments that it spreads in its
+    /// own selection set, directly or through other fragments.
+    reachable: Vec<Vec<u64>>,
+    /// The selections of fragments, by fragment name.
+    fragments: HashMap<String, Selections<'a>>,
+    /// The groups of fields of the selections seen so far.
+    summaries: HashMap<Selections<'a>, Rc<Summary<'a>>>,
+    /// The selections whose groups are being collected, to stop at
+    /// fragments that spread themselves.
+    in_progress: HashSet<Selections<'a>>,
+    /// The selections whose response shapes were already checked.
+    shapes_checked: HashSet<Selections<'a>>,
+    /// The selections whose names and arguments were already checked.
+    parents_checked: HashSet<Selections<'a>>,
+    /// The pairs of fields that were already reported as conflicting.
+    reported: HashSet<(Span, Span)>,
+}
+
+impl<'v, 'a> FieldMerging<'v, 'a> {
+    fn new(validator: &'v mut ExecutableValidator<'a>) -> Self {
+        let names: Vec<String> = validator.fragments.keys().cloned().collect();
+        let fragment_indices: HashMap<_, _> = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), index))
+            .collect();
+        let spreads: Vec<Vec<usize>> = names
+            .iter()
+            .map(|name| {
+                let selection_set = validator.fragments[name].selection_set();
+                let mut spreads = Vec::new();
+                if let Some(selection_set) = selection_set {
+                    level_spreads(&selection_set, &mut spreads);
+                }
+                spreads
+                    .iter()
+                    .filter_map(|name| fragment_indices.get(name).copied())
+                    .collect()
+            })
+            .collect();
+        let mut reachable = vec![Vec::new(); names.len()];
+        let mut visited = vec![false; names.len()];
+        for index in 0..names.len() {
+            reach(index, &spreads, &mut visited, &mut reachable);
+        }
+        Self {
+            validator,
+            fragment_indices,
+            reachable,
+            fragments: HashMap::new(),
+            summaries: HashMap::new(),
+            in_progress: HashSet::new(),
+            shapes_checked: HashSet::new(),
+            parents_checked: HashSet::new(),
+            reported: HashSet::new(),
+        }
+    }
+
+    fn schema(&self) -> &'a Schema {
+        self.validator.schema
+    }
+
+    fn fields_can_merge(&mut self, selections: &Selections<'a>) {
+        self.same_response_shape(selections);
+        self.same_for_common_parents(selections);
+    }
+
+    /// Get the selections of selection sets, with the fragments they spread.
+    fn selections(&self, sets: Vec<(Option<&'a str>, ast::SelectionSet)>) -> Selections<'a> {
+        let mut selections = Selections::default();
+        for (ty, selection_set) in sets {
+            self.add_selection_set(&mut selections, ty, selection_set);
+        }
+        selections.fragments = self.without_spread_fragments(selections.fragments);
+        selections
+    }
+
+    fn add_selection_set(
+        &self,
+        selections: &mut Selections<'a>,
+        ty: Option<&'a str>,
+        selection_set: ast::SelectionSet,
+    ) {
+        if has_fields(&selection_set) {
+            selections.sets.push((ty, selection_set.clone()));
+        }
+        level_spreads(&selection_set, &mut selections.fragments);
+    }
+
+    /// Get the selections of the fragment called `name`.
+    fn fragment_selections(&mut self, name: &str) -> Option<Selections<'a>> {
+        if let Some(selections) = self.fragments.get(name) {
+            return Some(selections.clone());
+        }
+        let fragment = self.validator.fragment(name)?;
+        let ty = type_condition_name(fragment.type_condition())
+            .and_then(|name| self.schema().type_by_name(&name))
+            .map(TypeDefinition::name);
+        let selections = self.selections(vec![(ty, fragment.selection_set()?)]);
+        self.fragments.insert(name.to_string(), selections.clone());
+        Some(selections)
+    }
+
+    /// Leave out the fragments that another one of `fragments` spreads.
+    fn without_spread_fragments(&self, fragments: Vec<String>) -> Vec<String> {
+        let mut kept: Vec<String> = Vec::new();
+        for name in fragments {
+            if kept
+                .iter()
+                .any(|other| *other == name || self.spreads(other, &name))
+            {
+                continue;
+            }
+            kept.retain(|other| !self.spreads(&name, other));
+            kept.push(name);
+        }
+        kept
+    }
+
+    /// Check whether the fragment `from` spreads the fragment `to` in its own
+    /// selection set, directly or through other fragments.
+    fn spreads(&self, from: &str, to: &str) -> bool {
+        match (
+            self.fragment_indices.get(from),
+            self.fragment_indices.get(to),
+        ) {
+            (Some(&from), Some(&to)) => self.reachable[from]
+                .get(to / 64)
+                .is_some_and(|bits| bits & (1 << (to % 64)) != 0),
+            _ => false,
+        }
+    }
+
+    /// Group the fields of selections by response name. The groups of the
+    /// fragments they spread are added with one field per parent type.
+    fn summary(&mut self, selections: &Selections<'a>) -> Rc<Summary<'a>> {
+        if let Some(summary) = self.summaries.get(selections) {
+            return summary.clone();
+        }
+        if !self.in_progress.insert(selections.clone()) {
+            return Rc::default();
+        }
+        let mut summary = Summary::new();
+        for (ty, selection_set) in &selections.sets {
+            self.collect_fields(*ty, selection_set, &mut summary);
+        }
+        for name in &selections.fragments {
+            let fragment = match self.fragment_selections(name) {
+                Some(fragment) => self.summary(&fragment),
+                None => continue,
+            };
+            for (response_name, fragment_group) in fragment.iter() {
+                let group = summary.entry(response_name.clone()).or_default();
+                group.typed.extend(fragment_group.typed.first().cloned());
+                for (parent, fragment_parent) in &fragment_group.by_parent {
+                    let parent = group.by_parent.entry(*parent).or_default();
+                    parent.fields.push(fragment_parent.fields[0].clone());
+                    let sub = &fragment_parent.selections;
+                    parent.selections.sets.extend(sub.sets.iter().cloned());
+                    parent
+                        .selections
+                        .fragments
+                        .extend(sub.fragments.iter().cloned());
+                }
+            }
+        }
+        for group in summary.values_mut() {
+            for parent in group.by_parent.values_mut() {
+                let fragments = std::mem::take(&mut parent.selections.fragments);
+                parent.selections.fragments = self.without_spread_fragments(fragments);
+            }
+        }
+        let summary = Rc::new(summary);
+        self.in_progress.remove(selections);
+        self.summaries.insert(selections.clone(), summary.clone());
+        summary
+    }
+
+    /// Add the fields of a selection set and of its inline fragments to
+    /// their groups.
+    fn collect_fields(
+        &self,
+        ty: Option<&'a str>,
+        selection_set: &ast::SelectionSet,
+        summary: &mut Summary<'a>,
+    ) {
+        let parent = ty.and_then(|ty| self.schema().type_by_name(ty));
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    let (response_name, name) = match (response_name(&field), field.name()) {
+                        (Some(response_name), Some(name)) => (response_name, name),
+                        _ => continue,
+                    };
+                    let definition =
+                        parent.and_then(|parent| self.schema().field(parent.name(), &name.text()));
+                    let group = summary.entry(response_name).or_default();
+                    let merged = MergedField {
+                        field: field.clone(),
+                        definition,
+                    };
+                    if definition.is_some() {
+                        group.typed.push(merged.clone());
+                    }
+                    let object = match parent {
+                        Some(TypeDefinition::Object(object)) => Some(object.name()),
+                        _ => None,
+                    };
+                    let parent = group.by_parent.entry(object).or_default();
+                    parent.fields.push(merged);
+                    if let Some(sub_selection_set) = field.selection_set() {
+                        let sub_ty = definition
+                            .and_then(|definition| self.schema().resolve_type(definition.ty()))
+                            .map(TypeDefinition::name);
+                        self.add_selection_set(&mut parent.selections, sub_ty, sub_selection_set);
+                    }
+                }
+                ast::Selection::FragmentSpread(_) => {}
+                ast::Selection::InlineFragment(inline) => {
+                    let ty = match inline.type_condition() {
+                        Some(condition) => type_condition_name(Some(condition))
+                            .and_then(|name| self.schema().type_by_name(&name))
+                            .map(TypeDefinition::name),
+                        None => ty,
+                    };
+                    if let Some(selection_set) = inline.selection_set() {
+                        self.collect_fields(ty, &selection_set, summary);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check that all fields of every group have the same response shape,
+    /// and that their merged sub-selections do too.
+    fn same_response_shape(&mut self, selections: &Selections<'a>) {
+        if !self.shapes_checked.insert(selections.clone()) {
+            return;
+        }
+        for name in &selections.fragments {
+            if let Some(fragment) = self.fragment_selections(name) {
+                self.same_response_shape(&fragment);
+            }
+        }
+        let summary = self.summary(selections);
+        for group in summary.values() {
+            if let Some((first, others)) = group.typed.split_first() {
+                let first_ty = first.definition.map(FieldDefinition::ty);
+                let conflict = others.iter().find_map(|other| {
+                    let other_ty = other.definition.map(FieldDefinition::ty);
+                    match (first_ty, other_ty) {
+                        (Some(a), Some(b)) if !self.same_shape(a, b) => Some((other, a, b)),
+                        _ => None,
+                    }
+                });
+                if let Some((other, a, b)) = conflict {
+                    let reason = format!("they have the different types `{}` and `{}`", a, b);
+                    self.report(first, other, reason);
+                    continue;
+                }
+            }
+            let sub_selections = self.merged_selections(group.by_parent.values());
+            if !sub_selections.is_empty() {
+                self.same_response_shape(&sub_selections);
+            }
+        }
+    }
+
+    /// Merge the sub-selections of fields selected on different types.
+    fn merged_selections<'g>(
+        &self,
+        parents: impl Iterator<Item = &'g ParentGroup<'a>>,
+    ) -> Selections<'a>
+    where
+        'a: 'g,
+    {
+        let mut selections = Selections::default();
+        for parent in parents {
+            selections
+                .sets
+                .extend(parent.selections.sets.iter().cloned());
+            selections
+                .fragments
+                .extend(parent.selections.fragments.iter().cloned());
+        }
+        selections.fragments = self.without_spread_fragments(selections.fragments);
+        selections
+    }
+
+    /// Check whether values of two types have the same shape in a response:
+    /// the same nullability and list wrappers, and the same leaf type.
+    /// Composite types are compared by their sub-selections.
+    fn same_shape(&self, a: &Type, b: &Type) -> bool {
+        match (a, b) {
+            (Type::NonNull(a), Type::NonNull(b)) | (Type::List(a), Type::List(b)) => {
+                self.same_shape(a, b)
+            }
+            (Type::NonNull(_), _) | (_, Type::NonNull(_)) => false,
+            (Type::List(_), _) | (_, Type::List(_)) => false,
+            (Type::Named(a), Type::Named(b)) => {
+                let is_leaf = |name: &str| {
+                    self.schema()
+                        .type_by_name(name)
+                        .is_some_and(TypeDefinition::is_leaf_type)
+                };
+                if is_leaf(a) || is_leaf(b) {
+                    a == b
+                } else {
+                    true
+                }
+            }
+        }
+    }
+
+    /// Check that the fields of every group that can apply to the same
+    /// object select the same field with the same arguments, and that their
+    /// merged sub-selections do too.
+    ///
+    /// Fields selected on different object types never apply to the same
+    /// object, while fields selected on abstract types may apply to any
+    /// object.
+    fn same_for_common_parents(&mut self, selections: &Selections<'a>) {
+        if !self.parents_checked.insert(selections.clone()) {
+            return;
+        }
+        for name in &selections.fragments {
+            if let Some(fragment) = self.fragment_selections(name) {
+                self.same_for_common_parents(&fragment);
+            }
+        }
+        let summary = self.summary(selections);
+        for group in summary.values() {
+            let abstract_parent = group.by_parent.get(&None);
+            let objects: Vec<_> = group
+                .by_parent
+                .iter()
+                .filter(|(object, _)| object.is_some())
+                .map(|(_, parent)| parent)
+                .collect();
+            let common_parents: Vec<Vec<&ParentGroup<'a>>> = if objects.is_empty() {
+                abstract_parent
+                    .into_iter()
+                    .map(|parent| vec![parent])
+                    .collect()
+            } else {
+                objects
+                    .into_iter()
+                    .map(|object| [object].into_iter().chain(abstract_parent).collect())
+                    .collect()
+            };
+            for parents in common_parents {
+                let mut fields = parents.iter().flat_map(|parent| &parent.fields);
+                let first = match fields.next() {
+                    Some(first) => first,
+                    None => continue,
+                };
+                let first_name = first.field.name().map(|name| name.text().to_string());
+                let mut conflict = false;
+                for other in fields {
+                    let other_name = other.field.name().map(|name| name.text().to_string());
+                    if first_name != other_name {
+                        self.report(
+                            first,
+                            other,
+                            format!(
+                                "they select the different fields `{}` and `{}`",
+                                first_name.as_deref().unwrap_or_default(),
+                                other_name.as_deref().unwrap_or_default()
+                            ),
+                        );
+                        conflict = true;
+                    } else if arguments(&first.field) != arguments(&other.field) {
+                        self.report(first, other, "they have different arguments".to_string());
+                        conflict = true;
+                    }
+                }
+                if conflict {
+                    continue;
+                }
+                let sub_selections = self.merged_selections(parents.into_iter());
+                if !sub_selections.is_empty() {
+                    self.same_for_common_parents(&sub_selections);
+                }
+            }
+        }
+    }
+
+    fn report(&mut self, a: &MergedField<'a>, b: &MergedField<'a>, reason: String) {
+        let a_span = self.validator.span(&a.field);
+        let b_span = self.validator.span(&b.field);
+        let pair = if a_span.start() <= b_span.start() {
+            (a_span, b_span)
+        } else {
+            (b_span, a_span)
+        };
+        if !self.reported.insert(pair) {
+            return;
+        }
+        let name = response_name(&a.field).unwrap_or_default();
+        self.validator.diagnostics.push(
+            Diagnostic::new(
+                DiagnosticKind::ConflictingFields,
+                format!(
+                    "the fields selected as `{}` cannot be merged, because {}",
+                    name, reason
+                ),
+            )
+            .label(a_span, format!("`{}` selected here", name))
+            .label(b_span, format!("`{}` selected here", name)),
+        );
+    }
+}
+
+/// Check whether a selection set or its inline fragments select fields.
+fn has_fields(selection_set: &ast::SelectionSet) -> bool {
+    selection_set.selections().any(|selection| match selection {
+        ast::Selection::Field(_) => true,
+        ast::Selection::FragmentSpread(_) => false,
+        ast::Selection::InlineFragment(inline) => {
+            inline.selection_set().is_some_and(|set| has_fields(&set))
+        }
+    })
+}
+
+/// Add the names of the fragments spread in a selection set and in its
+/// inline fragments, but not in the selection sets of its fields.
+fn level_spreads(selection_set: &ast::SelectionSet, fragments: &mut Vec<String>) {
+    for selection in selection_set.selections() {
+        match selection {
+            ast::Selection::Field(_) => {}
+            ast::Selection::FragmentSpread(spread) => {
+                if let Some(name) = spread.fragment_name().and_then(|name| name.name()) {
+                    fragments.push(name.text().to_string());
+                }
+            }
+            ast::Selection::InlineFragment(inline) => {
+                if let Some(selection_set) = inline.selection_set() {
+                    level_spreads(&selection_set, fragments);
+                }
+            }
+        }
+    }
+}
+
+/// Compute the bit set of the fragments that the fragment at `index`
+/// spreads, directly or through other fragments. In a cycle of spreads, the
+/// fragments whose bit sets are still being computed only add their own bit.
+fn reach(index: usize, spreads: &[Vec<usize>], visited: &mut [bool], reachable: &mut [Vec<u64>]) {
+    if visited[index] {
+        return;
+    }
+    visited[index] = true;
+    let mut bits = vec![0; reachable.len().div_ceil(64)];
+    for &spread in &spreads[index] {
+        reach(spread, spreads, visited, reachable);
+        bits[spread / 64] |= 1 << (spread % 64);
+        for (bits, spread_bits) in bits.iter_mut().zip(&reachable[spread]) {
+            *bits |= spread_bits;
+        }
+    }
+    reachable[index] = bits;
+}
+
+/// Get the arguments of a field, sorted by name, to compare them
+/// regardless of order.
+fn arguments(field: &ast::Field) -> Vec<(String, Option<Value>)> {
+    let mut arguments: Vec<_> = field
+        .arguments()
+        .into_iter()
+        .flat_map(|arguments| arguments.arguments())
+        .filter_map(|arg| {
+            let name = arg.name()?.text().to_string();
+            Some((name, arg.value().and_then(value)))
+        })
+        .collect();
+    arguments.sort_by(|a, b| a.0.cmp(&b.0));
+    arguments
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::validation::operations::test::messages;
+
+    #[test]
+    fn it_accepts_mergeable_fields() {
+        assert_eq!(
+            messages(
+                r#"
+{
+  me { name name friends { name } ...UserFields }
+  pet(id: 1) { name ... on Cat { name lives } ... on Dog { kind: name } ... on Cat { kind: name } }
+  pets { ... on Cat { value: name } ... on Dog { value: nickname } }
+}
+fragment UserFields on User { name friends { name friends { name } } }
+"#
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn it_reports_conflicting_names_and_arguments() {
+        assert_eq!(
+            messages(
+                r#"
+{
+  pet(id: 3) { ... on Dog { name: nickname name } }
+  a: pet(id: 1) { name }
+  a: pet(id: 2) { name }
+  b: pet(id: 1, kind: "dog") { name }
+  b: pet(kind: "dog", id: 1) { name }
+}
+"#
+            ),
+            [
+                "the fields selected as `name` cannot be merged, because they select the different fields `nickname` and `name`",
+                "the fields selected as `a` cannot be merged, because they have different arguments",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_conflicts_through_fragments() {
+        assert_eq!(
+            messages(
+                r#"
+{ pets { ...A ... on Pet { ...B } } }
+fragment A on Pet { ... on Dog { x: nickname } }
+fragment B on Pet { ... on Dog { x: name } }
+"#
+            ),
+            [
+                "the fields selected as `x` cannot be merged, because they select the different fields `nickname` and `name`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_conflicts_between_fragments_spread_in_merged_fields() {
+        assert_eq!(
+            messages(
+                r#"
+{ me { ...A friends { ...C } } }
+fragment A on User { friends { ...B } }
+fragment B on User { x: name }
+fragment C on User { x: friends { name } }
+"#
+            ),
+            [
+                "the fields selected as `x` cannot be merged, because they have the different types `[User]` and `String`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_stops_at_fragments_that_spread_themselves() {
+        assert_eq!(
+            messages(
+                r#"
+{ me { ...A } }
+fragment A on User { name ...B }
+fragment B on User { name: friends { name } ...A }
+"#
+            ),
+            [
+                "the fragment `A` cannot spread itself, but it does through `B` -> `A`",
+                "the fields selected as `name` cannot be merged, because they have the different types `[User]` and `String`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_different_response_shapes() {
+        assert_eq!(
+            messages(
+                r#"
+{
+  pets {
+    ... on Cat { value: lives }
+    ... on Dog { value: barks }
+  }
+  pet(id: 1) {
+    ... on Cat { shape: name }
+    ... on Dog { shape: name }
+  }
+  me { friends { name } }
+  me { friends: name }
+}
+"#
+            ),
+            [
+                "the fields selected as `value` cannot be merged, because they have the different types `Int` and `Boolean`",
+                "the fields selected as `friends` cannot be merged, because they have the different types `[User]` and `String`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_handles_deeply_nested_fragments() {
+        // Without merging of spreads of the same fragment, this expands to
+        // 2^64 fields.
+        let mut query = String::from("{ me { ...F0 } }\n");
+        for i in 0..64 {
+            query.push_str(&format!(
+                "fragment F{} on User {{ name friends {{ ...F{} }} ...F{} }}\n",
+                i,
+                i + 1,
+                i + 1
+            ));
+        }
+        query.push_str("fragment F64 on User { name }\n");
+        assert_eq!(messages(&query), Vec::<String>::new());
+    }
+}
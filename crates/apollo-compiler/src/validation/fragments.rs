@@ -0,0 +1,215 @@
+//! Rules for fragment definitions.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Validation.Fragments>.
+
+use std::collections::{HashMap, HashSet};
+
+use apollo_parser::ast;
+
+use crate::{
+    executable::fragment_name,
+    hir::{DirectiveLocation, TypeDefinition},
+    validation::{type_condition_name, type_kind, ExecutableValidator},
+    Diagnostic, DiagnosticKind, Span,
+};
+
+impl<'a> ExecutableValidator<'a> {
+    /// Validate all fragment definitions, including their selection sets.
+    pub(crate) fn fragments(&mut self) {
+        let used = self.used_fragments();
+        let mut names: HashMap<String, Span> = HashMap::new();
+        let mut visited = HashSet::new();
+        for fragment in self.document.fragments() {
+            let name = match fragment_name(&fragment) {
+                Some(name) => name,
+                None => continue,
+            };
+            let span = self.span(&fragment);
+            if let Some(previous) = names.get(&name) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::DuplicateDefinition,
+                        format!("the fragment `{}` is defined multiple times", name),
+                    )
+                    .label(span, format!("`{}` redefined here", name))
+                    .label(*previous, format!("previous definition of `{}` here", name)),
+                );
+                continue;
+            }
+            names.insert(name.clone(), span);
+
+            if !used.contains(&name) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UnusedFragment,
+                        format!("the fragment `{}` is never used", name),
+                    )
+                    .label(span, format!("`{}` defined here", name)),
+                );
+            }
+            self.fragment_cycles(
+                &fragment,
+                &mut visited,
+                &mut Vec::new(),
+                &mut HashMap::new(),
+            );
+            self.directives(fragment.directives(), DirectiveLocation::FragmentDefinition);
+            let ty = fragment
+                .type_condition()
+                .and_then(|condition| self.type_condition(&condition));
+            if let Some(selection_set) = fragment.selection_set() {
+                self.selection_set(ty, &selection_set);
+            }
+        }
+    }
+
+    /// Report the fragments that spread themselves, directly or through
+    /// other fragments, searching from `fragment`. Each fragment is only
+    /// searched once, so that each cycle is reported once.
+    ///
+    /// `path` holds the spreads that led to `fragment`, and `path_index` the
+    /// position in `path` at which each fragment on the path was entered.
+    fn fragment_cycles(
+        &mut self,
+        fragment: &ast::FragmentDefinition,
+        visited: &mut HashSet<String>,
+        path: &mut Vec<(String, ast::FragmentSpread)>,
+        path_index: &mut HashMap<String, usize>,
+    ) {
+        let name = match fragment_name(fragment) {
+            Some(name) => name,
+            None => return,
+        };
+        if !visited.insert(name.clone()) {
+            return;
+        }
+        path_index.insert(name.clone(), path.len());
+        for (spread_name, spread) in self.spreads_in(fragment) {
+            let cycle_index = path_index.get(&spread_name).copied();
+            path.push((spread_name.clone(), spread));
+            match cycle_index {
+                Some(index) => self.fragment_cycle(&path[index..]),
+                None => {
+                    if let Some(next) = self.fragment(&spread_name).cloned() {
+                        self.fragment_cycles(&next, visited, path, path_index);
+                    }
+                }
+            }
+            path.pop();
+        }
+        path_index.remove(&name);
+    }
+
+    /// Report a cycle of fragment spreads, which ends with a spread of the
+    /// fragment it starts in.
+    fn fragment_cycle(&mut self, cycle: &[(String, ast::FragmentSpread)]) {
+        let (name, _) = &cycle[cycle.len() - 1];
+        let names: Vec<_> = cycle
+            .iter()
+            .map(|(name, _)| format!("`{}`", name))
+            .collect();
+        let mut diagnostic = Diagnostic::new(
+            DiagnosticKind::FragmentCycle,
+            format!(
+                "the fragment `{}` cannot spread itself, but it does through {}",
+                name,
+                names.join(" -> ")
+            ),
+        );
+        for (spread_name, spread) in cycle {
+            diagnostic =
+                diagnostic.label(self.span(spread), format!("`{}` spread here", spread_name));
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Resolve the type of a type condition, and check that it is a
+    /// composite type.
+    pub(crate) fn type_condition(
+        &mut self,
+        condition: &ast::TypeCondition,
+    ) -> Option<&'a TypeDefinition> {
+        let schema = self.schema;
+        let name = type_condition_name(Some(condition.clone()))?;
+        let span = self.span(condition);
+        match schema.type_by_name(&name) {
+            Some(ty) if ty.is_composite_type() => Some(ty),
+            Some(ty) => {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidTypeCondition,
+                        format!(
+                            "fragments can only be on object, interface and union types, but `{}` is {}",
+                            name,
+                            type_kind(ty)
+                        ),
+                    )
+                    .label(span, "type condition here"),
+                );
+                None
+            }
+            None => {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UndefinedType,
+                        format!("the type `{}` is not defined", name),
+                    )
+                    .label(span, format!("`{}` used here", name)),
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::validation::operations::test::messages;
+
+    #[test]
+    fn it_reports_invalid_fragment_definitions() {
+        assert_eq!(
+            messages(
+                r#"
+{ me { ...A ...Missing } }
+fragment A on User { name }
+fragment A on User { name }
+fragment Unused on User { name }
+fragment OnScalar on String { length }
+fragment OnUndefined on Undefined { name }
+"#
+            ),
+            [
+                "the fragment `Missing` is not defined",
+                "the fragment `A` is defined multiple times",
+                "the fragment `Unused` is never used",
+                "the fragment `OnScalar` is never used",
+                "fragments can only be on object, interface and union types, but `String` is a scalar type",
+                "the fragment `OnUndefined` is never used",
+                "the type `Undefined` is not defined",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_fragment_cycles_once() {
+        assert_eq!(
+            messages(
+                r#"
+{ me { ...A } }
+fragment A on User { friends { ...B } }
+fragment B on User { friends { ...C ...A } }
+fragment C on User { name }
+fragment D on User { ...D }
+"#
+            ),
+            [
+                "the fragment `A` cannot spread itself, but it does through `B` -> `A`",
+                "the fragment `D` is never used",
+                "the fragment `D` cannot spread itself, but it does through `D`",
+            ]
+        );
+    }
+}
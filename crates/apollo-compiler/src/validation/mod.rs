@@ -0,0 +1,118 @@
+//! Validation rules from the GraphQL specification.
+
+mod directives;
+mod field_merging;
+mod fragments;
+mod operations;
+mod schema;
+mod selections;
+mod values;
+mod variables;
+
+use std::collections::HashMap;
+
+use apollo_parser::ast::{self, AstNode};
+
+use crate::{
+    executable::fragment_name,
+    hir::{OperationType, TypeDefinition},
+    schema::builder::operation_type,
+    Diagnostic, ExecutableDocument, Schema, Span,
+};
+
+pub(crate) use schema::validate_schema;
+
+/// Validate an executable document against a schema, and return all
+/// problems found, starting with the document's syntax errors.
+///
+/// This runs the "Validation" rules of the specification on the operations
+/// and fragments of the document. The schema itself is not validated, see
+/// [`Schema::validate`].
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{validate, DiagnosticKind, ExecutableDocument, Schema};
+///
+/// let schema = Schema::parse("type Query { me: User } type User { name: String }");
+/// let document = ExecutableDocument::parse("query.graphql", "{ me { name age } }");
+///
+/// let diagnostics = validate(&schema, &document);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].kind(), &DiagnosticKind::UndefinedField);
+/// assert_eq!(diagnostics[0].message(), "the type `User` does not have a field `age`");
+/// ```
+pub fn validate(schema: &Schema, document: &ExecutableDocument) -> Vec<Diagnostic> {
+    let mut fragments = HashMap::new();
+    for fragment in document.fragments() {
+        if let Some(name) = fragment_name(&fragment) {
+            // Duplicate fragments are reported, and the first one is used.
+            fragments.entry(name).or_insert(fragment);
+        }
+    }
+    let mut validator = ExecutableValidator {
+        schema,
+        document,
+        fragments,
+        diagnostics: document.diagnostics().cloned().collect(),
+    };
+    validator.operations();
+    validator.fragments();
+    validator.overlapping_fields();
+    validator.diagnostics
+}
+
+/// The state shared by the rules that validate an executable document.
+pub(crate) struct ExecutableValidator<'a> {
+    pub(crate) schema: &'a Schema,
+    pub(crate) document: &'a ExecutableDocument,
+    /// The fragment definitions by name.
+    pub(crate) fragments: HashMap<String, ast::FragmentDefinition>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl ExecutableValidator<'_> {
+    pub(crate) fn span(&self, node: &impl AstNode) -> Span {
+        self.document.span(node)
+    }
+
+    /// Get the fragment definition called `name`.
+    pub(crate) fn fragment(&self, name: &str) -> Option<&ast::FragmentDefinition> {
+        self.fragments.get(name)
+    }
+}
+
+/// Get the type of an operation. Operations written as just a selection set
+/// are queries.
+pub(crate) fn operation_type_of(operation: &ast::OperationDefinition) -> OperationType {
+    operation
+        .operation_type()
+        .and_then(operation_type)
+        .unwrap_or(OperationType::Query)
+}
+
+/// Get the response name of a field: its alias, or its name.
+pub(crate) fn response_name(field: &ast::Field) -> Option<String> {
+    let name = match field.alias() {
+        Some(alias) => alias.name()?,
+        None => field.name()?,
+    };
+    Some(name.text().to_string())
+}
+
+/// Get the name of the type of an inline fragment's or fragment
+/// definition's type condition.
+pub(crate) fn type_condition_name(condition: Option<ast::TypeCondition>) -> Option<String> {
+    Some(condition?.named_type()?.name()?.text().to_string())
+}
+
+/// Describe the kind of a type, like "an input object type".
+pub(crate) fn type_kind(ty: &TypeDefinition) -> &'static str {
+    match ty {
+        TypeDefinition::Scalar(_) => "a scalar type",
+        TypeDefinition::Object(_) => "an object type",
+        TypeDefinition::Interface(_) => "an interface type",
+        TypeDefinition::Union(_) => "a union type",
+        TypeDefinition::Enum(_) => "an enum type",
+        TypeDefinition::InputObject(_) => "an input object type",
+    }
+}
@@ -0,0 +1,337 @@
+//! Rules for operation definitions.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Validation.Operations>.
+
+use std::collections::{HashMap, HashSet};
+
+use apollo_parser::ast::{self, AstNode};
+
+use crate::{
+    hir::{DirectiveLocation, OperationType},
+    validation::{operation_type_of, response_name, ExecutableValidator},
+    Diagnostic, DiagnosticKind,
+};
+
+impl ExecutableValidator<'_> {
+    /// Validate all operations, including their selection sets.
+    pub(crate) fn operations(&mut self) {
+        let operations: Vec<_> = self.document.operations().collect();
+        let mut names = HashMap::new();
+        for operation in &operations {
+            let span = self.span(operation);
+            match operation.name() {
+                Some(name) => {
+                    let name = name.text().to_string();
+                    if let Some(previous) = names.get(&name) {
+                        self.diagnostics.push(
+                            Diagnostic::new(
+                                DiagnosticKind::DuplicateDefinition,
+                                format!("the operation `{}` is defined multiple times", name),
+                            )
+                            .label(span, format!("`{}` redefined here", name))
+                            .label(*previous, format!("previous definition of `{}` here", name)),
+                        );
+                    } else {
+                        names.insert(name, span);
+                    }
+                }
+                None if operations.len() > 1 => self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::AnonymousOperationNotAlone,
+                        "an anonymous operation must be the only operation in the document",
+                    )
+                    .label(span, "anonymous operation defined here"),
+                ),
+                None => {}
+            }
+
+            let operation_type = operation_type_of(operation);
+            let root = self.schema.root_operation(operation_type);
+            if root.is_none() {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UnsupportedOperation,
+                        format!(
+                            "the schema does not define a {} root operation type",
+                            operation_type
+                        ),
+                    )
+                    .label(span, format!("{} operation defined here", operation_type)),
+                );
+            }
+            if operation_type == OperationType::Subscription {
+                self.subscription_root_fields(operation);
+            }
+            self.variables(operation);
+            let location = match operation_type {
+                OperationType::Query => DirectiveLocation::Query,
+                OperationType::Mutation => DirectiveLocation::Mutation,
+                OperationType::Subscription => DirectiveLocation::Subscription,
+            };
+            self.directives(operation.directives(), location);
+            if let Some(selection_set) = operation.selection_set() {
+                let root = root.and_then(|root| self.schema.type_by_name(root.name()));
+                self.selection_set(root, &selection_set);
+            }
+        }
+    }
+
+    /// Check that a subscription selects exactly one root field, which is
+    /// not an introspection field.
+    fn subscription_root_fields(&mut self, operation: &ast::OperationDefinition) {
+        let mut fields = Vec::new();
+        if let Some(selection_set) = operation.selection_set() {
+            self.collect_fields(&selection_set, &mut HashSet::new(), &mut fields);
+        }
+        let mut seen = HashSet::new();
+        fields.retain(|(name, _)| seen.insert(name.clone()));
+        let name = operation
+            .name()
+            .map(|name| format!("the subscription `{}`", name.text()))
+            .unwrap_or_else(|| "an anonymous subscription".to_string());
+        for (_, field) in &fields {
+            let field_name = match field.name() {
+                Some(field_name) if field_name.text().starts_with("__") => field_name,
+                _ => continue,
+            };
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::IntrospectionSubscriptionRootField,
+                    format!(
+                        "{} must not select the introspection field `{}` as its root field",
+                        name,
+                        field_name.text()
+                    ),
+                )
+                .label(self.span(field), "introspection field selected here"),
+            );
+        }
+        if fields.len() > 1 {
+            let mut diagnostic = Diagnostic::new(
+                DiagnosticKind::MultipleSubscriptionRootFields,
+                format!(
+                    "{} must select only one root field, but it selects {}",
+                    name,
+                    fields.len()
+                ),
+            );
+            for (name, field) in &fields {
+                diagnostic =
+                    diagnostic.label(self.span(field), format!("`{}` selected here", name));
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Collect the fields of a selection set by response name, including the
+    /// fields of the fragments it spreads.
+    fn collect_fields(
+        &self,
+        selection_set: &ast::SelectionSet,
+        visited: &mut HashSet<String>,
+        fields: &mut Vec<(String, ast::Field)>,
+    ) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    if let Some(name) = response_name(&field) {
+                        fields.push((name, field));
+                    }
+                }
+                ast::Selection::FragmentSpread(spread) => {
+                    let fragment = spread
+                        .fragment_name()
+                        .and_then(|name| name.name())
+                        .map(|name| name.text().to_string())
+                        .filter(|name| visited.insert(name.clone()))
+                        .and_then(|name| self.fragment(&name));
+                    if let Some(selection_set) = fragment.and_then(|f| f.selection_set()) {
+                        self.collect_fields(&selection_set, visited, fields);
+                    }
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    if let Some(selection_set) = inline.selection_set() {
+                        self.collect_fields(&selection_set, visited, fields);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the fragment spreads in a definition or selection set, including
+    /// the ones in nested selection sets, with the names of the fragments
+    /// they spread.
+    pub(crate) fn spreads_in(&self, node: &impl AstNode) -> Vec<(String, ast::FragmentSpread)> {
+        node.syntax()
+            .descendants()
+            .filter_map(ast::FragmentSpread::cast)
+            .filter_map(|spread| {
+                let name = spread.fragment_name()?.name()?.text().to_string();
+                Some((name, spread))
+            })
+            .collect()
+    }
+
+    /// Get the names of the fragments used by operations, directly or
+    /// through other fragments.
+    pub(crate) fn used_fragments(&self) -> HashSet<String> {
+        let mut used = HashSet::new();
+        let mut stack: Vec<_> = self
+            .document
+            .operations()
+            .flat_map(|operation| self.spreads_in(&operation))
+            .map(|(name, _)| name)
+            .collect();
+        while let Some(name) = stack.pop() {
+            if used.insert(name.clone()) {
+                if let Some(fragment) = self.fragment(&name) {
+                    stack.extend(self.spreads_in(fragment).into_iter().map(|(name, _)| name));
+                }
+            }
+        }
+        used
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{validate, DiagnosticKind, ExecutableDocument, Schema};
+
+    pub(crate) const SCHEMA: &str = r#"
+type Query { me: User pet(id: ID!, kind: String = "cat"): Pet pets(first: Int): [Pet!]! users(filter: UserFilter, ids: [ID!]): [User] }
+type Subscription { newPet: Pet newUser: User }
+interface Pet { name: String! }
+type Cat implements Pet { name: String! lives: Int }
+type Dog implements Pet { name: String! nickname: String! barks: Boolean }
+type User { name: String friends: [User] }
+union Animal = Cat | Dog
+enum Color { RED }
+input UserFilter { name: String! limit: Int = 10 }
+"#;
+
+    pub(crate) fn messages(input: &str) -> Vec<String> {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse("query.graphql", input);
+        validate(&schema, &document)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_valid_operations() {
+        assert_eq!(
+            messages(
+                r#"
+query A { me { name } pet(id: 1) { name ... on Cat { lives } } }
+query B { __typename pets { ...Names } }
+subscription C { ... on Subscription { newPet { name } } newPet { __typename } }
+fragment Names on Pet { __typename name }
+"#
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_operation_definitions() {
+        assert_eq!(
+            messages("{ me { name } } query A { me { name } } query A { me { name } }"),
+            [
+                "an anonymous operation must be the only operation in the document",
+                "the operation `A` is defined multiple times",
+            ]
+        );
+        assert_eq!(
+            messages("mutation { me }"),
+            ["the schema does not define a mutation root operation type"]
+        );
+    }
+
+    #[test]
+    fn it_reports_subscriptions_with_multiple_root_fields() {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse(
+            "query.graphql",
+            "subscription S { newPet { name } ...F } fragment F on Subscription { newUser { name } newPet { name } }",
+        );
+        let diagnostics = validate(&schema, &document);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind(),
+            &DiagnosticKind::MultipleSubscriptionRootFields
+        );
+        assert_eq!(
+            diagnostics[0].message(),
+            "the subscription `S` must select only one root field, but it selects 2"
+        );
+        assert_eq!(diagnostics[0].labels().len(), 2);
+    }
+
+    #[test]
+    fn it_reports_subscriptions_with_introspection_root_fields() {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse(
+            "query.graphql",
+            "subscription { __typename } subscription S { newPet { name } } subscription T { ... { __typename } }",
+        );
+        let diagnostics = validate(&schema, &document);
+        let messages: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| {
+                diagnostic.kind() == &DiagnosticKind::IntrospectionSubscriptionRootField
+            })
+            .map(|diagnostic| diagnostic.message())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "an anonymous subscription must not select the introspection field `__typename` as its root field",
+                "the subscription `T` must not select the introspection field `__typename` as its root field",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_includes_syntax_errors() {
+        let schema = Schema::parse(SCHEMA);
+        let document =
+            ExecutableDocument::parse("query.graphql", "type T { a: Int } { me { name } }");
+        let kinds: Vec<_> = validate(&schema, &document)
+            .iter()
+            .map(|d| d.kind().clone())
+            .collect();
+        assert_eq!(kinds, [DiagnosticKind::SyntaxError]);
+    }
+
+    #[test]
+    fn it_validates_documents_with_missing_names() {
+        let schema = Schema::parse(SCHEMA);
+        let inputs = [
+            "{ pets { ... on { name } } }",
+            "{ me @ }",
+            "{ me { ...  } }",
+            "query Q($: Int) { me { name } }",
+            "{ pet(: 1) { name } }",
+            "{ pet(id: $) { name } }",
+            "{ users(filter: { : 1 }) { name } }",
+            "{ : me { name } }",
+            "fragment on User { name } { me { ... } }",
+            "subscription { newPet @ { name } }",
+            "query Q($a: ) { me { name } }",
+        ];
+        for input in inputs {
+            let document = ExecutableDocument::parse("query.graphql", input);
+            let diagnostics = validate(&schema, &document);
+            assert!(
+                diagnostics
+                    .iter()
+                    .any(|d| d.kind() == &DiagnosticKind::SyntaxError),
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,773 @@
+//! The "Type System" validation rules.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Type-System>.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    hir::{
+        Directive, DirectiveLocation, FieldDefinition, InputObjectType, InputValueDefinition, Type,
+        TypeDefinition,
+    },
+    validation::{
+        directives::{type_location, validate_directives},
+        type_kind,
+        values::invalid_value,
+    },
+    Diagnostic, DiagnosticKind, Schema, Span,
+};
+
+/// Run the type system validation rules on the user-defined parts of
+/// `schema`. Built-in definitions are assumed to be valid.
+pub(crate) fn validate_schema(schema: &Schema) -> Vec<Diagnostic> {
+    let mut validator = SchemaValidator {
+        schema,
+        diagnostics: Vec::new(),
+    };
+    validator.root_operations();
+    validator.directives(DirectiveLocation::Schema, schema.schema_directives());
+    for definition in schema.directive_definitions() {
+        if definition.is_built_in() {
+            continue;
+        }
+        let name = definition.name();
+        validator.reserved_name(name, "directive", definition.span());
+        validator.arguments(
+            &format!("@{}", name),
+            DirectiveLocation::ArgumentDefinition,
+            definition.arguments(),
+        );
+    }
+    for ty in schema.types() {
+        if !ty.is_built_in() {
+            validator.type_definition(ty);
+        }
+    }
+    validator.input_object_cycles();
+    validator.diagnostics
+}
+
+struct SchemaValidator<'a> {
+    schema: &'a Schema,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> SchemaValidator<'a> {
+    fn root_operations(&mut self) {
+        for (operation, name) in &self.schema.root_operations {
+            let span = self
+                .schema
+                .definition_span()
+                .or_else(|| self.schema.type_by_name(name).map(|ty| ty.span()));
+            let mut diagnostic = match self.schema.type_by_name(name) {
+                Some(TypeDefinition::Object(_)) => continue,
+                Some(ty) => Diagnostic::new(
+                    DiagnosticKind::InvalidRootOperationType,
+                    format!(
+                        "the {} root operation type must be an object type, but `{}` is {}",
+                        operation,
+                        name,
+                        type_kind(ty)
+                    ),
+                ),
+                None => Diagnostic::new(
+                    DiagnosticKind::UndefinedType,
+                    format!(
+                        "the {} root operation type `{}` is not defined",
+                        operation, name
+                    ),
+                ),
+            };
+            if let Some(span) = span {
+                diagnostic = diagnostic.label(span, format!("{} root operation type", operation));
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn type_definition(&mut self, ty: &TypeDefinition) {
+        self.reserved_name(ty.name(), "type", ty.span());
+        self.directives(type_location(ty), ty.directives());
+        match ty {
+            TypeDefinition::Scalar(_) => {}
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) => {
+                if ty.fields().is_empty() {
+                    self.empty_type(ty, "fields");
+                }
+                self.fields(ty.name(), ty.fields());
+                self.implementations(ty);
+            }
+            TypeDefinition::Union(union) => {
+                if union.members().is_empty() {
+                    self.empty_type(ty, "member types");
+                }
+                let mut seen = HashSet::new();
+                for member in union.members() {
+                    if !seen.insert(member) {
+                        self.diagnostics.push(
+                            Diagnostic::new(
+                                DiagnosticKind::DuplicateDefinition,
+                                format!(
+                                    "the union `{}` includes `{}` multiple times",
+                                    ty.name(),
+                                    member
+                                ),
+                            )
+                            .label(ty.span(), format!("`{}` defined here", ty.name())),
+                        );
+                        continue;
+                    }
+                    match self.schema.type_by_name(member) {
+                        Some(TypeDefinition::Object(_)) => {}
+                        Some(member_ty) => self.diagnostics.push(
+                            Diagnostic::new(
+                                DiagnosticKind::InvalidUnionMember,
+                                format!(
+                                    "the union `{}` can only include object types, but `{}` is {}",
+                                    ty.name(),
+                                    member,
+                                    type_kind(member_ty)
+                                ),
+                            )
+                            .label(ty.span(), format!("`{}` defined here", ty.name()))
+                            .label(member_ty.span(), format!("`{}` defined here", member)),
+                        ),
+                        None => self.undefined_type(member, ty.span()),
+                    }
+                }
+            }
+            TypeDefinition::Enum(enum_ty) => {
+                if enum_ty.values().is_empty() {
+                    self.empty_type(ty, "values");
+                }
+                let mut seen = HashMap::new();
+                for value in enum_ty.values() {
+                    self.directives(DirectiveLocation::EnumValue, value.directives());
+                    let name = value.value();
+                    if let Some(previous) = seen.insert(name, value.span()) {
+                        self.duplicate(
+                            format!("the enum value `{}.{}`", ty.name(), name),
+                            value.span(),
+                            previous,
+                        );
+                    }
+                }
+            }
+            TypeDefinition::InputObject(input) => {
+                if input.fields().is_empty() {
+                    self.empty_type(ty, "fields");
+                }
+                self.arguments(
+                    ty.name(),
+                    DirectiveLocation::InputFieldDefinition,
+                    input.fields(),
+                );
+            }
+        }
+    }
+
+    fn fields(&mut self, type_name: &str, fields: &[FieldDefinition]) {
+        let mut seen = HashMap::new();
+        for field in fields {
+            let coordinate = format!("{}.{}", type_name, field.name());
+            if let Some(previous) = seen.insert(field.name(), field.span()) {
+                self.duplicate(
+                    format!("the field `{}`", coordinate),
+                    field.span(),
+                    previous,
+                );
+            }
+            self.reserved_name(field.name(), "field", field.span());
+            self.directives(DirectiveLocation::FieldDefinition, field.directives());
+            match self.schema.resolve_type(field.ty()) {
+                Some(ty) if !ty.is_output_type() => self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidFieldType,
+                        format!(
+                            "the field `{}` must have an output type, but `{}` is an input object type",
+                            coordinate,
+                            ty.name()
+                        ),
+                    )
+                    .label(field.span(), format!("`{}` defined here", coordinate)),
+                ),
+                Some(_) => {}
+                None => self.undefined_type(field.ty().name(), field.span()),
+            }
+            self.arguments(
+                &coordinate,
+                DirectiveLocation::ArgumentDefinition,
+                field.arguments(),
+            );
+        }
+    }
+
+    /// Check the arguments of a field or directive, or the fields of an input
+    /// object. `parent` is the name of the field, directive or input object.
+    fn arguments(
+        &mut self,
+        parent: &str,
+        location: DirectiveLocation,
+        arguments: &[InputValueDefinition],
+    ) {
+        let what = match location {
+            DirectiveLocation::InputFieldDefinition => "input field",
+            _ => "argument",
+        };
+        let mut seen = HashMap::new();
+        for arg in arguments {
+            let coordinate = format!("{}.{}", parent, arg.name());
+            if let Some(previous) = seen.insert(arg.name(), arg.span()) {
+                self.duplicate(format!("`{}`", coordinate), arg.span(), previous);
+            }
+            self.reserved_name(arg.name(), what, arg.span());
+            match self.schema.resolve_type(arg.ty()) {
+                Some(ty) if !ty.is_input_type() => self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidFieldType,
+                        format!(
+                            "`{}` must have an input type, but `{}` is {}",
+                            coordinate,
+                            ty.name(),
+                            type_kind(ty)
+                        ),
+                    )
+                    .label(arg.span(), format!("`{}` defined here", coordinate)),
+                ),
+                Some(_) => {}
+                None => self.undefined_type(arg.ty().name(), arg.span()),
+            }
+            if let Some(default) = arg.default_value() {
+                let what = format!("the default value of `{}`", coordinate);
+                self.diagnostics.extend(invalid_value(
+                    self.schema,
+                    &what,
+                    arg.ty(),
+                    default,
+                    arg.span(),
+                ));
+            }
+            self.directives(location, arg.directives());
+        }
+    }
+
+    fn directives(&mut self, location: DirectiveLocation, directives: &[Directive]) {
+        self.diagnostics
+            .extend(validate_directives(self.schema, location, directives));
+    }
+
+    /// Check that an object or interface type correctly implements its
+    /// interfaces.
+    fn implementations(&mut self, ty: &TypeDefinition) {
+        let interfaces = ty.implements_interfaces();
+        let mut seen = HashSet::new();
+        for name in interfaces {
+            if !seen.insert(name) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::DuplicateDefinition,
+                        format!("`{}` implements `{}` multiple times", ty.name(), name),
+                    )
+                    .label(ty.span(), format!("`{}` defined here", ty.name())),
+                );
+                continue;
+            }
+            let interface = match self.schema.type_by_name(name) {
+                Some(interface @ TypeDefinition::Interface(_)) => interface,
+                Some(other) => {
+                    self.invalid_implementation(
+                        ty,
+                        format!(
+                            "`{}` cannot implement `{}`, which is {}",
+                            ty.name(),
+                            name,
+                            type_kind(other)
+                        ),
+                        other.span(),
+                        format!("`{}` defined here", name),
+                    );
+                    continue;
+                }
+                None => {
+                    self.undefined_type(name, ty.span());
+                    continue;
+                }
+            };
+            if name == ty.name() {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidImplementation,
+                        format!("the interface `{}` cannot implement itself", name),
+                    )
+                    .label(ty.span(), format!("`{}` defined here", name)),
+                );
+                continue;
+            }
+
+            for transitive in interface.implements_interfaces() {
+                if transitive != ty.name() && !interfaces.contains(transitive) {
+                    self.invalid_implementation(
+                        ty,
+                        format!(
+                            "`{}` must also implement `{}`, because `{}` implements it",
+                            ty.name(),
+                            transitive,
+                            name
+                        ),
+                        interface.span(),
+                        format!("`{}` implements `{}` here", name, transitive),
+                    );
+                }
+            }
+            for interface_field in interface.fields() {
+                self.implementation_field(ty, name, interface_field);
+            }
+        }
+    }
+
+    /// Check that `ty` correctly implements the field `interface_field` of
+    /// the interface called `interface`.
+    fn implementation_field(
+        &mut self,
+        ty: &TypeDefinition,
+        interface: &str,
+        interface_field: &FieldDefinition,
+    ) {
+        let interface_coordinate = format!("{}.{}", interface, interface_field.name());
+        let field = match ty
+            .fields()
+            .iter()
+            .find(|field| field.name() == interface_field.name())
+        {
+            Some(field) => field,
+            None => {
+                self.invalid_implementation(
+                    ty,
+                    format!(
+                        "`{}` must define the field `{}`, because it implements `{}`",
+                        ty.name(),
+                        interface_field.name(),
+                        interface
+                    ),
+                    interface_field.span(),
+                    format!("`{}` defined here", interface_coordinate),
+                );
+                return;
+            }
+        };
+        let coordinate = format!("{}.{}", ty.name(), field.name());
+
+        if !self.is_valid_implementation_type(field.ty(), interface_field.ty()) {
+            self.invalid_implementation(
+                ty,
+                format!(
+                    "the type of `{}` must be `{}` or a subtype of it, but it is `{}`",
+                    coordinate,
+                    interface_field.ty(),
+                    field.ty()
+                ),
+                interface_field.span(),
+                format!("`{}` defined here", interface_coordinate),
+            );
+        }
+        for interface_arg in interface_field.arguments() {
+            match field.argument(interface_arg.name()) {
+                Some(arg) if arg.ty() == interface_arg.ty() => {}
+                Some(arg) => self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidImplementation,
+                        format!(
+                            "the argument `{}.{}` must have the type `{}`, like in `{}`",
+                            coordinate,
+                            arg.name(),
+                            interface_arg.ty(),
+                            interface_coordinate
+                        ),
+                    )
+                    .label(arg.span(), format!("argument of type `{}` here", arg.ty()))
+                    .label(
+                        interface_arg.span(),
+                        format!("argument of type `{}` here", interface_arg.ty()),
+                    ),
+                ),
+                None => self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidImplementation,
+                        format!(
+                            "`{}` must define the argument `{}`, like in `{}`",
+                            coordinate,
+                            interface_arg.name(),
+                            interface_coordinate
+                        ),
+                    )
+                    .label(field.span(), format!("`{}` defined here", coordinate))
+                    .label(
+                        interface_arg.span(),
+                        format!("`{}` defined here", interface_arg.name()),
+                    ),
+                ),
+            }
+        }
+        for arg in field.arguments() {
+            if arg.is_required() && interface_field.argument(arg.name()).is_none() {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::InvalidImplementation,
+                        format!(
+                            "the argument `{}.{}` must not be required, because it is not defined in `{}`",
+                            coordinate,
+                            arg.name(),
+                            interface_coordinate
+                        ),
+                    )
+                    .label(arg.span(), "required argument defined here"),
+                );
+            }
+        }
+    }
+
+    /// Check whether a field of type `field_ty` can implement an interface
+    /// field of type `interface_ty`: return types of fields are covariant.
+    fn is_valid_implementation_type(&self, field_ty: &Type, interface_ty: &Type) -> bool {
+        match (field_ty, interface_ty) {
+            (Type::NonNull(field_ty), Type::NonNull(interface_ty)) => {
+                self.is_valid_implementation_type(field_ty, interface_ty)
+            }
+            (Type::NonNull(field_ty), interface_ty) => {
+                self.is_valid_implementation_type(field_ty, interface_ty)
+            }
+            (Type::List(field_ty), Type::List(interface_ty)) => {
+                self.is_valid_implementation_type(field_ty, interface_ty)
+            }
+            (Type::Named(field_name), Type::Named(interface_name)) => {
+                if field_name == interface_name {
+                    return true;
+                }
+                match self.schema.type_by_name(interface_name) {
+                    Some(TypeDefinition::Union(union)) => union.members().contains(field_name),
+                    Some(TypeDefinition::Interface(_)) => self
+                        .schema
+                        .type_by_name(field_name)
+                        .is_some_and(|ty| ty.implements_interfaces().contains(interface_name)),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Report input objects that reference themselves through a chain of
+    /// non-null fields, which makes it impossible to provide a value for
+    /// them.
+    fn input_object_cycles(&mut self) {
+        let schema = self.schema;
+        let mut visited = HashSet::new();
+        for ty in schema.types() {
+            if let TypeDefinition::InputObject(input) = ty {
+                if !input.is_built_in() {
+                    let mut path = Vec::new();
+                    let mut path_index = HashMap::new();
+                    self.detect_cycle(input, &mut visited, &mut path, &mut path_index);
+                }
+            }
+        }
+    }
+
+    fn detect_cycle(
+        &mut self,
+        input: &'a InputObjectType,
+        visited: &mut HashSet<&'a str>,
+        path: &mut Vec<(&'a str, &'a InputValueDefinition)>,
+        path_index: &mut HashMap<&'a str, usize>,
+    ) {
+        let schema = self.schema;
+        if !visited.insert(input.name()) {
+            return;
+        }
+        path_index.insert(input.name(), path.len());
+        for field in input.fields() {
+            let field_ty = match field.ty() {
+                Type::NonNull(inner) => match &**inner {
+                    Type::Named(name) => match schema.type_by_name(name) {
+                        Some(TypeDefinition::InputObject(field_ty)) => field_ty,
+                        _ => continue,
+                    },
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            path.push((input.name(), field));
+            match path_index.get(field_ty.name()) {
+                Some(&index) => {
+                    let cycle = &path[index..];
+                    let fields: Vec<_> = cycle
+                        .iter()
+                        .map(|(owner, field)| format!("`{}.{}`", owner, field.name()))
+                        .collect();
+                    let mut diagnostic = Diagnostic::new(
+                        DiagnosticKind::InputObjectCycle,
+                        format!(
+                            "the input object `{}` references itself through the non-null fields {}",
+                            field_ty.name(),
+                            fields.join(", ")
+                        ),
+                    );
+                    for (owner, field) in cycle {
+                        diagnostic = diagnostic.label(
+                            field.span(),
+                            format!("`{}.{}` references `{}`", owner, field.name(), field.ty()),
+                        );
+                    }
+                    self.diagnostics.push(diagnostic);
+                }
+                None => self.detect_cycle(field_ty, visited, path, path_index),
+            }
+            path.pop();
+        }
+        path_index.remove(input.name());
+    }
+
+    fn reserved_name(&mut self, name: &str, what: &str, span: Span) {
+        if name.starts_with("__") {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::ReservedName,
+                    format!(
+                        "the {} name `{}` must not begin with `__`, which is reserved for introspection",
+                        what, name
+                    ),
+                )
+                .label(span, format!("{} `{}` defined here", what, name)),
+            );
+        }
+    }
+
+    fn empty_type(&mut self, ty: &TypeDefinition, items: &str) {
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagnosticKind::EmptyType,
+                format!(
+                    "`{}` must define one or more {}, because it is {}",
+                    ty.name(),
+                    items,
+                    type_kind(ty)
+                ),
+            )
+            .label(ty.span(), format!("`{}` defined here", ty.name())),
+        );
+    }
+
+    fn undefined_type(&mut self, name: &str, span: Span) {
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagnosticKind::UndefinedType,
+                format!("the type `{}` is not defined", name),
+            )
+            .label(span, format!("`{}` used here", name)),
+        );
+    }
+
+    fn duplicate(&mut self, item: String, span: Span, previous: Span) {
+        self.diagnostics.push(
+            Diagnostic::new(
+                DiagnosticKind::DuplicateDefinition,
+                format!("{} is defined multiple times", item),
+            )
+            .label(span, "redefined here")
+            .label(previous, "previous definition here"),
+        );
+    }
+
+    fn invalid_implementation(
+        &mut self,
+        ty: &TypeDefinition,
+        message: String,
+        span: Span,
+        label: String,
+    ) {
+        self.diagnostics.push(
+            Diagnostic::new(DiagnosticKind::InvalidImplementation, message)
+                .label(ty.span(), format!("`{}` defined here", ty.name()))
+                .label(span, label),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{DiagnosticKind, Schema, SchemaBuilder};
+
+    fn messages(input: &str) -> Vec<String> {
+        Schema::parse(input)
+            .validate()
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_a_valid_schema() {
+        let schema = Schema::parse(
+            r#"
+type Query { node(id: ID!): Node search: [Result!]! }
+interface Node { id: ID! }
+interface Resource implements Node { id: ID! url(scale: Int): String }
+type Image implements Resource & Node { id: ID! url(scale: Int, format: String = "png"): String! }
+union Result = Image
+enum Format { PNG JPG }
+input Size { width: Int! height: Int! next: Size }
+directive @cache(maxAge: Int) on FIELD_DEFINITION
+"#,
+        );
+        assert_eq!(schema.validate(), []);
+    }
+
+    #[test]
+    fn it_reports_empty_types() {
+        let mut builder = SchemaBuilder::new();
+        for (i, input) in [
+            "type Query { a: Int }",
+            "type A",
+            "interface B",
+            "union C enum D input E",
+        ]
+        .iter()
+        .enumerate()
+        {
+            builder.add_document(format!("{}.graphql", i), *input);
+        }
+        let diagnostics = builder.build().validate();
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`A` must define one or more fields, because it is an object type",
+                "`B` must define one or more fields, because it is an interface type",
+                "`C` must define one or more member types, because it is a union type",
+                "`D` must define one or more values, because it is an enum type",
+                "`E` must define one or more fields, because it is an input object type",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_implementations() {
+        assert_eq!(
+            messages(
+                r#"
+type Query implements A & B & Query { a: Int b(x: Int): String c(y: Int!): Int }
+interface A { a: Int! }
+interface B implements C { b(x: String): String c: Int }
+interface C { c: Int }
+"#
+            ),
+            [
+                "the type of `Query.a` must be `Int!` or a subtype of it, but it is `Int`",
+                "`Query` must also implement `C`, because `B` implements it",
+                "the argument `Query.b.x` must have the type `String`, like in `B.b`",
+                "the argument `Query.c.y` must not be required, because it is not defined in `B.c`",
+                "`Query` cannot implement `Query`, which is an object type",
+            ]
+        );
+        assert_eq!(
+            messages("type Query implements I { b: Int } interface I { a(x: Int): Int }"),
+            ["`Query` must define the field `a`, because it implements `I`"]
+        );
+    }
+
+    #[test]
+    fn it_accepts_covariant_field_types() {
+        let schema = Schema::parse(
+            r#"
+type Query implements I { a: Cat! b: [Cat!]! c: Cat }
+interface I { a: Pet b: [Pet] c: Animal }
+interface Pet { name: String }
+type Cat implements Pet { name: String }
+union Animal = Cat
+"#,
+        );
+        assert_eq!(schema.validate(), []);
+    }
+
+    #[test]
+    fn it_reports_invalid_references() {
+        assert_eq!(
+            messages(
+                r#"
+type Query { a: Missing b(input: Query): Int c: In }
+union U = Query | In | Other
+input In { a: Query }
+"#
+            ),
+            [
+                "the type `Missing` is not defined",
+                "`Query.b.input` must have an input type, but `Query` is an object type",
+                "the field `Query.c` must have an output type, but `In` is an input object type",
+                "the union `U` can only include object types, but `In` is an input object type",
+                "the type `Other` is not defined",
+                "`In.a` must have an input type, but `Query` is an object type",
+            ]
+        );
+        assert_eq!(
+            messages("schema { query: Q mutation: E } enum E { A }"),
+            [
+                "the query root operation type `Q` is not defined",
+                "the mutation root operation type must be an object type, but `E` is an enum type",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_duplicate_and_reserved_names() {
+        let diagnostics = Schema::parse(
+            r#"
+type Query { a: Int a(x: Int, x: Int): Int __b: Int }
+enum E { A A }
+type __Mine { a: Int }
+directive @__d(__x: Int) on FIELD
+"#,
+        )
+        .validate();
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "the directive name `__d` must not begin with `__`, which is reserved for introspection",
+                "the argument name `__x` must not begin with `__`, which is reserved for introspection",
+                "the field `Query.a` is defined multiple times",
+                "`Query.a.x` is defined multiple times",
+                "the field name `__b` must not begin with `__`, which is reserved for introspection",
+                "the enum value `E.A` is defined multiple times",
+                "the type name `__Mine` must not begin with `__`, which is reserved for introspection",
+            ]
+        );
+        assert_eq!(diagnostics[2].kind(), &DiagnosticKind::DuplicateDefinition);
+        assert_eq!(diagnostics[2].labels().len(), 2);
+    }
+
+    #[test]
+    fn it_reports_input_object_cycles() {
+        let diagnostics = Schema::parse(
+            r#"
+type Query { a: Int }
+input A { b: B! ok: A }
+input B { c: C! list: [A!]! }
+input C { a: A! }
+input Self { self: Self! }
+"#,
+        )
+        .validate();
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.message()).collect();
+        assert_eq!(
+            messages,
+            [
+                "the input object `A` references itself through the non-null fields `A.b`, `B.c`, `C.a`",
+                "the input object `Self` references itself through the non-null fields `Self.self`",
+            ]
+        );
+        assert_eq!(diagnostics[0].kind(), &DiagnosticKind::InputObjectCycle);
+        assert_eq!(diagnostics[0].labels().len(), 3);
+    }
+}
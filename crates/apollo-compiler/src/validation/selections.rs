@@ -0,0 +1,355 @@
+//! Rules for fields, arguments and fragment spreads in selection sets.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Validation.Fields>.
+
+use std::collections::HashMap;
+
+use apollo_parser::ast;
+
+use crate::{
+    hir::{DirectiveLocation, FieldDefinition, InputValueDefinition, TypeDefinition},
+    schema::builder::value,
+    validation::{type_condition_name, values::invalid_value, ExecutableValidator},
+    Diagnostic, DiagnosticKind, Span,
+};
+
+impl<'a> ExecutableValidator<'a> {
+    /// Validate a selection set. `parent` is the type it selects fields of,
+    /// or `None` if it is unknown, in which case only the rules that do not
+    /// depend on the type are checked.
+    pub(crate) fn selection_set(
+        &mut self,
+        parent: Option<&'a TypeDefinition>,
+        selection_set: &ast::SelectionSet,
+    ) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => self.field(parent, &field),
+                ast::Selection::FragmentSpread(spread) => self.fragment_spread(parent, &spread),
+                ast::Selection::InlineFragment(inline) => self.inline_fragment(parent, &inline),
+            }
+        }
+    }
+
+    fn field(&mut self, parent: Option<&'a TypeDefinition>, field: &ast::Field) {
+        let schema = self.schema;
+        let name = match field.name() {
+            Some(name) => name.text().to_string(),
+            None => return,
+        };
+        let definition = parent.and_then(|parent| {
+            let definition = schema.field(parent.name(), &name);
+            if definition.is_none() {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UndefinedField,
+                        format!(
+                            "the type `{}` does not have a field `{}`",
+                            parent.name(),
+                            name
+                        ),
+                    )
+                    .label(self.span(field), format!("field `{}` selected here", name))
+                    .label(parent.span(), format!("`{}` defined here", parent.name())),
+                );
+            }
+            definition
+        });
+        self.field_arguments(field, definition);
+        self.directives(field.directives(), DirectiveLocation::Field);
+
+        let ty = definition.and_then(|definition| schema.resolve_type(definition.ty()));
+        match (ty, field.selection_set()) {
+            (Some(ty), Some(_)) if ty.is_leaf_type() => self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::UnexpectedSubselection,
+                    format!(
+                        "the field `{}` cannot have a selection set, because its type `{}` is a leaf type",
+                        name,
+                        ty.name()
+                    ),
+                )
+                .label(self.span(field), format!("field `{}` selected here", name)),
+            ),
+            (Some(ty), None) if ty.is_composite_type() => self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::MissingSubselection,
+                    format!(
+                        "the field `{}` must have a selection set, because its type `{}` is a composite type",
+                        name,
+                        ty.name()
+                    ),
+                )
+                .label(self.span(field), format!("field `{}` selected here", name)),
+            ),
+            _ => {}
+        }
+        if let Some(selection_set) = field.selection_set() {
+            let ty = ty.filter(|ty| ty.is_composite_type());
+            self.selection_set(ty, &selection_set);
+        }
+    }
+
+    /// Check that the arguments of a field are defined, unique, and that
+    /// all required arguments are provided.
+    fn field_arguments(&mut self, field: &ast::Field, definition: Option<&FieldDefinition>) {
+        let mut seen: HashMap<String, Span> = HashMap::new();
+        for arg in field
+            .arguments()
+            .into_iter()
+            .flat_map(|args| args.arguments())
+        {
+            let name = match arg.name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            let span = self.span(&arg);
+            if let Some(previous) = seen.get(&name) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::DuplicateArgument,
+                        format!("the argument `{}` is provided multiple times", name),
+                    )
+                    .label(span, format!("`{}` provided again here", name))
+                    .label(*previous, format!("`{}` first provided here", name)),
+                );
+                continue;
+            }
+            seen.insert(name.clone(), span);
+            if let Some(definition) = definition {
+                if let Some(arg_definition) = definition.argument(&name) {
+                    let what = format!("the argument `{}`", name);
+                    self.argument_value(&what, arg_definition, &arg);
+                } else {
+                    self.diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticKind::UndefinedArgument,
+                            format!(
+                                "the field `{}` does not have an argument `{}`",
+                                definition.name(),
+                                name
+                            ),
+                        )
+                        .label(span, format!("argument `{}` provided here", name))
+                        .label(
+                            definition.span(),
+                            format!("field `{}` defined here", definition.name()),
+                        ),
+                    );
+                }
+            }
+        }
+
+        let definition = match definition {
+            Some(definition) => definition,
+            None => return,
+        };
+        for arg in definition.arguments() {
+            if arg.is_required() && !seen.contains_key(arg.name()) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::MissingArgument,
+                        format!(
+                            "the field `{}` requires the argument `{}`",
+                            definition.name(),
+                            arg.name()
+                        ),
+                    )
+                    .label(
+                        self.span(field),
+                        format!("field `{}` selected here", definition.name()),
+                    )
+                    .label(
+                        arg.span(),
+                        format!("argument `{}` defined here", arg.name()),
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Check that the value of an argument is valid for its definition.
+    fn argument_value(
+        &mut self,
+        what: &str,
+        definition: &InputValueDefinition,
+        arg: &ast::Argument,
+    ) {
+        let value = match arg.value().and_then(value) {
+            Some(value) => value,
+            None => return,
+        };
+        let span = self.span(arg);
+        self.diagnostics.extend(invalid_value(
+            self.schema,
+            what,
+            definition.ty(),
+            &value,
+            span,
+        ));
+    }
+
+    fn fragment_spread(
+        &mut self,
+        parent: Option<&'a TypeDefinition>,
+        spread: &ast::FragmentSpread,
+    ) {
+        self.directives(spread.directives(), DirectiveLocation::FragmentSpread);
+        let name = match spread.fragment_name().and_then(|name| name.name()) {
+            Some(name) => name.text().to_string(),
+            None => return,
+        };
+        let fragment = match self.fragment(&name) {
+            Some(fragment) => fragment,
+            None => {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::UndefinedFragment,
+                        format!("the fragment `{}` is not defined", name),
+                    )
+                    .label(self.span(spread), format!("`{}` spread here", name)),
+                );
+                return;
+            }
+        };
+        // Unknown type conditions are reported with the fragment definition.
+        let condition = type_condition_name(fragment.type_condition())
+            .and_then(|name| self.schema.type_by_name(&name));
+        if let (Some(parent), Some(condition)) = (parent, condition) {
+            let what = format!("the fragment `{}`", name);
+            self.spread_possibility(&what, parent, condition, self.span(spread));
+        }
+    }
+
+    fn inline_fragment(
+        &mut self,
+        parent: Option<&'a TypeDefinition>,
+        inline: &ast::InlineFragment,
+    ) {
+        self.directives(inline.directives(), DirectiveLocation::InlineFragment);
+        let ty = match inline.type_condition() {
+            Some(condition) => {
+                let ty = self.type_condition(&condition);
+                if let (Some(parent), Some(ty)) = (parent, ty) {
+                    self.spread_possibility("an inline fragment", parent, ty, self.span(inline));
+                }
+                ty
+            }
+            None => parent,
+        };
+        if let Some(selection_set) = inline.selection_set() {
+            self.selection_set(ty, &selection_set);
+        }
+    }
+
+    /// Check that a fragment on `condition` can apply to values of the
+    /// `parent` type, that is, that the types share a possible type.
+    fn spread_possibility(
+        &mut self,
+        what: &str,
+        parent: &TypeDefinition,
+        condition: &TypeDefinition,
+        span: Span,
+    ) {
+        if !parent.is_composite_type() || !condition.is_composite_type() {
+            return;
+        }
+        let parent_types = self.schema.possible_types(parent.name());
+        let possible = self
+            .schema
+            .possible_types(condition.name())
+            .iter()
+            .any(|ty| parent_types.iter().any(|parent| parent.name() == ty.name()));
+        if !possible {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::ImpossibleFragmentSpread,
+                    format!(
+                        "{} on `{}` cannot be spread here, because values of type `{}` can never be of type `{}`",
+                        what,
+                        condition.name(),
+                        parent.name(),
+                        condition.name()
+                    ),
+                )
+                .label(span, "fragment spread here"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::validation::operations::test::messages;
+
+    #[test]
+    fn it_reports_undefined_fields_and_arguments() {
+        assert_eq!(
+            messages(
+                r#"
+{
+  me { name age }
+  pet(id: 1, id: 2, size: 3) { name }
+  pets { lives }
+  nope { name }
+  __schema { queryType { name } }
+  __type(name: "Pet") { name }
+}
+"#
+            ),
+            [
+                "the type `User` does not have a field `age`",
+                "the argument `id` is provided multiple times",
+                "the field `pet` does not have an argument `size`",
+                "the type `Pet` does not have a field `lives`",
+                "the type `Query` does not have a field `nope`",
+            ]
+        );
+        assert_eq!(
+            messages("{ me { __schema { queryType { name } } } }"),
+            ["the type `User` does not have a field `__schema`"]
+        );
+    }
+
+    #[test]
+    fn it_reports_missing_arguments() {
+        assert_eq!(
+            messages("{ pet { name } pets { name } }"),
+            ["the field `pet` requires the argument `id`"]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_leaf_selections() {
+        assert_eq!(
+            messages("{ me { name { length } } pets }"),
+            [
+                "the field `name` cannot have a selection set, because its type `String` is a leaf type",
+                "the field `pets` must have a selection set, because its type `Pet` is a composite type",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_impossible_spreads() {
+        assert_eq!(
+            messages(
+                r#"
+{
+  me { ... on Cat { lives } ...CatFields }
+  pets { ... on Animal { __typename } ... on Dog { barks } ... on User { name } ... on Color { name } }
+}
+fragment CatFields on Cat { lives }
+"#
+            ),
+            [
+                "an inline fragment on `Cat` cannot be spread here, because values of type `User` can never be of type `Cat`",
+                "the fragment `CatFields` on `Cat` cannot be spread here, because values of type `User` can never be of type `Cat`",
+                "an inline fragment on `User` cannot be spread here, because values of type `Pet` can never be of type `User`",
+                "fragments can only be on object, interface and union types, but `Color` is an enum type",
+            ]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! The "Values of Correct Type" rule, for the values of arguments, input
+//! fields and default values.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Values-of-Correct-Type>.
+
+use std::collections::HashSet;
+
+use crate::{
+    hir::{Type, TypeDefinition, Value},
+    Diagnostic, DiagnosticKind, Schema, Span,
+};
+
+/// Check that `value` is a valid value of the input type `ty`, and create a
+/// diagnostic if it is not. `what` describes where the value is provided,
+/// like "the argument `id`".
+///
+/// Variables are not checked here, see the variable usage rules.
+pub(crate) fn invalid_value(
+    schema: &Schema,
+    what: &str,
+    ty: &Type,
+    value: &Value,
+    span: Span,
+) -> Option<Diagnostic> {
+    let error = value_error(schema, ty, value)?;
+    Some(
+        Diagnostic::new(
+            DiagnosticKind::InvalidValue,
+            format!("invalid value for {}: {}", what, error),
+        )
+        .label(span, format!("`{}` expected here", ty)),
+    )
+}
+
+/// Get why `value` is not a valid value of the input type `ty`, if it is
+/// not.
+fn value_error(schema: &Schema, ty: &Type, value: &Value) -> Option<String> {
+    match (ty, value) {
+        (_, Value::Variable(_)) => None,
+        (Type::NonNull(_), Value::Null) => Some(mismatch(ty, value)),
+        (Type::NonNull(ty), value) => value_error(schema, ty, value),
+        (_, Value::Null) => None,
+        (Type::List(item), Value::List(values)) => values
+            .iter()
+            .find_map(|value| value_error(schema, item, value)),
+        // A single value is coerced to a list of one item.
+        (Type::List(item), value) => value_error(schema, item, value),
+        (Type::Named(name), value) => match schema.type_by_name(name)? {
+            TypeDefinition::Scalar(_) => scalar_error(schema, ty, value),
+            TypeDefinition::Enum(enum_ty) => match value {
+                Value::Enum(name) if enum_ty.value(name).is_some() => None,
+                Value::Enum(name) => Some(format!(
+                    "`{}` is not a value of the enum `{}`",
+                    name,
+                    enum_ty.name()
+                )),
+                _ => Some(mismatch(ty, value)),
+            },
+            TypeDefinition::InputObject(input_object) => {
+                let fields = match value {
+                    Value::Object(fields) => fields,
+                    _ => return Some(mismatch(ty, value)),
+                };
+                let mut seen = HashSet::new();
+                for (name, value) in fields {
+                    if !seen.insert(name.as_str()) {
+                        return Some(format!("the field `{}` is provided multiple times", name));
+                    }
+                    let field = match input_object.field(name) {
+                        Some(field) => field,
+                        None => {
+                            return Some(format!(
+                                "the input object `{}` does not have a field `{}`",
+                                input_object.name(),
+                                name
+                            ))
+                        }
+                    };
+                    if let Some(error) = value_error(schema, field.ty(), value) {
+                        return Some(error);
+                    }
+                }
+                input_object
+                    .fields()
+                    .iter()
+                    .find(|field| field.is_required() && !seen.contains(field.name()))
+                    .map(|field| {
+                        format!(
+                            "the input object `{}` requires the field `{}`",
+                            input_object.name(),
+                            field.name()
+                        )
+                    })
+            }
+            // Output types are reported with the definition.
+            _ => None,
+        },
+    }
+}
+
+/// Get why `value` is not a valid value of the scalar type `ty`, if it is
+/// not. Custom scalars are checked by the validator registered for them, if
+/// any.
+fn scalar_error(schema: &Schema, ty: &Type, value: &Value) -> Option<String> {
+    let valid = match (ty.name(), value) {
+        ("Int", Value::Int(text)) => {
+            if text.parse::<i32>().is_err() {
+                return Some(format!("`{}` is outside the range of `Int`", text));
+            }
+            true
+        }
+        ("Float", Value::Int(text) | Value::Float(text)) => {
+            if !text.parse::<f64>().is_ok_and(f64::is_finite) {
+                return Some(format!("`{}` is outside the range of `Float`", text));
+            }
+            true
+        }
+        ("String", Value::String(_)) => true,
+        ("Boolean", Value::Boolean(_)) => true,
+        ("ID", Value::String(_) | Value::Int(_)) => true,
+        ("Int" | "Float" | "String" | "Boolean" | "ID", _) => false,
+        (name, value) => {
+            let validator = schema.scalar_validators.0.get(name)?;
+            return validator(value).err();
+        }
+    };
+    if valid {
+        None
+    } else {
+        Some(mismatch(ty, value))
+    }
+}
+
+fn mismatch(ty: &Type, value: &Value) -> String {
+    format!("expected a value of type `{}`, found `{}`", ty, value)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        hir::Value, validate, validation::operations::test::messages, ExecutableDocument,
+        SchemaBuilder,
+    };
+
+    #[test]
+    fn it_reports_invalid_argument_values() {
+        assert_eq!(
+            messages(
+                r#"
+{
+  a: pet(id: 1.5) { name }
+  b: pet(id: "1", kind: null) { name }
+  c: pets(first: "ten") { name }
+  d: pets(first: 3000000000) { name }
+  e: users(ids: [1, "2", null]) { name }
+  f: users(ids: 1, filter: { limit: 1 }) { name }
+  g: users(filter: { name: "a", nmae: "b" }) { name }
+  h: users(filter: { name: RED }) { name }
+  i: pet(id: null) { name }
+  me { name @include(if: "yes") }
+}
+"#
+            ),
+            [
+                "invalid value for the argument `id`: expected a value of type `ID`, found `1.5`",
+                "invalid value for the argument `first`: expected a value of type `Int`, found `\"ten\"`",
+                "invalid value for the argument `first`: `3000000000` is outside the range of `Int`",
+                "invalid value for the argument `ids`: expected a value of type `ID!`, found `null`",
+                "invalid value for the argument `filter`: the input object `UserFilter` requires the field `name`",
+                "invalid value for the argument `filter`: the input object `UserFilter` does not have a field `nmae`",
+                "invalid value for the argument `filter`: expected a value of type `String`, found `RED`",
+                "invalid value for the argument `id`: expected a value of type `ID!`, found `null`",
+                "invalid value for the argument `if` of `@include`: expected a value of type `Boolean`, found `\"yes\"`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_default_values() {
+        assert_eq!(
+            messages(r#"query($first: Int = 1.5, $id: ID! = 1) { pets(first: $first) { name } pet(id: $id) { name } }"#),
+            ["invalid value for the default value of `$first`: expected a value of type `Int`, found `1.5`"]
+        );
+
+        let mut builder = SchemaBuilder::new();
+        builder.add_document(
+            "schema.graphql",
+            r#"
+type Query {
+  pets(first: Int = "ten", color: Color = BLUE, colors: [Color!] = RED): [String]
+}
+enum Color { RED }
+input Filter { limit: Float = 1 name: String = 1 }
+"#,
+        );
+        let messages: Vec<_> = builder
+            .build()
+            .validate()
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "invalid value for the default value of `Query.pets.first`: expected a value of type `Int`, found `\"ten\"`",
+                "invalid value for the default value of `Query.pets.color`: `BLUE` is not a value of the enum `Color`",
+                "invalid value for the default value of `Filter.name`: expected a value of type `String`, found `1`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_checks_custom_scalars_with_validators() {
+        let mut builder = SchemaBuilder::new();
+        builder.add_document(
+            "schema.graphql",
+            r#"
+scalar Limit
+scalar Json
+type Query { users(limit: Limit, data: Json): [String] }
+"#,
+        );
+        builder.add_scalar_validator("Limit", |value| match value {
+            Value::Int(_) => Ok(()),
+            _ => Err("a limit must be an integer".to_string()),
+        });
+        let schema = builder.build();
+        assert_eq!(schema.validate().len(), 0);
+
+        let document = ExecutableDocument::parse(
+            "query.graphql",
+            r#"{ a: users(limit: 10, data: "x") b: users(limit: "ten", data: { a: 1 }) }"#,
+        );
+        let messages: Vec<_> = validate(&schema, &document)
+            .iter()
+            .map(|diagnostic| diagnostic.message().to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            ["invalid value for the argument `limit`: a limit must be an integer"]
+        );
+    }
+}
@@ -0,0 +1,546 @@
+//! Rules for variable definitions and variable usages.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Validation.Variables>.
+
+use std::collections::HashSet;
+
+use apollo_parser::ast;
+use indexmap::IndexMap;
+
+use crate::{
+    hir::{DirectiveLocation, InputValueDefinition, Type, TypeDefinition, Value},
+    schema::builder::{ty, value},
+    validation::{
+        operation_type_of, type_condition_name, type_kind, values::invalid_value,
+        ExecutableValidator,
+    },
+    Diagnostic, DiagnosticKind, Span,
+};
+
+/// A variable defined by an operation.
+struct VariableDefinition {
+    name: String,
+    /// The type of the variable, if it is complete.
+    ty: Option<Type>,
+    /// Whether the variable has a default value other than `null`.
+    has_default: bool,
+    span: Span,
+}
+
+/// A use of a variable in an argument or input field.
+struct VariableUsage {
+    name: String,
+    variable: ast::Variable,
+    /// The type expected where the variable is used, and whether the
+    /// argument or input field has a default value, if it is known.
+    location: Option<(Type, bool)>,
+}
+
+impl<'a> ExecutableValidator<'a> {
+    /// Validate the variables of an operation: their definitions, and their
+    /// usages in the operation and in the fragments it spreads.
+    pub(crate) fn variables(&mut self, operation: &ast::OperationDefinition) {
+        let definitions = self.variable_definitions(operation);
+        let operation_name = match operation.name() {
+            Some(name) => format!("the operation `{}`", name.text()),
+            None => "the anonymous operation".to_string(),
+        };
+
+        let mut usages = Vec::new();
+        self.directive_usages(operation.directives(), &mut usages);
+        if let Some(selection_set) = operation.selection_set() {
+            let root = self
+                .schema
+                .root_operation(operation_type_of(operation))
+                .and_then(|root| self.schema.type_by_name(root.name()));
+            self.variable_usages(root, &selection_set, &mut HashSet::new(), &mut usages);
+        }
+
+        let mut used = HashSet::new();
+        for usage in &usages {
+            let span = self.span(&usage.variable);
+            used.insert(usage.name.as_str());
+            let definition = match definitions.get(&usage.name) {
+                Some(definition) => definition,
+                None => {
+                    self.diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticKind::UndefinedVariable,
+                            format!(
+                                "the variable `${}` is not defined by {}",
+                                usage.name, operation_name
+                            ),
+                        )
+                        .label(span, format!("`${}` used here", usage.name))
+                        .label(self.span(operation), "operation defined here"),
+                    );
+                    continue;
+                }
+            };
+            let (variable_type, location) = match (&definition.ty, &usage.location) {
+                (Some(variable_type), Some(location)) => (variable_type, location),
+                _ => continue,
+            };
+            let (location_type, location_has_default) = location;
+            if !is_usage_allowed(
+                variable_type,
+                definition.has_default,
+                location_type,
+                *location_has_default,
+            ) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::IncompatibleVariableUsage,
+                        format!(
+                            "the variable `${}` of type `{}` cannot be used where a `{}` is expected",
+                            usage.name, variable_type, location_type
+                        ),
+                    )
+                    .label(span, format!("`{}` expected here", location_type))
+                    .label(
+                        definition.span,
+                        format!("`${}` defined as `{}` here", usage.name, variable_type),
+                    ),
+                );
+            }
+        }
+
+        for definition in definitions
+            .values()
+            .filter(|definition| !used.contains(definition.name.as_str()))
+        {
+            self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::UnusedVariable,
+                    format!(
+                        "the variable `${}` is never used by {}",
+                        definition.name, operation_name
+                    ),
+                )
+                .label(
+                    definition.span,
+                    format!("`${}` defined here", definition.name),
+                ),
+            );
+        }
+    }
+
+    /// Collect the variable definitions of an operation by name, and check
+    /// that they are unique, have input types and valid directives.
+    fn variable_definitions(
+        &mut self,
+        operation: &ast::OperationDefinition,
+    ) -> IndexMap<String, VariableDefinition> {
+        let mut definitions: IndexMap<String, VariableDefinition> = IndexMap::new();
+        for definition in operation
+            .variable_definitions()
+            .into_iter()
+            .flat_map(|definitions| definitions.variable_definitions())
+        {
+            self.directives(
+                definition.directives(),
+                DirectiveLocation::VariableDefinition,
+            );
+            let name = match definition.variable().and_then(|variable| variable.name()) {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            let span = self.span(&definition);
+            if let Some(previous) = definitions.get(&name) {
+                self.diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticKind::DuplicateDefinition,
+                        format!("the variable `${}` is defined multiple times", name),
+                    )
+                    .label(span, format!("`${}` redefined here", name))
+                    .label(
+                        previous.span,
+                        format!("previous definition of `${}` here", name),
+                    ),
+                );
+                continue;
+            }
+            let variable_type = definition.ty().and_then(|node| {
+                let variable_type = ty(node.clone())?;
+                self.variable_type(&name, &variable_type, self.span(&node));
+                Some(variable_type)
+            });
+            let default = definition
+                .default_value()
+                .and_then(|default| default.value())
+                .and_then(value);
+            if let (Some(variable_type), Some(default)) = (&variable_type, &default) {
+                let what = format!("the default value of `${}`", name);
+                self.diagnostics.extend(invalid_value(
+                    self.schema,
+                    &what,
+                    variable_type,
+                    default,
+                    span,
+                ));
+            }
+            let has_default = default.is_some_and(|default| default != Value::Null);
+            definitions.insert(
+                name.clone(),
+                VariableDefinition {
+                    name,
+                    ty: variable_type,
+                    has_default,
+                    span,
+                },
+            );
+        }
+        definitions
+    }
+
+    /// Check that the type of a variable is defined and is an input type.
+    fn variable_type(&mut self, name: &str, variable_type: &Type, span: Span) {
+        match self.schema.type_by_name(variable_type.name()) {
+            Some(named) if named.is_input_type() => {}
+            Some(named) => self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::InvalidVariableType,
+                    format!(
+                        "the variable `${}` must have an input type, but `{}` is {}",
+                        name,
+                        named.name(),
+                        type_kind(named)
+                    ),
+                )
+                .label(span, format!("`${}` has type `{}`", name, variable_type)),
+            ),
+            None => self.diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticKind::UndefinedType,
+                    format!("the type `{}` is not defined", variable_type.name()),
+                )
+                .label(span, format!("`{}` used here", variable_type.name())),
+            ),
+        }
+    }
+
+    /// Collect the variable usages in a selection set, including the ones in
+    /// the fragments it spreads. `visited` holds the fragments already
+    /// searched, so that each is searched once.
+    fn variable_usages(
+        &self,
+        parent: Option<&'a TypeDefinition>,
+        selection_set: &ast::SelectionSet,
+        visited: &mut HashSet<String>,
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        let schema = self.schema;
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    let definition = field
+                        .name()
+                        .and_then(|name| schema.field(parent?.name(), name.text().as_ref()));
+                    let arguments = definition.map_or(&[][..], |field| field.arguments());
+                    self.argument_usages(field.arguments(), arguments, usages);
+                    self.directive_usages(field.directives(), usages);
+                    if let Some(selection_set) = field.selection_set() {
+                        let ty = definition.and_then(|field| schema.resolve_type(field.ty()));
+                        self.variable_usages(ty, &selection_set, visited, usages);
+                    }
+                }
+                ast::Selection::FragmentSpread(spread) => {
+                    self.directive_usages(spread.directives(), usages);
+                    let fragment = spread
+                        .fragment_name()
+                        .and_then(|name| name.name())
+                        .map(|name| name.text().to_string())
+                        .filter(|name| visited.insert(name.clone()))
+                        .and_then(|name| self.fragment(&name));
+                    if let Some(fragment) = fragment {
+                        self.directive_usages(fragment.directives(), usages);
+                        let ty = type_condition_name(fragment.type_condition())
+                            .and_then(|name| schema.type_by_name(&name));
+                        if let Some(selection_set) = fragment.selection_set() {
+                            self.variable_usages(ty, &selection_set, visited, usages);
+                        }
+                    }
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    self.directive_usages(inline.directives(), usages);
+                    let ty = match inline.type_condition() {
+                        Some(condition) => type_condition_name(Some(condition))
+                            .and_then(|name| schema.type_by_name(&name)),
+                        None => parent,
+                    };
+                    if let Some(selection_set) = inline.selection_set() {
+                        self.variable_usages(ty, &selection_set, visited, usages);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collect the variable usages in the arguments of directives.
+    fn directive_usages(
+        &self,
+        directives: Option<ast::Directives>,
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        for directive in directives
+            .into_iter()
+            .flat_map(|directives| directives.directives())
+        {
+            let definition = directive
+                .name()
+                .and_then(|name| self.schema.directive_definition(name.text().as_ref()));
+            let arguments = definition.map_or(&[][..], |directive| directive.arguments());
+            self.argument_usages(directive.arguments(), arguments, usages);
+        }
+    }
+
+    /// Collect the variable usages in arguments, given the definitions of
+    /// the arguments, which are empty if they are unknown.
+    fn argument_usages(
+        &self,
+        arguments: Option<ast::Arguments>,
+        definitions: &[InputValueDefinition],
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        for argument in arguments
+            .into_iter()
+            .flat_map(|arguments| arguments.arguments())
+        {
+            let location = argument.name().and_then(|name| {
+                let definition = definitions
+                    .iter()
+                    .find(|definition| definition.name() == name.text().as_ref())?;
+                Some((
+                    definition.ty().clone(),
+                    definition.default_value().is_some(),
+                ))
+            });
+            if let Some(value) = argument.value() {
+                self.value_usages(value, location, usages);
+            }
+        }
+    }
+
+    /// Collect the variable usages in a value, including the ones nested in
+    /// list and input object values.
+    fn value_usages(
+        &self,
+        value: ast::Value,
+        location: Option<(Type, bool)>,
+        usages: &mut Vec<VariableUsage>,
+    ) {
+        match value {
+            ast::Value::Variable(variable) => {
+                if let Some(name) = variable.name() {
+                    usages.push(VariableUsage {
+                        name: name.text().to_string(),
+                        variable,
+                        location,
+                    });
+                }
+            }
+            ast::Value::ListValue(list) => {
+                let item = location.and_then(|(ty, _)| Some((ty.item_type()?.clone(), false)));
+                for value in list.values() {
+                    self.value_usages(value, item.clone(), usages);
+                }
+            }
+            ast::Value::ObjectValue(object) => {
+                // An input object value can also be given for a list of input
+                // objects, so the type of the fields is looked up by name.
+                let input_object =
+                    location.and_then(|(ty, _)| match self.schema.type_by_name(ty.name())? {
+                        TypeDefinition::InputObject(input_object) => Some(input_object),
+                        _ => None,
+                    });
+                for field in object.object_fields() {
+                    let location = field.name().and_then(|name| {
+                        let definition = input_object?.field(name.text().as_ref())?;
+                        Some((
+                            definition.ty().clone(),
+                            definition.default_value().is_some(),
+                        ))
+                    });
+                    if let Some(value) = field.value() {
+                        self.value_usages(value, location, usages);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check whether a variable of type `variable` can be used where a value of
+/// type `location` is expected. A nullable variable can be used where a
+/// non-null value is expected if the variable or the location has a default
+/// value.
+fn is_usage_allowed(
+    variable: &Type,
+    variable_has_default: bool,
+    location: &Type,
+    location_has_default: bool,
+) -> bool {
+    match location {
+        Type::NonNull(nullable) if !variable.is_non_null() => {
+            (variable_has_default || location_has_default)
+                && are_types_compatible(variable, nullable)
+        }
+        _ => are_types_compatible(variable, location),
+    }
+}
+
+/// Check whether values of type `variable` are always valid values of type
+/// `location`.
+fn are_types_compatible(variable: &Type, location: &Type) -> bool {
+    match (variable, location) {
+        (Type::NonNull(variable), Type::NonNull(location)) => {
+            are_types_compatible(variable, location)
+        }
+        (_, Type::NonNull(_)) => false,
+        (Type::NonNull(variable), location) => are_types_compatible(variable, location),
+        (Type::List(variable), Type::List(location)) => are_types_compatible(variable, location),
+        (Type::Named(variable), Type::Named(location)) => variable == location,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        validate,
+        validation::operations::test::{messages, SCHEMA},
+        DiagnosticKind, ExecutableDocument, Schema,
+    };
+
+    #[test]
+    fn it_accepts_valid_variables() {
+        assert_eq!(
+            messages(
+                r#"
+query A($id: ID!, $first: Int, $name: String!, $withName: Boolean!) {
+  pet(id: $id) { ...Name }
+  pets(first: $first) { name }
+  users(filter: { name: $name }, ids: [$id]) { name @include(if: $withName) }
+}
+query B($id: ID = 1, $kind: String, $limit: Int, $ids: [ID!]!) {
+  pet(id: $id, kind: $kind) { name }
+  users(filter: { name: "a", limit: $limit }, ids: $ids) { name }
+}
+fragment Name on Pet { ... on Pet @skip(if: $withName) { name } }
+"#
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn it_reports_undefined_and_unused_variables() {
+        assert_eq!(
+            messages(
+                r#"
+query A($id: ID!, $unused: Int, $unused: Int) {
+  pet(id: $id) { ...Names }
+  pets(first: $first) { name }
+}
+query B { pet(id: "1") { ...Names } }
+fragment Names on Pet { name @include(if: $withName) }
+"#
+            ),
+            [
+                "the variable `$unused` is defined multiple times",
+                "the variable `$withName` is not defined by the operation `A`",
+                "the variable `$first` is not defined by the operation `A`",
+                "the variable `$unused` is never used by the operation `A`",
+                "the variable `$withName` is not defined by the operation `B`",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_variables_of_output_types() {
+        assert_eq!(
+            messages("query($user: User, $color: Colour) { me { name } }"),
+            [
+                "the variable `$user` must have an input type, but `User` is an object type",
+                "the type `Colour` is not defined",
+                "the variable `$user` is never used by the anonymous operation",
+                "the variable `$color` is never used by the anonymous operation",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_incompatible_variable_usages() {
+        assert_eq!(
+            messages(
+                r#"
+query($id: ID, $ids: [ID], $name: String, $first: String, $limit: Int!) {
+  pet(id: $id) { name }
+  users(filter: { name: $name, limit: $limit }, ids: $ids) { name }
+  pets(first: $first) { name }
+  other: users(ids: [$id, $limit]) { name }
+}
+"#
+            ),
+            [
+                "the variable `$id` of type `ID` cannot be used where a `ID!` is expected",
+                "the variable `$name` of type `String` cannot be used where a `String!` is expected",
+                "the variable `$ids` of type `[ID]` cannot be used where a `[ID!]` is expected",
+                "the variable `$first` of type `String` cannot be used where a `Int` is expected",
+                "the variable `$id` of type `ID` cannot be used where a `ID!` is expected",
+                "the variable `$limit` of type `Int!` cannot be used where a `ID!` is expected",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_points_at_the_definition_and_the_usage() {
+        let schema = Schema::parse(SCHEMA);
+        let input = "query($id: ID) { pet(id: $id) { name } }";
+        let document = ExecutableDocument::parse("query.graphql", input);
+        let diagnostics = validate(&schema, &document);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].kind(),
+            &DiagnosticKind::IncompatibleVariableUsage
+        );
+        let labels: Vec<_> = diagnostics[0]
+            .labels()
+            .map(|label| (&input[label.span().range()], label.message()))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                ("$id", "`ID!` expected here"),
+                ("$id: ID", "`$id` defined as `ID` here"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_collects_variables_with_missing_names() {
+        let schema = Schema::parse(SCHEMA);
+        let inputs = [
+            "query($: Int) { a }",
+            "{ a(x: $) }",
+            "query($: ID!) { pet(id: $) { name } }",
+            "query($id: ID!) { pet(id: $id) @include(if: $) { name } }",
+            "query { users(filter: { name: $ }) { name } }",
+            "query($id: ID!) { users(ids: [$id, $]) { name } }",
+        ];
+        for input in inputs {
+            let document = ExecutableDocument::parse("query.graphql", input);
+            let diagnostics = validate(&schema, &document);
+            assert!(
+                diagnostics
+                    .iter()
+                    .any(|d| d.kind() == &DiagnosticKind::SyntaxError),
+                "{}",
+                input
+            );
+        }
+    }
+}
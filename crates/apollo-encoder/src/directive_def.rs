@@ -42,6 +42,9 @@ pub struct Directive {
     // Locations returns a List of __DirectiveLocation representing the valid
     // locations this directive may be placed.
     locations: Vec<String>,
+    // Repeatable returns true if this directive may be applied multiple
+    // times at the same location.
+    is_repeatable: bool,
 }
 
 impl Directive {
@@ -52,6 +55,7 @@ impl Directive {
             description: StringValue::Top { source: None },
             args: Vec::new(),
             locations: Vec::new(),
+            is_repeatable: false,
         }
     }
 
@@ -71,6 +75,11 @@ impl Directive {
     pub fn arg(&mut self, arg: InputValue) {
         self.args.push(arg);
     }
+
+    /// Make the Directive repeatable.
+    pub fn repeatable(&mut self) {
+        self.is_repeatable = true;
+    }
 }
 
 impl fmt::Display for Directive {
@@ -88,6 +97,10 @@ impl fmt::Display for Directive {
             write!(f, ")")?;
         }
 
+        if self.is_repeatable {
+            write!(f, " repeatable")?;
+        }
+
         for (i, location) in self.locations.iter().enumerate() {
             match i {
                 0 => write!(f, " on {}", location)?,
@@ -158,6 +171,25 @@ directive @infer on OBJECT | FIELD_DEFINITION | INPUT_FIELD_DEFINITION
             directive.to_string(),
             r#""Infer field types from field values."
 directive @infer(cat: [SpaceProgram]) on OBJECT
+"#
+        );
+    }
+
+    #[test]
+    fn it_encodes_repeatable_directives() {
+        let mut directive = Directive::new("tag".to_string());
+        directive.location("FIELD_DEFINITION".to_string());
+        directive.repeatable();
+
+        let ty = Type_::NamedType {
+            name: "String".to_string(),
+        };
+        let ty = Type_::NonNull { ty: Box::new(ty) };
+        directive.arg(InputValue::new("name".to_string(), ty));
+
+        assert_eq!(
+            directive.to_string(),
+            r#"directive @tag(name: String!) repeatable on FIELD_DEFINITION
 "#
         );
     }
@@ -67,7 +67,7 @@ impl fmt::Display for EnumValue {
 
         if self.is_deprecated {
             write!(f, " @deprecated")?;
-            if let StringValue::Reason { source: _ } = &self.deprecation_reason {
+            if let StringValue::Reason { source: Some(_) } = &self.deprecation_reason {
                 write!(f, "(reason:")?;
                 write!(f, "{}", self.deprecation_reason)?;
                 write!(f, ")")?
@@ -134,4 +134,12 @@ mod tests {
   )"#
         );
     }
+
+    #[test]
+    fn it_encodes_an_enum_value_with_deprecated_without_reason() {
+        let mut enum_ty = EnumValue::new("CARDBOARD_BOX".to_string());
+        enum_ty.deprecated(None);
+
+        assert_eq!(enum_ty.to_string(), "  CARDBOARD_BOX @deprecated");
+    }
 }
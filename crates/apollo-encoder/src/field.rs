@@ -99,7 +99,7 @@ impl fmt::Display for Field {
         if self.is_deprecated {
             write!(f, " @deprecated")?;
 
-            if let StringValue::Reason { source: _ } = &self.deprecation_reason {
+            if let StringValue::Reason { source: Some(_) } = &self.deprecation_reason {
                 write!(f, "(reason:")?;
                 write!(f, "{}", self.deprecation_reason)?;
                 write!(f, ")")?
@@ -194,4 +194,15 @@ mod tests {
   spaceCat(cat: [SpaceProgram] @deprecated(reason: "Cats are no longer sent to space.")): [SpaceProgram!]!"#
         );
     }
+
+    #[test]
+    fn it_encodes_fields_with_deprecation_without_reason() {
+        let ty = Type_::NamedType {
+            name: "SpaceProgram".to_string(),
+        };
+        let mut field = Field::new("cat".to_string(), ty);
+        field.deprecated(None);
+
+        assert_eq!(field.to_string(), "  cat: SpaceProgram @deprecated");
+    }
 }
@@ -30,6 +30,10 @@ pub struct InputField {
     type_: Type_,
     // Default value for this input field.
     default_value: Option<String>,
+    // Deprecated returns true if this field should no longer be used, otherwise false.
+    is_deprecated: bool,
+    // Deprecation reason optionally provides a reason why this field is deprecated.
+    deprecation_reason: StringValue,
 }
 
 impl InputField {
@@ -40,6 +44,8 @@ impl InputField {
             name,
             type_,
             default_value: None,
+            is_deprecated: false,
+            deprecation_reason: StringValue::Reason { source: None },
         }
     }
 
@@ -54,6 +60,12 @@ impl InputField {
     pub fn default(&mut self, default: Option<String>) {
         self.default_value = default;
     }
+
+    /// Set the InputField's deprecation properties.
+    pub fn deprecated(&mut self, reason: Option<String>) {
+        self.is_deprecated = true;
+        self.deprecation_reason = StringValue::Reason { source: reason };
+    }
 }
 
 impl fmt::Display for InputField {
@@ -65,6 +77,16 @@ impl fmt::Display for InputField {
             write!(f, " = {}", default)?;
         }
 
+        if self.is_deprecated {
+            write!(f, " @deprecated")?;
+
+            if let StringValue::Reason { source: Some(_) } = &self.deprecation_reason {
+                write!(f, "(reason:")?;
+                write!(f, "{}", self.deprecation_reason)?;
+                write!(f, ")")?
+            }
+        }
+
         Ok(())
     }
 }
@@ -85,4 +107,22 @@ mod tests {
 
         assert_eq!(field.to_string(), r#"  cat: CatBreed = "Norwegian Forest""#);
     }
+
+    #[test]
+    fn it_encodes_fields_with_deprecation() {
+        let ty_1 = Type_::NamedType {
+            name: "CatBreed".to_string(),
+        };
+
+        let mut field = InputField::new("cat".to_string(), ty_1);
+        field.deprecated(Some("Cats are all the same breed.".to_string()));
+
+        assert_eq!(
+            field.to_string(),
+            r#"  cat: CatBreed @deprecated(reason: "Cats are all the same breed.")"#
+        );
+
+        field.deprecated(None);
+        assert_eq!(field.to_string(), r#"  cat: CatBreed @deprecated"#);
+    }
 }
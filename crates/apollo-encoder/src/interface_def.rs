@@ -119,7 +119,7 @@ impl fmt::Display for InterfaceDef {
         for (i, interface) in self.interfaces.iter().enumerate() {
             match i {
                 0 => write!(f, " implements {}", interface)?,
-                _ => write!(f, "& {}", interface)?,
+                _ => write!(f, " & {}", interface)?,
             }
         }
         write!(f, " {{")?;
@@ -195,4 +195,26 @@ mod tests {
             "# }
         );
     }
+
+    #[test]
+    fn it_encodes_interfaces_implementing_interfaces() {
+        let ty = Type_::NonNull {
+            ty: Box::new(Type_::NamedType {
+                name: "ID".to_string(),
+            }),
+        };
+        let mut interface = InterfaceDef::new("Pet".to_string());
+        interface.interface("Node".to_string());
+        interface.interface("Named".to_string());
+        interface.field(Field::new("id".to_string(), ty));
+
+        assert_eq!(
+            interface.to_string(),
+            indoc! { r#"
+            interface Pet implements Node & Named {
+              id: ID!
+            }
+            "# }
+        );
+    }
 }
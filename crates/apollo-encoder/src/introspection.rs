@@ -0,0 +1,640 @@
+use std::fmt;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    Directive, EnumDef, EnumValue, Field, InputField, InputObjectDef, InputValue, InterfaceDef,
+    ObjectDef, ScalarDef, Schema, SchemaDef, Type_, UnionDef,
+};
+
+/// The deprecation reason that is implied by `@deprecated` without a reason.
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// The scalars every schema has, which are not encoded.
+const SPECIFIED_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+
+/// The directives every schema has, which are not encoded.
+const SPECIFIED_DIRECTIVES: [&str; 4] = ["include", "skip", "deprecated", "specifiedBy"];
+
+/// An error that occurred while reading an introspection result.
+#[derive(Debug)]
+pub enum IntrospectionError {
+    /// The input is not valid JSON, or a part of it does not have the shape
+    /// of an introspection result.
+    Json(serde_json::Error),
+    /// The input has neither a `data.__schema` nor a `__schema` object.
+    MissingSchema,
+    /// A `LIST` or `NON_NULL` type reference has no `ofType`, or another type
+    /// reference has no `name`.
+    InvalidTypeRef,
+}
+
+impl fmt::Display for IntrospectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntrospectionError::Json(error) => {
+                write!(f, "invalid introspection result: {}", error)
+            }
+            IntrospectionError::MissingSchema => {
+                write!(f, "the introspection result does not contain `__schema`")
+            }
+            IntrospectionError::InvalidTypeRef => write!(
+                f,
+                "a type reference is missing its `name`, or its `ofType` if it is a list or non-null type"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IntrospectionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            IntrospectionError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for IntrospectionError {
+    fn from(error: serde_json::Error) -> Self {
+        IntrospectionError::Json(error)
+    }
+}
+
+impl Schema {
+    /// Create a Schema Encoder from the JSON result of an introspection
+    /// query, like the `IntrospectionQuery` of GraphQL tools.
+    ///
+    /// The result can be a whole response, `{"data": {"__schema": ...}}`, or
+    /// just its data, `{"__schema": ...}`. Descriptions, deprecations,
+    /// default values, `specifiedByURL` and repeatable directives are
+    /// preserved. The built-in scalars and directives, and the introspection
+    /// types, are left out, and so is the schema definition if the root
+    /// operation types have their default names.
+    ///
+    /// ### Example
+    /// ```rust
+    /// use apollo_encoder::Schema;
+    /// use indoc::indoc;
+    ///
+    /// let json = r#"{ "data": { "__schema": {
+    ///   "queryType": { "name": "Query" },
+    ///   "types": [{
+    ///     "kind": "OBJECT",
+    ///     "name": "Query",
+    ///     "description": "The root query.",
+    ///     "fields": [{
+    ///       "name": "cats",
+    ///       "args": [{
+    ///         "name": "first",
+    ///         "type": { "kind": "SCALAR", "name": "Int" },
+    ///         "defaultValue": "10"
+    ///       }],
+    ///       "type": { "kind": "LIST", "ofType": { "kind": "SCALAR", "name": "String" } },
+    ///       "isDeprecated": true,
+    ///       "deprecationReason": "Use `dogs`."
+    ///     }],
+    ///     "interfaces": []
+    ///   }],
+    ///   "directives": []
+    /// } } }"#;
+    ///
+    /// let schema = Schema::from_introspection(json).unwrap();
+    /// assert_eq!(
+    ///     schema.finish(),
+    ///     indoc! { r#"
+    ///         "The root query."
+    ///         type Query {
+    ///           cats(first: Int = 10): [String] @deprecated(reason: "Use `dogs`.")
+    ///         }
+    ///     "# }
+    /// );
+    /// ```
+    pub fn from_introspection(json: &str) -> Result<Self, IntrospectionError> {
+        let mut value: Value = serde_json::from_str(json)?;
+        let schema = match value.pointer_mut("/data/__schema") {
+            Some(schema) => schema.take(),
+            None => match value.get_mut("__schema") {
+                Some(schema) => schema.take(),
+                None => return Err(IntrospectionError::MissingSchema),
+            },
+        };
+        let introspection: IntrospectionSchema = serde_json::from_value(schema)?;
+
+        let mut schema = Schema::new();
+        if let Some(schema_def) = introspection.schema_def() {
+            schema.schema(schema_def);
+        }
+        for directive in &introspection.directives {
+            if !SPECIFIED_DIRECTIVES.contains(&directive.name.as_str()) {
+                schema.directive(directive.encode()?);
+            }
+        }
+        for ty in &introspection.types {
+            if ty.name.starts_with("__")
+                || (ty.kind == Kind::Scalar && SPECIFIED_SCALARS.contains(&ty.name.as_str()))
+            {
+                continue;
+            }
+            ty.encode(&mut schema)?;
+        }
+        Ok(schema)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectionSchema {
+    description: Option<String>,
+    query_type: Option<NamedRef>,
+    mutation_type: Option<NamedRef>,
+    subscription_type: Option<NamedRef>,
+    types: Vec<FullType>,
+    #[serde(default)]
+    directives: Vec<DirectiveDef>,
+}
+
+impl IntrospectionSchema {
+    /// Get the schema definition, unless it can be left out because the
+    /// root operation types have their default names.
+    fn schema_def(&self) -> Option<SchemaDef> {
+        let is_named = |root: &Option<NamedRef>, default: &str| {
+            root.as_ref().is_none_or(|root| root.name == default)
+        };
+        if self.description.is_none()
+            && is_named(&self.query_type, "Query")
+            && is_named(&self.mutation_type, "Mutation")
+            && is_named(&self.subscription_type, "Subscription")
+        {
+            return None;
+        }
+        let mut schema_def = SchemaDef::new();
+        schema_def.description(self.description.clone());
+        if let Some(query) = &self.query_type {
+            schema_def.query(query.name.clone());
+        }
+        if let Some(mutation) = &self.mutation_type {
+            schema_def.mutation(mutation.name.clone());
+        }
+        if let Some(subscription) = &self.subscription_type {
+            schema_def.subscription(subscription.name.clone());
+        }
+        Some(schema_def)
+    }
+}
+
+#[derive(Deserialize)]
+struct NamedRef {
+    name: String,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Kind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FullType {
+    kind: Kind,
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "specifiedByURL")]
+    specified_by_url: Option<String>,
+    fields: Option<Vec<FieldDef>>,
+    input_fields: Option<Vec<InputValueDef>>,
+    interfaces: Option<Vec<TypeRef>>,
+    enum_values: Option<Vec<EnumValueDef>>,
+    possible_types: Option<Vec<TypeRef>>,
+}
+
+impl FullType {
+    fn encode(&self, schema: &mut Schema) -> Result<(), IntrospectionError> {
+        let description = self.description.clone();
+        match self.kind {
+            Kind::Scalar => {
+                let mut scalar = ScalarDef::new(self.name.clone());
+                scalar.description(description);
+                if let Some(url) = &self.specified_by_url {
+                    scalar.specified_by(url.clone());
+                }
+                schema.scalar(scalar);
+            }
+            Kind::Object => {
+                let mut object = ObjectDef::new(self.name.clone());
+                object.description(description);
+                for interface in self.interfaces.iter().flatten() {
+                    object.interface(interface.name()?);
+                }
+                for field in self.fields.iter().flatten() {
+                    object.field(field.encode()?);
+                }
+                schema.object(object);
+            }
+            Kind::Interface => {
+                let mut interface = InterfaceDef::new(self.name.clone());
+                interface.description(description);
+                for implemented in self.interfaces.iter().flatten() {
+                    interface.interface(implemented.name()?);
+                }
+                for field in self.fields.iter().flatten() {
+                    interface.field(field.encode()?);
+                }
+                schema.interface(interface);
+            }
+            Kind::Union => {
+                let mut union_ = UnionDef::new(self.name.clone());
+                union_.description(description);
+                for member in self.possible_types.iter().flatten() {
+                    union_.member(member.name()?);
+                }
+                schema.union(union_);
+            }
+            Kind::Enum => {
+                let mut enum_ = EnumDef::new(self.name.clone());
+                enum_.description(description);
+                for value in self.enum_values.iter().flatten() {
+                    let mut enum_value = EnumValue::new(value.name.clone());
+                    enum_value.description(value.description.clone());
+                    if let Some(reason) =
+                        deprecation(value.is_deprecated, &value.deprecation_reason)
+                    {
+                        enum_value.deprecated(reason);
+                    }
+                    enum_.value(enum_value);
+                }
+                schema.enum_(enum_);
+            }
+            Kind::InputObject => {
+                let mut input = InputObjectDef::new(self.name.clone());
+                input.description(description);
+                for field in self.input_fields.iter().flatten() {
+                    let mut input_field = InputField::new(field.name.clone(), field.ty.encode()?);
+                    input_field.description(field.description.clone());
+                    input_field.default(field.default_value.clone());
+                    if let Some(reason) =
+                        deprecation(field.is_deprecated, &field.deprecation_reason)
+                    {
+                        input_field.deprecated(reason);
+                    }
+                    input.field(input_field);
+                }
+                schema.input(input);
+            }
+            // Wrapping types are never named types.
+            Kind::List | Kind::NonNull => return Err(IntrospectionError::InvalidTypeRef),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FieldDef {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    args: Vec<InputValueDef>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+impl FieldDef {
+    fn encode(&self) -> Result<Field, IntrospectionError> {
+        let mut field = Field::new(self.name.clone(), self.ty.encode()?);
+        field.description(self.description.clone());
+        for arg in &self.args {
+            field.arg(arg.encode()?);
+        }
+        if let Some(reason) = deprecation(self.is_deprecated, &self.deprecation_reason) {
+            field.deprecated(reason);
+        }
+        Ok(field)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InputValueDef {
+    name: String,
+    description: Option<String>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    default_value: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+impl InputValueDef {
+    fn encode(&self) -> Result<InputValue, IntrospectionError> {
+        let mut value = InputValue::new(self.name.clone(), self.ty.encode()?);
+        value.description(self.description.clone());
+        value.default(self.default_value.clone());
+        if let Some(reason) = deprecation(self.is_deprecated, &self.deprecation_reason) {
+            value.deprecated(reason);
+        }
+        Ok(value)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnumValueDef {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DirectiveDef {
+    name: String,
+    description: Option<String>,
+    #[serde(default)]
+    is_repeatable: bool,
+    locations: Vec<String>,
+    #[serde(default)]
+    args: Vec<InputValueDef>,
+}
+
+impl DirectiveDef {
+    fn encode(&self) -> Result<Directive, IntrospectionError> {
+        let mut directive = Directive::new(self.name.clone());
+        directive.description(self.description.clone());
+        for arg in &self.args {
+            directive.arg(arg.encode()?);
+        }
+        if self.is_repeatable {
+            directive.repeatable();
+        }
+        for location in &self.locations {
+            directive.location(location.clone());
+        }
+        Ok(directive)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: Kind,
+    name: Option<String>,
+    of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    fn name(&self) -> Result<String, IntrospectionError> {
+        self.name.clone().ok_or(IntrospectionError::InvalidTypeRef)
+    }
+
+    fn encode(&self) -> Result<Type_, IntrospectionError> {
+        let of_type = || match &self.of_type {
+            Some(of_type) => Ok(Box::new(of_type.encode()?)),
+            None => Err(IntrospectionError::InvalidTypeRef),
+        };
+        Ok(match self.kind {
+            Kind::NonNull => Type_::NonNull { ty: of_type()? },
+            Kind::List => Type_::List { ty: of_type()? },
+            _ => Type_::NamedType { name: self.name()? },
+        })
+    }
+}
+
+/// Get the reason to encode with `@deprecated` if an element is deprecated.
+/// The default reason is left out, like graphql-js does.
+fn deprecation(is_deprecated: bool, reason: &Option<String>) -> Option<Option<String>> {
+    if !is_deprecated {
+        return None;
+    }
+    Some(
+        reason
+            .clone()
+            .filter(|reason| reason != DEFAULT_DEPRECATION_REASON),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn named(kind: &str, name: &str) -> Value {
+        serde_json::json!({ "kind": kind, "name": name, "ofType": null })
+    }
+
+    fn wrapped(kind: &str, of_type: Value) -> Value {
+        serde_json::json!({ "kind": kind, "name": null, "ofType": of_type })
+    }
+
+    #[test]
+    fn it_encodes_introspection_results() {
+        let string = named("SCALAR", "String");
+        let json = serde_json::json!({ "data": { "__schema": {
+            "description": null,
+            "queryType": { "name": "Query" },
+            "mutationType": null,
+            "subscriptionType": null,
+            "types": [
+                {
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "description": null,
+                    "fields": [{
+                        "name": "pets",
+                        "description": "All the pets.",
+                        "args": [
+                            {
+                                "name": "filter",
+                                "description": null,
+                                "type": named("INPUT_OBJECT", "PetFilter"),
+                                "defaultValue": "{first: 10}",
+                                "isDeprecated": false,
+                                "deprecationReason": null
+                            },
+                            {
+                                "name": "sort",
+                                "description": null,
+                                "type": string,
+                                "defaultValue": null,
+                                "isDeprecated": true,
+                                "deprecationReason": "No longer supported"
+                            }
+                        ],
+                        "type": wrapped("NON_NULL", wrapped("LIST", wrapped("NON_NULL", named("INTERFACE", "Pet")))),
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    }],
+                    "inputFields": null,
+                    "interfaces": [],
+                    "enumValues": null,
+                    "possibleTypes": null
+                },
+                {
+                    "kind": "INTERFACE",
+                    "name": "Pet",
+                    "description": null,
+                    "fields": [{
+                        "name": "name",
+                        "args": [],
+                        "type": string,
+                        "isDeprecated": true,
+                        "deprecationReason": "Pets are \"anonymous\"."
+                    }],
+                    "interfaces": [named("INTERFACE", "Node"), named("INTERFACE", "Named")],
+                    "possibleTypes": [named("OBJECT", "Cat")]
+                },
+                {
+                    "kind": "UNION",
+                    "name": "Animal",
+                    "description": "Any animal.",
+                    "possibleTypes": [named("OBJECT", "Cat"), named("OBJECT", "Dog")]
+                },
+                {
+                    "kind": "ENUM",
+                    "name": "Color",
+                    "description": null,
+                    "enumValues": [
+                        { "name": "RED", "description": "Like a fox.", "isDeprecated": false, "deprecationReason": null },
+                        { "name": "BLUE", "description": null, "isDeprecated": true, "deprecationReason": "No longer supported" }
+                    ]
+                },
+                {
+                    "kind": "INPUT_OBJECT",
+                    "name": "PetFilter",
+                    "description": null,
+                    "inputFields": [
+                        {
+                            "name": "first",
+                            "description": null,
+                            "type": named("SCALAR", "Int"),
+                            "defaultValue": "10",
+                            "isDeprecated": false,
+                            "deprecationReason": null
+                        },
+                        {
+                            "name": "color",
+                            "description": null,
+                            "type": named("ENUM", "Color"),
+                            "defaultValue": "RED",
+                            "isDeprecated": true,
+                            "deprecationReason": "Use `colors`."
+                        }
+                    ]
+                },
+                { "kind": "SCALAR", "name": "DateTime", "description": null, "specifiedByURL": "https://tools.ietf.org/html/rfc3339" },
+                { "kind": "SCALAR", "name": "String", "description": "The `String` scalar type.", "specifiedByURL": null },
+                { "kind": "OBJECT", "name": "__Schema", "fields": [], "interfaces": [] }
+            ],
+            "directives": [
+                {
+                    "name": "tag",
+                    "description": "Tags a field.",
+                    "isRepeatable": true,
+                    "locations": ["FIELD_DEFINITION", "OBJECT"],
+                    "args": [{
+                        "name": "name",
+                        "description": null,
+                        "type": wrapped("NON_NULL", named("SCALAR", "String")),
+                        "defaultValue": null,
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    }]
+                },
+                {
+                    "name": "skip",
+                    "description": null,
+                    "isRepeatable": false,
+                    "locations": ["FIELD"],
+                    "args": []
+                }
+            ]
+        } } });
+
+        let schema = Schema::from_introspection(&json.to_string()).unwrap();
+        assert_eq!(
+            schema.finish(),
+            indoc! { r#"
+                "Tags a field."
+                directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+                type Query {
+                  "All the pets."
+                  pets(filter: PetFilter = {first: 10}, sort: String @deprecated): [Pet!]!
+                }
+                interface Pet implements Node & Named {
+                  name: String @deprecated(reason:
+                  """
+                  Pets are "anonymous".
+                  """
+                  )
+                }
+                "Any animal."
+                union Animal = Cat | Dog
+                enum Color {
+                  "Like a fox."
+                  RED
+                  BLUE @deprecated
+                }
+                input PetFilter {
+                  first: Int = 10
+                  color: Color = RED @deprecated(reason: "Use `colors`.")
+                }
+                scalar DateTime @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+            "# }
+        );
+    }
+
+    #[test]
+    fn it_encodes_schema_definitions_for_custom_root_types() {
+        let json = r#"{ "__schema": {
+            "queryType": { "name": "Root" },
+            "mutationType": { "name": "Mutation" },
+            "types": []
+        } }"#;
+
+        assert_eq!(
+            Schema::from_introspection(json).unwrap().finish(),
+            indoc! { r#"
+                schema {
+                  query: Root
+                  mutation: Mutation
+                }
+            "# }
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_introspection_results() {
+        let error = Schema::from_introspection(r#"{ "data": {} }"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the introspection result does not contain `__schema`"
+        );
+
+        let json = r#"{ "__schema": { "types": [{
+            "kind": "OBJECT",
+            "name": "Query",
+            "fields": [{ "name": "a", "type": { "kind": "LIST", "ofType": null } }]
+        }] } }"#;
+        let error = Schema::from_introspection(json).unwrap_err();
+        assert!(matches!(error, IntrospectionError::InvalidTypeRef));
+
+        let error = Schema::from_introspection(r#"{ "__schema": { "types": 1 } }"#).unwrap_err();
+        assert!(matches!(error, IntrospectionError::Json(_)));
+    }
+}
@@ -91,6 +91,11 @@
 //! );
 //! ```
 //!
+//! ## Encoding introspection results
+//! A schema can also be encoded from the JSON result of an introspection
+//! query with [`Schema::from_introspection`], to get the SDL of a schema that
+//! is only available through introspection.
+//!
 //! ## License
 //! Licensed under either of
 //!
@@ -116,6 +121,7 @@ mod input_field;
 mod input_object_def;
 mod input_value;
 mod interface_def;
+mod introspection;
 mod object_def;
 mod scalar_def;
 mod schema;
@@ -132,6 +138,7 @@ pub use input_field::InputField;
 pub use input_object_def::InputObjectDef;
 pub use input_value::InputValue;
 pub use interface_def::InterfaceDef;
+pub use introspection::IntrospectionError;
 pub use object_def::ObjectDef;
 pub use scalar_def::ScalarDef;
 pub use schema::Schema;
@@ -30,6 +30,8 @@ pub struct ScalarDef {
     name: String,
     // Description may return a String or null.
     description: StringValue,
+    // The URL of the specification of this scalar, if any.
+    specified_by: Option<String>,
 }
 
 impl ScalarDef {
@@ -38,6 +40,7 @@ impl ScalarDef {
         Self {
             name,
             description: StringValue::Top { source: None },
+            specified_by: None,
         }
     }
 
@@ -47,12 +50,24 @@ impl ScalarDef {
             source: description,
         };
     }
+
+    /// Set the URL of the ScalarDef's specification, encoded with the
+    /// `@specifiedBy` directive.
+    pub fn specified_by(&mut self, url: String) {
+        self.specified_by = Some(url);
+    }
 }
 
 impl fmt::Display for ScalarDef {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.description)?;
-        writeln!(f, "scalar {}", self.name)
+        write!(f, "scalar {}", self.name)?;
+
+        if let Some(url) = &self.specified_by {
+            write!(f, " @specifiedBy(url: \"{}\")", url)?;
+        }
+
+        writeln!(f)
     }
 }
 
@@ -82,6 +97,18 @@ mod tests {
             scalar.to_string(),
             r#""Int representing number of treats received."
 scalar NumberOfTreatsPerDay
+"#
+        );
+    }
+
+    #[test]
+    fn it_encodes_scalar_with_specified_by() {
+        let mut scalar = ScalarDef::new("DateTime".to_string());
+        scalar.specified_by("https://tools.ietf.org/html/rfc3339".to_string());
+
+        assert_eq!(
+            scalar.to_string(),
+            r#"scalar DateTime @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
 "#
         );
     }
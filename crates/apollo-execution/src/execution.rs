@@ -0,0 +1,1511 @@
+//! Execution of queries and mutations.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Execution>.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
+
+use apollo_compiler::{
+    hir::{FieldDefinition, InputValueDefinition, OperationType, Type, TypeDefinition, Value},
+    ExecutableDocument, Schema,
+};
+use apollo_parser::{
+    ast::{self, AstNode},
+    LineIndex,
+};
+use futures::{executor::block_on, future::join_all};
+use indexmap::IndexMap;
+use serde_json::{Number, Value as JsonValue};
+
+use crate::{
+    introspection::SchemaIntrospection,
+    resolver::{AsyncResolver, BoxFuture, FieldError, JsonMap, ResolvedValue, Resolver},
+    response::{GraphQLError, Location, PathSegment, Response},
+};
+
+/// Execute a query or mutation, and get the response.
+///
+/// The document should be valid, see [`apollo_compiler::validate`]. The
+/// values of fields come from `root`, the object of the operation's root
+/// type, and from the objects its fields resolve to. The introspection
+/// fields are resolved from the schema. Subscriptions are executed with
+/// [`subscribe`](crate::subscribe) instead.
+///
+/// The values of `variables` are coerced to the types of the operation's
+/// variables, the values of arguments to the types of their arguments, and
+/// the values of scalar and enum fields to the types of their fields.
+///
+/// Fields that cannot be resolved or coerced are `null` in the response's
+/// `data`, with an error. If such a field is non-null, its parent becomes
+/// `null` instead, up to the nearest nullable field, or to `data` itself. If
+/// the operation cannot be executed at all, like when a variable is missing
+/// or invalid, the response only has an error.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{ExecutableDocument, Schema};
+/// use apollo_execution::{execute, FieldError, JsonMap, ResolvedValue, Resolver};
+/// use serde_json::json;
+///
+/// struct Query;
+///
+/// impl Resolver for Query {
+///     fn resolve_field<'a>(
+///         &'a self,
+///         field_name: &str,
+///         arguments: &JsonMap,
+///     ) -> Result<ResolvedValue<'a>, FieldError> {
+///         match field_name {
+///             "add" => {
+///                 let a = arguments["a"].as_i64().unwrap();
+///                 let b = arguments["b"].as_i64().unwrap();
+///                 Ok(ResolvedValue::leaf(a + b))
+///             }
+///             _ => Ok(ResolvedValue::null()),
+///         }
+///     }
+/// }
+///
+/// let schema = Schema::parse("type Query { add(a: Int!, b: Int! = 1): Int! }");
+/// let document = ExecutableDocument::parse("query.graphql", "{ add(a: 41) __typename }");
+///
+/// let response = execute(&schema, &document, None, &JsonMap::new(), &Query);
+/// assert_eq!(
+///     response.to_json(),
+///     json!({ "data": { "add": 42, "__typename": "Query" } })
+/// );
+/// ```
+pub fn execute(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+    root: &dyn Resolver,
+) -> Response {
+    block_on(execute_operation(
+        schema,
+        document,
+        operation_name,
+        variables,
+        ObjectValue::Sync(root),
+    ))
+}
+
+/// Execute a query or mutation with asynchronous resolvers, and get the
+/// response. See [`execute`].
+pub async fn execute_async(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+    root: &dyn AsyncResolver,
+) -> Response {
+    execute_operation(
+        schema,
+        document,
+        operation_name,
+        variables,
+        ObjectValue::Async(root),
+    )
+    .await
+}
+
+async fn execute_operation(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+    root: ObjectValue<'_>,
+) -> Response {
+    let (context, operation) =
+        match ExecutionContext::new(schema, document, operation_name, variables) {
+            Ok(execution) => execution,
+            Err(error) => return Response::from_error(*error),
+        };
+    let operation_type = operation_type(&operation);
+    if operation_type == OperationType::Subscription {
+        return Response::from_error(
+            context.error("subscriptions cannot be executed as queries", &operation),
+        );
+    }
+    let (root_type, selection_set) = match context.root(&operation) {
+        Ok(root) => root,
+        Err(error) => return Response::from_error(*error),
+    };
+    // The root fields of mutations are executed one after the other, so that
+    // their side effects happen in order.
+    let serial = operation_type == OperationType::Mutation;
+    let data = context
+        .execute_selection_set(&[selection_set], root_type, root, Vec::new(), serial)
+        .await;
+    context.response(data)
+}
+
+/// An object that a selection set is executed on.
+#[derive(Clone, Copy)]
+pub(crate) enum ObjectValue<'a> {
+    Sync(&'a dyn Resolver),
+    Async(&'a dyn AsyncResolver),
+}
+
+impl<'a> ObjectValue<'a> {
+    fn resolve_type(&self) -> Option<&'a str> {
+        match *self {
+            ObjectValue::Sync(object) => object.resolve_type(),
+            ObjectValue::Async(object) => object.resolve_type(),
+        }
+    }
+
+    async fn resolve_field(
+        &self,
+        field_name: &'a str,
+        arguments: &'a JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        match *self {
+            ObjectValue::Sync(object) => object.resolve_field(field_name, arguments),
+            ObjectValue::Async(object) => object.resolve_field(field_name, arguments).await,
+        }
+    }
+}
+
+/// A `null` value in a non-null position, which makes the nearest nullable
+/// parent `null`. The error explaining it has already been raised.
+#[derive(Debug)]
+pub(crate) struct PropagatedNull;
+
+/// The state of the execution of one operation.
+pub(crate) struct ExecutionContext<'a> {
+    pub(crate) schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    /// The fragment definitions of the document by name.
+    fragments: HashMap<String, ast::FragmentDefinition>,
+    /// The values of the operation's variables, including default values.
+    variables: JsonMap,
+    /// The introspection of the schema, computed when it is first needed.
+    introspection: OnceLock<SchemaIntrospection<'a>>,
+    /// The lines of the document, computed when the first error is located.
+    line_index: OnceLock<LineIndex>,
+    /// The errors raised by fields so far.
+    errors: RefCell<Vec<GraphQLError>>,
+}
+
+impl<'a> ExecutionContext<'a> {
+    /// Prepare the execution of the operation called `operation_name`, or of
+    /// the only operation of the document if no name is given.
+    pub(crate) fn new(
+        schema: &'a Schema,
+        document: &'a ExecutableDocument,
+        operation_name: Option<&str>,
+        variables: &JsonMap,
+    ) -> Result<(Self, ast::OperationDefinition), Box<GraphQLError>> {
+        let operation = operation(document, operation_name)
+            .map_err(|message| Box::new(GraphQLError::new(message)))?;
+        let fragments = document
+            .fragments()
+            .filter_map(|fragment| {
+                let name = fragment.fragment_name()?.name()?.text().to_string();
+                Some((name, fragment))
+            })
+            .collect();
+        let mut context = Self {
+            schema,
+            document,
+            fragments,
+            variables: JsonMap::new(),
+            introspection: OnceLock::new(),
+            line_index: OnceLock::new(),
+            errors: RefCell::new(Vec::new()),
+        };
+        context.variables = context.coerce_variable_values(&operation, variables)?;
+        Ok((context, operation))
+    }
+
+    /// Coerce the values provided for the variables of an operation to the
+    /// types of the variables, and add the default values of the variables
+    /// that are not provided.
+    ///
+    /// See: <https://spec.graphql.org/October2021/#CoerceVariableValues()>
+    fn coerce_variable_values(
+        &self,
+        operation: &ast::OperationDefinition,
+        variables: &JsonMap,
+    ) -> Result<JsonMap, Box<GraphQLError>> {
+        let mut values = JsonMap::new();
+        for definition in operation
+            .variable_definitions()
+            .into_iter()
+            .flat_map(|definitions| definitions.variable_definitions())
+        {
+            let name = definition.variable().and_then(|variable| variable.name());
+            let ty = definition.ty().and_then(variable_type);
+            let (name, ty) = match (name, ty) {
+                (Some(name), Some(ty)) => (name.text().to_string(), ty),
+                _ => continue,
+            };
+            let default = definition
+                .default_value()
+                .and_then(|default| default.value())
+                .and_then(|default| json_value(default, &JsonMap::new()));
+            let value = match (variables.get(&name), default) {
+                (None, Some(default)) => default,
+                (None, None) if ty.is_non_null() => {
+                    let message = format!(
+                        "the variable `${}` of the non-null type `{}` must be provided",
+                        name, ty
+                    );
+                    return Err(Box::new(self.error(message, &definition)));
+                }
+                (None, None) => continue,
+                (Some(value), _) => self.coerce_input_value(&ty, value).map_err(|reason| {
+                    let message = format!("invalid value for the variable `${}`: {}", name, reason);
+                    Box::new(self.error(message, &definition))
+                })?,
+            };
+            values.insert(name, value);
+        }
+        Ok(values)
+    }
+
+    /// Coerce the value of a variable or an argument to an input type.
+    /// Returns why the value is invalid if it cannot be coerced.
+    ///
+    /// See: <https://spec.graphql.org/October2021/#sec-Input-Values>
+    fn coerce_input_value(&self, ty: &Type, value: &JsonValue) -> Result<JsonValue, String> {
+        let invalid = || format!("expected a value of type `{}`, found `{}`", ty, value);
+        match (ty, value) {
+            (Type::NonNull(_), JsonValue::Null) => Err(invalid()),
+            (Type::NonNull(ty), value) => self.coerce_input_value(ty, value),
+            (_, JsonValue::Null) => Ok(JsonValue::Null),
+            (Type::List(item), JsonValue::Array(items)) => items
+                .iter()
+                .map(|value| self.coerce_input_value(item, value))
+                .collect::<Result<_, _>>()
+                .map(JsonValue::Array),
+            // A single value is coerced to a list of one item.
+            (Type::List(item), value) => {
+                Ok(JsonValue::Array(
+                    vec![self.coerce_input_value(item, value)?],
+                ))
+            }
+            (Type::Named(name), value) => match self.schema.type_by_name(name) {
+                Some(TypeDefinition::InputObject(input_object)) => {
+                    let fields = value.as_object().ok_or_else(invalid)?;
+                    if let Some(unknown) = fields
+                        .keys()
+                        .find(|field| input_object.field(field).is_none())
+                    {
+                        return Err(format!(
+                            "the input object `{}` does not have a field `{}`",
+                            name, unknown
+                        ));
+                    }
+                    let mut values = JsonMap::new();
+                    for definition in input_object.fields() {
+                        let value = match fields.get(definition.name()) {
+                            Some(value) => self.coerce_input_value(definition.ty(), value)?,
+                            None => match definition.default_value() {
+                                Some(default) => default_json_value(default),
+                                None if definition.ty().is_non_null() => {
+                                    return Err(format!(
+                                    "the field `{}.{}` of the non-null type `{}` must be provided",
+                                    name,
+                                    definition.name(),
+                                    definition.ty()
+                                ))
+                                }
+                                None => continue,
+                            },
+                        };
+                        values.insert(definition.name().to_string(), value);
+                    }
+                    Ok(JsonValue::Object(values))
+                }
+                Some(ty @ (TypeDefinition::Scalar(_) | TypeDefinition::Enum(_))) => {
+                    coerce_leaf_value(ty, value).ok_or_else(invalid)
+                }
+                _ => Err(format!("`{}` is not an input type", name)),
+            },
+        }
+    }
+
+    /// Get the root type and the selection set of an operation.
+    pub(crate) fn root(
+        &self,
+        operation: &ast::OperationDefinition,
+    ) -> Result<(&'a str, ast::SelectionSet), Box<GraphQLError>> {
+        let operation_type = operation_type(operation);
+        let root = self
+            .schema
+            .root_operation_name(operation_type)
+            .ok_or_else(|| {
+                let message = format!(
+                    "the schema does not define a {} root operation type",
+                    operation_type
+                );
+                Box::new(self.error(message, operation))
+            })?;
+        let selection_set = operation.selection_set().ok_or_else(|| {
+            Box::new(self.error("the operation does not have a selection set", operation))
+        })?;
+        Ok((root, selection_set))
+    }
+
+    /// Get the response for the data of the operation, with the errors
+    /// raised while executing it.
+    pub(crate) fn response(&self, data: Result<JsonMap, PropagatedNull>) -> Response {
+        Response {
+            errors: self.errors.take(),
+            data: Some(data.map_or(JsonValue::Null, JsonValue::Object)),
+            extensions: JsonMap::new(),
+        }
+    }
+
+    /// Create an error located at a node of the document.
+    pub(crate) fn error(&self, message: impl Into<String>, node: &impl AstNode) -> GraphQLError {
+        GraphQLError {
+            locations: vec![self.location(node)],
+            ..GraphQLError::new(message)
+        }
+    }
+
+    /// Get the location of the start of a node of the document.
+    fn location(&self, node: &impl AstNode) -> Location {
+        let line_index = self
+            .line_index
+            .get_or_init(|| LineIndex::new(self.document.source().text()));
+        let line_col = line_index.line_col_utf16(self.document.span(node).start());
+        Location {
+            line: line_col.line + 1,
+            column: line_col.col + 1,
+        }
+    }
+
+    /// Raise an error for the fields with the same response name at `path`.
+    fn field_error(&self, error: FieldError, fields: &[ast::Field], path: Vec<PathSegment>) {
+        let error = self.response_error(error, fields, path);
+        self.errors.borrow_mut().push(error);
+    }
+
+    /// Get the error in the response for an error of the fields with the same
+    /// response name at `path`.
+    pub(crate) fn response_error(
+        &self,
+        error: FieldError,
+        fields: &[ast::Field],
+        path: Vec<PathSegment>,
+    ) -> GraphQLError {
+        GraphQLError {
+            message: error.message().to_string(),
+            locations: fields.iter().map(|field| self.location(field)).collect(),
+            path,
+            extensions: error.extensions().clone(),
+        }
+    }
+
+    /// Execute selection sets on an object of type `object_type`, and get
+    /// the response object. The selection sets are merged, as for the
+    /// sub-selections of fields with the same response name.
+    ///
+    /// Fields are executed concurrently, or one after the other if `serial`
+    /// is true. If a non-null field is `null`, the whole object is `null`.
+    pub(crate) fn execute_selection_set<'b>(
+        &'b self,
+        selection_sets: &'b [ast::SelectionSet],
+        object_type: &'b str,
+        object: ObjectValue<'b>,
+        path: Vec<PathSegment>,
+        serial: bool,
+    ) -> BoxFuture<'b, Result<JsonMap, PropagatedNull>> {
+        Box::pin(async move {
+            let mut fields = IndexMap::new();
+            for selection_set in selection_sets {
+                self.collect_fields(object_type, selection_set, &mut HashSet::new(), &mut fields);
+            }
+            let mut response = JsonMap::new();
+            if serial {
+                for (response_name, fields) in fields {
+                    let mut path = path.clone();
+                    path.push(PathSegment::Field(response_name.clone()));
+                    let value = self
+                        .execute_field(object_type, object, &fields, path)
+                        .await?;
+                    if let Some(value) = value {
+                        response.insert(response_name, value);
+                    }
+                }
+            } else {
+                let values = join_all(fields.iter().map(|(response_name, fields)| {
+                    let mut path = path.clone();
+                    path.push(PathSegment::Field(response_name.clone()));
+                    self.execute_field(object_type, object, fields, path)
+                }))
+                .await;
+                for ((response_name, _), value) in fields.into_iter().zip(values) {
+                    if let Some(value) = value? {
+                        response.insert(response_name, value);
+                    }
+                }
+            }
+            Ok(response)
+        })
+    }
+
+    /// Collect the fields of a selection set that apply to an object of type
+    /// `object_type`, grouped by response name. `visited` holds the
+    /// fragments already spread, which are only collected once.
+    pub(crate) fn collect_fields(
+        &self,
+        object_type: &str,
+        selection_set: &ast::SelectionSet,
+        visited: &mut HashSet<String>,
+        fields: &mut IndexMap<String, Vec<ast::Field>>,
+    ) {
+        for selection in selection_set.selections() {
+            match selection {
+                ast::Selection::Field(field) => {
+                    if !self.should_include(field.directives()) {
+                        continue;
+                    }
+                    if let Some(name) = response_name(&field) {
+                        fields.entry(name).or_default().push(field);
+                    }
+                }
+                ast::Selection::FragmentSpread(spread) => {
+                    if !self.should_include(spread.directives()) {
+                        continue;
+                    }
+                    let fragment = spread
+                        .fragment_name()
+                        .and_then(|name| name.name())
+                        .map(|name| name.text().to_string())
+                        .filter(|name| visited.insert(name.clone()))
+                        .and_then(|name| self.fragments.get(&name));
+                    let fragment = match fragment {
+                        Some(fragment) => fragment,
+                        None => continue,
+                    };
+                    if !self.does_fragment_type_apply(object_type, fragment.type_condition()) {
+                        continue;
+                    }
+                    if let Some(selection_set) = fragment.selection_set() {
+                        self.collect_fields(object_type, &selection_set, visited, fields);
+                    }
+                }
+                ast::Selection::InlineFragment(inline) => {
+                    if !self.should_include(inline.directives())
+                        || !self.does_fragment_type_apply(object_type, inline.type_condition())
+                    {
+                        continue;
+                    }
+                    if let Some(selection_set) = inline.selection_set() {
+                        self.collect_fields(object_type, &selection_set, visited, fields);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check the `@skip` and `@include` directives of a selection.
+    fn should_include(&self, directives: Option<ast::Directives>) -> bool {
+        for directive in directives
+            .into_iter()
+            .flat_map(|directives| directives.directives())
+        {
+            let name = match directive.name() {
+                Some(name) => name.text().to_string(),
+                None => continue,
+            };
+            let condition = directive
+                .arguments()
+                .into_iter()
+                .flat_map(|arguments| arguments.arguments())
+                .find(|argument| argument.name().is_some_and(|name| name.text() == "if"))
+                .and_then(|argument| argument.value())
+                .and_then(|value| json_value(value, &self.variables));
+            match (name.as_str(), condition) {
+                ("skip", Some(JsonValue::Bool(true))) => return false,
+                ("include", Some(JsonValue::Bool(false))) => return false,
+                _ => {}
+            }
+        }
+        true
+    }
+
+    /// Check whether a fragment with a type condition applies to an object
+    /// of type `object_type`. Fragments without a type condition always
+    /// apply.
+    fn does_fragment_type_apply(
+        &self,
+        object_type: &str,
+        condition: Option<ast::TypeCondition>,
+    ) -> bool {
+        let condition = match condition
+            .and_then(|condition| condition.named_type())
+            .and_then(|named| named.name())
+        {
+            Some(name) => name.text().to_string(),
+            None => return true,
+        };
+        condition == object_type
+            || self
+                .schema
+                .possible_types(&condition)
+                .iter()
+                .any(|ty| ty.name() == object_type)
+    }
+
+    /// Execute the fields with the same response name on an object. Returns
+    /// `None` if the field is not defined on the object's type, in which
+    /// case it is left out of the response.
+    async fn execute_field(
+        &self,
+        object_type: &str,
+        object: ObjectValue<'_>,
+        fields: &[ast::Field],
+        path: Vec<PathSegment>,
+    ) -> Result<Option<JsonValue>, PropagatedNull> {
+        let name = match fields[0].name() {
+            Some(name) => name.text().to_string(),
+            None => return Ok(None),
+        };
+        if name == "__typename" {
+            return Ok(Some(JsonValue::String(object_type.to_string())));
+        }
+        let definition = match self.schema.field(object_type, &name) {
+            Some(definition) => definition,
+            None => return Ok(None),
+        };
+        let arguments = match self.argument_values(definition.arguments(), &fields[0]) {
+            Ok(arguments) => arguments,
+            Err(error) => {
+                let value = self
+                    .complete_field(object_type, definition, fields, Err(error), path)
+                    .await?;
+                return Ok(Some(value));
+            }
+        };
+        let value = match name.as_str() {
+            "__schema" => Ok(self.introspection().schema()),
+            "__type" => Ok(match arguments.get("name") {
+                Some(JsonValue::String(name)) => self.introspection().type_by_name(name),
+                _ => ResolvedValue::null(),
+            }),
+            _ => object.resolve_field(&name, &arguments).await,
+        };
+        let value = self
+            .complete_field(object_type, definition, fields, value, path)
+            .await?;
+        Ok(Some(value))
+    }
+
+    /// Complete the value a field was resolved to on an object of type
+    /// `object_type`, or raise the error it could not be resolved with.
+    pub(crate) async fn complete_field(
+        &self,
+        object_type: &str,
+        definition: &FieldDefinition,
+        fields: &[ast::Field],
+        value: Result<ResolvedValue<'_>, FieldError>,
+        path: Vec<PathSegment>,
+    ) -> Result<JsonValue, PropagatedNull> {
+        match value {
+            Ok(value) => {
+                let coordinate = format!("{}.{}", object_type, definition.name());
+                self.complete_value(definition.ty(), fields, &coordinate, value, path)
+                    .await
+            }
+            Err(error) => {
+                self.field_error(error, fields, path);
+                if definition.ty().is_non_null() {
+                    return Err(PropagatedNull);
+                }
+                Ok(JsonValue::Null)
+            }
+        }
+    }
+
+    /// Complete a resolved value according to the type of its field, by
+    /// executing the sub-selections of the field on objects.
+    fn complete_value<'b>(
+        &'b self,
+        ty: &'b Type,
+        fields: &'b [ast::Field],
+        coordinate: &'b str,
+        value: ResolvedValue<'b>,
+        path: Vec<PathSegment>,
+    ) -> BoxFuture<'b, Result<JsonValue, PropagatedNull>> {
+        Box::pin(async move {
+            let completed = match (ty, value) {
+                (Type::NonNull(_), value) if value.is_null() => {
+                    let message =
+                        format!("cannot return null for the non-null field `{}`", coordinate);
+                    self.field_error(FieldError::new(message), fields, path);
+                    return Err(PropagatedNull);
+                }
+                (Type::NonNull(ty), value) => {
+                    // The inner value can only be `null` if an error was
+                    // raised while completing it.
+                    return match self
+                        .complete_value(ty, fields, coordinate, value, path)
+                        .await?
+                    {
+                        JsonValue::Null => Err(PropagatedNull),
+                        value => Ok(value),
+                    };
+                }
+                (_, value) if value.is_null() => Ok(JsonValue::Null),
+                (Type::List(item), ResolvedValue::List(items)) => {
+                    self.complete_list(item, fields, coordinate, items, path)
+                        .await
+                }
+                (Type::List(item), ResolvedValue::Leaf(JsonValue::Array(items))) => {
+                    let items = items.into_iter().map(ResolvedValue::Leaf).collect();
+                    self.complete_list(item, fields, coordinate, items, path)
+                        .await
+                }
+                (Type::List(_), _) => {
+                    let message = format!("expected a list for the field `{}`", coordinate);
+                    self.field_error(FieldError::new(message), fields, path);
+                    Err(PropagatedNull)
+                }
+                (Type::Named(name), value) => {
+                    self.complete_named(name, fields, coordinate, value, path)
+                        .await
+                }
+            };
+            // A nullable value stops the propagation of `null`.
+            Ok(completed.unwrap_or(JsonValue::Null))
+        })
+    }
+
+    async fn complete_list<'b>(
+        &'b self,
+        item: &'b Type,
+        fields: &'b [ast::Field],
+        coordinate: &'b str,
+        items: Vec<ResolvedValue<'b>>,
+        path: Vec<PathSegment>,
+    ) -> Result<JsonValue, PropagatedNull> {
+        let items = items.into_iter().enumerate().map(|(index, value)| {
+            let mut path = path.clone();
+            path.push(PathSegment::Index(index));
+            self.complete_value(item, fields, coordinate, value, path)
+        });
+        let items = join_all(items)
+            .await
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+        Ok(JsonValue::Array(items))
+    }
+
+    async fn complete_named<'b>(
+        &'b self,
+        name: &'b str,
+        fields: &'b [ast::Field],
+        coordinate: &'b str,
+        value: ResolvedValue<'b>,
+        path: Vec<PathSegment>,
+    ) -> Result<JsonValue, PropagatedNull> {
+        let ty = match self.schema.type_by_name(name) {
+            Some(ty) => ty,
+            None => return Ok(JsonValue::Null),
+        };
+        let object = match (ty, &value) {
+            (_, ResolvedValue::Leaf(value)) if ty.is_leaf_type() => {
+                return match coerce_leaf_value(ty, value) {
+                    Some(value) => Ok(value),
+                    None => {
+                        let value = match value {
+                            JsonValue::String(value) => value.clone(),
+                            value => value.to_string(),
+                        };
+                        let message = match ty {
+                            TypeDefinition::Enum(_) => format!(
+                                "`{}` is not a value of the enum `{}` for the field `{}`",
+                                value, name, coordinate
+                            ),
+                            _ => format!(
+                                "`{}` is not a valid `{}` for the field `{}`",
+                                value, name, coordinate
+                            ),
+                        };
+                        self.field_error(FieldError::new(message), fields, path);
+                        Err(PropagatedNull)
+                    }
+                };
+            }
+            (_, ResolvedValue::Object(object)) if ty.is_composite_type() => {
+                ObjectValue::Sync(&**object)
+            }
+            (_, ResolvedValue::AsyncObject(object)) if ty.is_composite_type() => {
+                ObjectValue::Async(&**object)
+            }
+            _ => {
+                let expected = if ty.is_leaf_type() {
+                    "a leaf value"
+                } else {
+                    "an object"
+                };
+                let message = format!("expected {} for the field `{}`", expected, coordinate);
+                self.field_error(FieldError::new(message), fields, path);
+                return Err(PropagatedNull);
+            }
+        };
+        let object_type = match self.resolve_type(ty, object, coordinate) {
+            Ok(object_type) => object_type,
+            Err(message) => {
+                self.field_error(FieldError::new(message), fields, path);
+                return Err(PropagatedNull);
+            }
+        };
+        let selection_sets: Vec<_> = fields
+            .iter()
+            .filter_map(|field| field.selection_set())
+            .collect();
+        let response = self
+            .execute_selection_set(&selection_sets, object_type, object, path, false)
+            .await?;
+        Ok(JsonValue::Object(response))
+    }
+
+    /// Get the object type of an object value of the type `ty`. For abstract
+    /// types, this is decided by the object's `resolve_type` hook.
+    fn resolve_type<'b>(
+        &self,
+        ty: &'b TypeDefinition,
+        object: ObjectValue<'b>,
+        coordinate: &str,
+    ) -> Result<&'b str, String> {
+        if !ty.is_abstract_type() {
+            return Ok(ty.name());
+        }
+        let object_type = object.resolve_type().ok_or_else(|| {
+            format!(
+                "the abstract type `{}` must resolve to an object type for the field `{}`",
+                ty.name(),
+                coordinate
+            )
+        })?;
+        let is_possible = self
+            .schema
+            .possible_types(ty.name())
+            .iter()
+            .any(|possible| possible.name() == object_type);
+        if !is_possible {
+            return Err(format!(
+                "`{}` is not a possible type of `{}` for the field `{}`",
+                object_type,
+                ty.name(),
+                coordinate
+            ));
+        }
+        Ok(object_type)
+    }
+
+    /// Get the values of the arguments of a field: the values provided in
+    /// the field, or the default values of the argument definitions, coerced
+    /// to the types of the arguments.
+    ///
+    /// See: <https://spec.graphql.org/October2021/#CoerceArgumentValues()>
+    pub(crate) fn argument_values(
+        &self,
+        definitions: &[InputValueDefinition],
+        field: &ast::Field,
+    ) -> Result<JsonMap, FieldError> {
+        let mut values = JsonMap::new();
+        for definition in definitions {
+            let value = field
+                .arguments()
+                .into_iter()
+                .flat_map(|arguments| arguments.arguments())
+                .find(|argument| {
+                    argument
+                        .name()
+                        .is_some_and(|name| name.text() == definition.name())
+                })
+                .and_then(|argument| argument.value())
+                .and_then(|value| json_value(value, &self.variables))
+                .or_else(|| definition.default_value().map(default_json_value));
+            if let Some(value) = value {
+                let value = self
+                    .coerce_input_value(definition.ty(), &value)
+                    .map_err(|reason| {
+                        FieldError::new(format!(
+                            "invalid value for the argument `{}`: {}",
+                            definition.name(),
+                            reason
+                        ))
+                    })?;
+                values.insert(definition.name().to_string(), value);
+            }
+        }
+        Ok(values)
+    }
+
+    fn introspection(&self) -> &SchemaIntrospection<'a> {
+        self.introspection
+            .get_or_init(|| SchemaIntrospection::new(self.schema))
+    }
+}
+
+/// Get the operation called `operation_name`, or the only operation of the
+/// document if no name is given.
+fn operation(
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Result<ast::OperationDefinition, String> {
+    let mut operations = document.operations();
+    match operation_name {
+        Some(name) => operations
+            .find(|operation| operation.name().is_some_and(|n| n.text() == name))
+            .ok_or_else(|| format!("the operation `{}` is not defined", name)),
+        None => match (operations.next(), operations.next()) {
+            (Some(operation), None) => Ok(operation),
+            (None, _) => Err("the document does not contain any operations".to_string()),
+            (Some(_), Some(_)) => Err(
+                "an operation name is required when the document contains multiple operations"
+                    .to_string(),
+            ),
+        },
+    }
+}
+
+/// Get the type of an operation. Operations written as just a selection set
+/// are queries.
+pub(crate) fn operation_type(operation: &ast::OperationDefinition) -> OperationType {
+    match operation.operation_type() {
+        Some(ty) if ty.mutation_token().is_some() => OperationType::Mutation,
+        Some(ty) if ty.subscription_token().is_some() => OperationType::Subscription,
+        _ => OperationType::Query,
+    }
+}
+
+/// Get the response name of a field: its alias, or its name.
+pub(crate) fn response_name(field: &ast::Field) -> Option<String> {
+    let name = match field.alias() {
+        Some(alias) => alias.name()?,
+        None => field.name()?,
+    };
+    Some(name.text().to_string())
+}
+
+/// Convert a value in a document to JSON. Returns `None` for variables
+/// without a value, and for values that are incomplete because of syntax
+/// errors.
+pub(crate) fn json_value(value: ast::Value, variables: &JsonMap) -> Option<JsonValue> {
+    Some(match value {
+        ast::Value::Variable(variable) => variables.get(variable.name()?.text().as_str())?.clone(),
+        ast::Value::IntValue(int) => match i64::try_from(int.clone()) {
+            Ok(int) => JsonValue::from(int),
+            Err(_) => float(f64::try_from(int).ok()?),
+        },
+        ast::Value::FloatValue(value) => float(f64::try_from(value).ok()?),
+        ast::Value::StringValue(string) => JsonValue::String(string.value().ok()?.into_owned()),
+        ast::Value::BooleanValue(boolean) => JsonValue::Bool(boolean.into()),
+        ast::Value::NullValue(_) => JsonValue::Null,
+        ast::Value::EnumValue(value) => JsonValue::String(value.text().to_string()),
+        ast::Value::ListValue(list) => JsonValue::Array(
+            list.values()
+                .map(|value| json_value(value, variables).unwrap_or(JsonValue::Null))
+                .collect(),
+        ),
+        ast::Value::ObjectValue(object) => JsonValue::Object(
+            object
+                .object_fields()
+                .filter_map(|field| {
+                    let name = field.name()?.text().to_string();
+                    Some((name, json_value(field.value()?, variables)?))
+                })
+                .collect(),
+        ),
+    })
+}
+
+/// Convert a default value in the schema to JSON.
+fn default_json_value(value: &Value) -> JsonValue {
+    match value {
+        Value::Variable(_) | Value::Null => JsonValue::Null,
+        Value::Int(text) => match text.parse::<i64>() {
+            Ok(int) => JsonValue::from(int),
+            Err(_) => text.parse().map(float).unwrap_or(JsonValue::Null),
+        },
+        Value::Float(text) => text.parse().map(float).unwrap_or(JsonValue::Null),
+        Value::String(string) | Value::Enum(string) => JsonValue::String(string.clone()),
+        Value::Boolean(boolean) => JsonValue::Bool(*boolean),
+        Value::List(values) => JsonValue::Array(values.iter().map(default_json_value).collect()),
+        Value::Object(fields) => JsonValue::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), default_json_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Coerce a value to a scalar or enum type. Values of the built-in scalars
+/// are checked and converted, like integers to `ID` strings; values of custom
+/// scalars are kept as they are. Returns `None` if the value is invalid.
+///
+/// See: <https://spec.graphql.org/October2021/#sec-Scalars>
+fn coerce_leaf_value(ty: &TypeDefinition, value: &JsonValue) -> Option<JsonValue> {
+    if let TypeDefinition::Enum(enum_ty) = ty {
+        enum_ty.value(value.as_str()?)?;
+        return Some(value.clone());
+    }
+    match (ty.name(), value) {
+        ("Int", JsonValue::Number(number)) => {
+            let int = number.as_f64()?;
+            let is_int = int.fract() == 0.0 && int >= i32::MIN.into() && int <= i32::MAX.into();
+            is_int.then(|| JsonValue::from(int as i32))
+        }
+        ("Float", JsonValue::Number(_))
+        | ("String" | "ID", JsonValue::String(_))
+        | ("Boolean", JsonValue::Bool(_)) => Some(value.clone()),
+        ("ID", JsonValue::Number(number)) if number.is_i64() || number.is_u64() => {
+            Some(JsonValue::String(number.to_string()))
+        }
+        ("Int" | "Float" | "String" | "Boolean" | "ID", _) => None,
+        _ => Some(value.clone()),
+    }
+}
+
+/// Get the type of a variable definition. Returns `None` if it is incomplete
+/// because of a syntax error.
+fn variable_type(ty: ast::Type) -> Option<Type> {
+    Some(match ty {
+        ast::Type::NamedType(named) => Type::Named(named.name()?.text().to_string()),
+        ast::Type::ListType(list) => Type::List(Box::new(variable_type(list.ty()?)?)),
+        ast::Type::NonNullType(non_null) => {
+            let inner = match (non_null.named_type(), non_null.list_type()) {
+                (Some(named), _) => ast::Type::NamedType(named),
+                (None, Some(list)) => ast::Type::ListType(list),
+                (None, None) => return None,
+            };
+            Type::NonNull(Box::new(variable_type(inner)?))
+        }
+    })
+}
+
+fn float(value: f64) -> JsonValue {
+    Number::from_f64(value).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use apollo_compiler::{ExecutableDocument, Schema};
+    use futures::executor::block_on;
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value as JsonValue};
+
+    use crate::{
+        execute, execute_async, AsyncResolver, BoxFuture, FieldError, JsonMap, ResolvedValue,
+        Resolver,
+    };
+
+    const SCHEMA: &str = r#"
+type Query {
+  pets(first: Int = 2): [Pet!]!
+  search: [Any]
+  pet(name: String!): Pet
+  count: Int!
+  broken: String
+  names: [String!]
+  required: [String!]!
+  color: Color
+}
+type Mutation { increment(by: Int = 1): Int! }
+interface Pet { name: String! }
+type Cat implements Pet { name: String! lives: Int }
+type Dog implements Pet { name: String! barks: Boolean }
+union Any = Cat | Dog
+enum Color { RED GREEN }
+"#;
+
+    struct Query {
+        count: Cell<i64>,
+    }
+
+    impl Resolver for Query {
+        fn resolve_field<'a>(
+            &'a self,
+            field_name: &str,
+            arguments: &JsonMap,
+        ) -> Result<ResolvedValue<'a>, FieldError> {
+            let pets = || vec![Pet::Cat("Tom"), Pet::Dog("Rex"), Pet::Cat("Felix")];
+            match field_name {
+                "pets" => {
+                    let first = arguments["first"].as_u64().unwrap() as usize;
+                    Ok(ResolvedValue::list(
+                        pets().into_iter().take(first).map(ResolvedValue::object),
+                    ))
+                }
+                "search" => Ok(ResolvedValue::list(
+                    pets()
+                        .into_iter()
+                        .map(ResolvedValue::object)
+                        .chain([ResolvedValue::object(Pet::Unknown)]),
+                )),
+                "pet" => Ok(pets()
+                    .into_iter()
+                    .find(|pet| pet.name() == arguments["name"])
+                    .map_or_else(ResolvedValue::null, ResolvedValue::object)),
+                "count" | "increment" => {
+                    let by = arguments.get("by").and_then(JsonValue::as_i64).unwrap_or(0);
+                    self.count.set(self.count.get() + by);
+                    Ok(ResolvedValue::leaf(self.count.get()))
+                }
+                "broken" => Err(FieldError::new("the field is broken").extension("code", "BROKEN")),
+                "names" | "required" => Ok(ResolvedValue::leaf(json!(["a", null]))),
+                "color" => Ok(ResolvedValue::leaf("BLUE")),
+                _ => Ok(ResolvedValue::null()),
+            }
+        }
+    }
+
+    enum Pet {
+        Cat(&'static str),
+        Dog(&'static str),
+        Unknown,
+    }
+
+    impl Pet {
+        fn name(&self) -> &'static str {
+            match self {
+                Pet::Cat(name) | Pet::Dog(name) => name,
+                Pet::Unknown => "",
+            }
+        }
+    }
+
+    impl Resolver for Pet {
+        fn resolve_type(&self) -> Option<&str> {
+            match self {
+                Pet::Cat(_) => Some("Cat"),
+                Pet::Dog(_) => Some("Dog"),
+                Pet::Unknown => None,
+            }
+        }
+
+        fn resolve_field<'a>(
+            &'a self,
+            field_name: &str,
+            _arguments: &JsonMap,
+        ) -> Result<ResolvedValue<'a>, FieldError> {
+            Ok(match field_name {
+                "name" => ResolvedValue::leaf(self.name()),
+                "lives" => ResolvedValue::leaf(9),
+                "barks" => ResolvedValue::leaf(true),
+                _ => ResolvedValue::null(),
+            })
+        }
+    }
+
+    fn execute_with(query: &str, variables: JsonMap) -> JsonValue {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse("query.graphql", query);
+        let root = Query {
+            count: Cell::new(0),
+        };
+        execute(&schema, &document, None, &variables, &root).to_json()
+    }
+
+    #[test]
+    fn it_resolves_abstract_types() {
+        let response = execute_with(
+            r#"
+{
+  pets(first: 3) {
+    __typename
+    name
+    ... on Cat { lives }
+    ...DogFields
+  }
+  pet(name: "Rex") { ... on Dog { barks } }
+}
+fragment DogFields on Dog { barks }
+"#,
+            JsonMap::new(),
+        );
+        assert_eq!(
+            response,
+            json!({
+                "data": {
+                    "pets": [
+                        { "__typename": "Cat", "name": "Tom", "lives": 9 },
+                        { "__typename": "Dog", "name": "Rex", "barks": true },
+                        { "__typename": "Cat", "name": "Felix", "lives": 9 },
+                    ],
+                    "pet": { "barks": true },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn it_applies_skip_and_include() {
+        let response = execute_with(
+            r#"
+query($skip: Boolean!, $include: Boolean = false) {
+  pets {
+    name @skip(if: $skip)
+    ... on Cat @include(if: $include) { lives }
+    ... @skip(if: false) { alias: name }
+  }
+}
+"#,
+            json!({ "skip": true }).as_object().unwrap().clone(),
+        );
+        assert_eq!(
+            response,
+            json!({ "data": { "pets": [{ "alias": "Tom" }, { "alias": "Rex" }] } })
+        );
+    }
+
+    #[test]
+    fn it_executes_mutations_serially() {
+        let response = execute_with(
+            "mutation { a: increment b: increment(by: 10) c: increment(by: -3) }",
+            JsonMap::new(),
+        );
+        assert_eq!(response, json!({ "data": { "a": 1, "b": 11, "c": 8 } }));
+    }
+
+    #[test]
+    fn it_reports_field_errors() {
+        let response = execute_with(
+            "{ broken color search { ... on Cat { name } } }",
+            JsonMap::new(),
+        );
+        assert_eq!(
+            response,
+            json!({
+                "errors": [
+                    {
+                        "message": "the field is broken",
+                        "locations": [{ "line": 1, "column": 3 }],
+                        "path": ["broken"],
+                        "extensions": { "code": "BROKEN" },
+                    },
+                    {
+                        "message": "`BLUE` is not a value of the enum `Color` for the field `Query.color`",
+                        "locations": [{ "line": 1, "column": 10 }],
+                        "path": ["color"],
+                    },
+                    {
+                        "message": "the abstract type `Any` must resolve to an object type for the field `Query.search`",
+                        "locations": [{ "line": 1, "column": 16 }],
+                        "path": ["search", 3],
+                    },
+                ],
+                "data": {
+                    "broken": null,
+                    "color": null,
+                    "search": [{ "name": "Tom" }, {}, { "name": "Felix" }, null],
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn it_propagates_null_to_the_nearest_nullable_parent() {
+        let response = execute_with("{ count names }", JsonMap::new());
+        assert_eq!(
+            response,
+            json!({
+                "errors": [{
+                    "message": "cannot return null for the non-null field `Query.names`",
+                    "locations": [{ "line": 1, "column": 9 }],
+                    "path": ["names", 1],
+                }],
+                "data": { "count": 0, "names": null }
+            })
+        );
+
+        let response = execute_with("{ count\n  required\n  again: required }", JsonMap::new());
+        assert_eq!(
+            response,
+            json!({
+                "errors": [
+                    {
+                        "message": "cannot return null for the non-null field `Query.required`",
+                        "locations": [{ "line": 2, "column": 3 }],
+                        "path": ["required", 1],
+                    },
+                    {
+                        "message": "cannot return null for the non-null field `Query.required`",
+                        "locations": [{ "line": 3, "column": 3 }],
+                        "path": ["again", 1],
+                    },
+                ],
+                "data": null
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_subscriptions() {
+        let schema = Schema::parse("type Query { a: Int } type Subscription { a: Int }");
+        let document = ExecutableDocument::parse("query.graphql", "subscription { a }");
+        let root = Query {
+            count: Cell::new(0),
+        };
+        assert_eq!(
+            execute(&schema, &document, None, &JsonMap::new(), &root).to_json(),
+            json!({
+                "errors": [{
+                    "message": "subscriptions cannot be executed as queries",
+                    "locations": [{ "line": 1, "column": 1 }],
+                }]
+            })
+        );
+    }
+
+    struct AsyncQuery;
+
+    impl AsyncResolver for AsyncQuery {
+        fn resolve_field<'a>(
+            &'a self,
+            field_name: &'a str,
+            _arguments: &'a JsonMap,
+        ) -> BoxFuture<'a, Result<ResolvedValue<'a>, FieldError>> {
+            Box::pin(async move {
+                match field_name {
+                    "count" => Ok(ResolvedValue::leaf(1)),
+                    "pet" => Ok(ResolvedValue::object(Pet::Dog("Rex"))),
+                    "pets" => Ok(ResolvedValue::list([ResolvedValue::async_object(
+                        AsyncQuery,
+                    )])),
+                    _ => Ok(ResolvedValue::null()),
+                }
+            })
+        }
+
+        fn resolve_type(&self) -> Option<&str> {
+            Some("Cat")
+        }
+    }
+
+    #[test]
+    fn it_executes_async_resolvers() {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse(
+            "query.graphql",
+            r#"{ count pet(name: "Rex") { name } pets { __typename lives } }"#,
+        );
+        let response = block_on(execute_async(
+            &schema,
+            &document,
+            None,
+            &JsonMap::new(),
+            &AsyncQuery,
+        ))
+        .to_json();
+        assert_eq!(
+            response,
+            json!({
+                "data": {
+                    "count": 1,
+                    "pet": { "name": "Rex" },
+                    "pets": [{ "__typename": "Cat", "lives": null }],
+                }
+            })
+        );
+    }
+
+    /// Resolves each field to the value of the same name, and `echo` to its
+    /// arguments.
+    struct Values(JsonValue);
+
+    impl Resolver for Values {
+        fn resolve_field<'a>(
+            &'a self,
+            field_name: &str,
+            arguments: &JsonMap,
+        ) -> Result<ResolvedValue<'a>, FieldError> {
+            Ok(match field_name {
+                "echo" => ResolvedValue::leaf(JsonValue::Object(arguments.clone()).to_string()),
+                _ => ResolvedValue::leaf(self.0[field_name].clone()),
+            })
+        }
+    }
+
+    const VALUES_SCHEMA: &str = r#"
+type Query {
+  int: Int
+  float: Float
+  id: ID
+  color: Color
+  custom: Custom
+  big: Int
+  text: Int
+  object: String
+  number: Color
+  echo(int: Int, list: [Int], input: Input, default: Int, id: ID, filter: Filter): String
+}
+input Input { a: Int!, b: String = "b", c: [Color!] }
+input Filter { a: Int = 10, b: String }
+enum Color { RED GREEN }
+scalar Custom
+"#;
+
+    fn execute_values(query: &str, values: JsonValue, variables: JsonValue) -> JsonValue {
+        let schema = Schema::parse(VALUES_SCHEMA);
+        let document = ExecutableDocument::parse("query.graphql", query);
+        let variables = variables.as_object().unwrap().clone();
+        execute(&schema, &document, None, &variables, &Values(values)).to_json()
+    }
+
+    #[test]
+    fn it_coerces_leaf_values() {
+        let response = execute_values(
+            "{ int float id color custom big text object number }",
+            json!({
+                "int": 1.0,
+                "float": 2,
+                "id": 3,
+                "color": "RED",
+                "custom": { "a": 1 },
+                "big": 2147483648_i64,
+                "text": "not an int",
+                "object": { "a": 1 },
+                "number": 5,
+            }),
+            json!({}),
+        );
+        assert_eq!(
+            response,
+            json!({
+                "errors": [
+                    {
+                        "message": "`2147483648` is not a valid `Int` for the field `Query.big`",
+                        "locations": [{ "line": 1, "column": 29 }],
+                        "path": ["big"],
+                    },
+                    {
+                        "message": "`not an int` is not a valid `Int` for the field `Query.text`",
+                        "locations": [{ "line": 1, "column": 33 }],
+                        "path": ["text"],
+                    },
+                    {
+                        "message": "`{\"a\":1}` is not a valid `String` for the field `Query.object`",
+                        "locations": [{ "line": 1, "column": 38 }],
+                        "path": ["object"],
+                    },
+                    {
+                        "message": "`5` is not a value of the enum `Color` for the field `Query.number`",
+                        "locations": [{ "line": 1, "column": 45 }],
+                        "path": ["number"],
+                    },
+                ],
+                "data": {
+                    "int": 1,
+                    "float": 2,
+                    "id": "3",
+                    "color": "RED",
+                    "custom": { "a": 1 },
+                    "big": null,
+                    "text": null,
+                    "object": null,
+                    "number": null,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn it_coerces_variable_values() {
+        let response = execute_values(
+            r#"
+query($int: Int!, $list: [Int], $input: Input, $default: Int = 3, $unused: ID) {
+  echo(int: $int, list: $list, input: $input, default: $default)
+}
+"#,
+            json!({}),
+            json!({ "int": 1.0, "list": 2, "input": { "a": 1, "c": ["GREEN"] } }),
+        );
+        let echo = response["data"]["echo"].as_str().unwrap();
+        assert_eq!(
+            serde_json::from_str::<JsonValue>(echo).unwrap(),
+            json!({
+                "int": 1,
+                "list": [2],
+                "input": { "a": 1, "b": "b", "c": ["GREEN"] },
+                "default": 3,
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_variable_values() {
+        let query = "query($int: Int!, $input: Input) { echo(int: $int, input: $input) }";
+        let error = |variables| {
+            let response = execute_values(query, json!({}), variables);
+            assert_eq!(response.get("data"), None);
+            response["errors"][0]["message"].clone()
+        };
+        assert_eq!(
+            error(json!({})),
+            "the variable `$int` of the non-null type `Int!` must be provided"
+        );
+        assert_eq!(
+            error(json!({ "int": null })),
+            "invalid value for the variable `$int`: expected a value of type `Int!`, found `null`"
+        );
+        assert_eq!(
+            error(json!({ "int": "abc" })),
+            "invalid value for the variable `$int`: expected a value of type `Int`, found `\"abc\"`"
+        );
+        assert_eq!(
+            error(json!({ "int": 1, "input": { "b": "b" } })),
+            "invalid value for the variable `$input`: the field `Input.a` of the non-null type `Int!` must be provided"
+        );
+        assert_eq!(
+            error(json!({ "int": 1, "input": { "a": 1, "d": 1 } })),
+            "invalid value for the variable `$input`: the input object `Input` does not have a field `d`"
+        );
+        assert_eq!(
+            error(json!({ "int": 1, "input": { "a": 1, "c": ["BLUE"] } })),
+            "invalid value for the variable `$input`: expected a value of type `Color`, found `\"BLUE\"`"
+        );
+
+        let response = execute_values(query, json!({}), json!({}));
+        assert_eq!(
+            response["errors"][0]["locations"],
+            json!([{ "line": 1, "column": 7 }])
+        );
+    }
+
+    #[test]
+    fn it_coerces_argument_values() {
+        let response = execute_values(
+            r#"
+query($id: ID, $filter: Filter, $list: [Int]) {
+  literals: echo(id: 1, filter: { b: "x" }, list: 1)
+  variables: echo(id: $id, filter: $filter, list: $list)
+  invalid: echo(int: "a")
+}
+"#,
+            json!({}),
+            json!({ "id": 1, "filter": { "b": "x" }, "list": 1 }),
+        );
+        let expected = json!({ "id": "1", "filter": { "a": 10, "b": "x" }, "list": [1] });
+        for alias in ["literals", "variables"] {
+            let echo = response["data"][alias].as_str().unwrap();
+            assert_eq!(serde_json::from_str::<JsonValue>(echo).unwrap(), expected);
+        }
+        assert_eq!(response["data"]["invalid"], JsonValue::Null);
+        assert_eq!(
+            response["errors"],
+            json!([{
+                "message": "invalid value for the argument `int`: expected a value of type `Int`, found `\"a\"`",
+                "locations": [{ "line": 5, "column": 3 }],
+                "path": ["invalid"],
+            }])
+        );
+    }
+}
@@ -0,0 +1,1167 @@
+//! Execution of introspection queries against a schema.
+//!
+//! The results are the same as the ones graphql-js gives for a schema built
+//! from the same SDL, down to the order of types and the printing of default
+//! values.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Introspection>.
+
+use std::{collections::HashSet, fmt};
+
+use apollo_compiler::{
+    hir::{
+        Directive, DirectiveDefinition, EnumValueDefinition, FieldDefinition, InputValueDefinition,
+        OperationType, Type, TypeDefinition, Value,
+    },
+    ExecutableDocument, Schema,
+};
+use futures::executor::block_on;
+use indexmap::IndexMap;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    execution::{ExecutionContext, ObjectValue},
+    resolver::{FieldError, JsonMap, ResolvedValue, Resolver},
+    response::Response,
+};
+
+/// The deprecation reason of elements with a `@deprecated` directive
+/// without a `reason` argument.
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// Execute an operation that only selects the introspection fields
+/// `__schema`, `__type` and `__typename` on the root type, and get the
+/// response.
+///
+/// The response only has an error if the operation cannot be found, or if
+/// it selects other root fields.
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{ExecutableDocument, Schema};
+/// use apollo_execution::{execute_introspection, JsonMap};
+/// use serde_json::json;
+///
+/// let schema = Schema::parse("type Query { me: String @deprecated }");
+/// let document = ExecutableDocument::parse(
+///     "query.graphql",
+///     "{ __type(name: \"Query\") { fields(includeDeprecated: true) { name isDeprecated } } }",
+/// );
+/// let response = execute_introspection(&schema, &document, None, &JsonMap::new());
+/// assert_eq!(
+///     response.to_json(),
+///     json!({ "data": { "__type": { "fields": [{ "name": "me", "isDeprecated": true }] } } })
+/// );
+/// ```
+pub fn execute_introspection(
+    schema: &Schema,
+    document: &ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+) -> Response {
+    let (context, operation) =
+        match ExecutionContext::new(schema, document, operation_name, variables) {
+            Ok(execution) => execution,
+            Err(error) => return Response::from_error(*error),
+        };
+    let (root, selection_set) = match context.root(&operation) {
+        Ok(root) => root,
+        Err(error) => return Response::from_error(*error),
+    };
+
+    let mut fields = IndexMap::new();
+    context.collect_fields(root, &selection_set, &mut HashSet::new(), &mut fields);
+    for field in fields.values().map(|fields| &fields[0]) {
+        match field.name() {
+            Some(name) if !name.text().starts_with("__") => {
+                let message = format!("the field `{}` is not an introspection field", name.text());
+                return Response::from_error(context.error(message, field));
+            }
+            _ => {}
+        }
+    }
+    let data = block_on(context.execute_selection_set(
+        &[selection_set],
+        root,
+        ObjectValue::Sync(&RootResolver),
+        Vec::new(),
+        false,
+    ));
+    context.response(data)
+}
+
+/// The root object of an introspection query. Its only fields are the
+/// meta-fields, which are resolved by the execution itself.
+struct RootResolver;
+
+impl Resolver for RootResolver {
+    fn resolve_field<'a>(
+        &'a self,
+        _field_name: &str,
+        _arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        Ok(ResolvedValue::null())
+    }
+}
+
+/// The types and directives of a schema, as introspection sees them.
+pub(crate) struct SchemaIntrospection<'a> {
+    schema: &'a Schema,
+    /// The types that are referenced by the schema, in the order graphql-js
+    /// collects them in. Built-in types that are never referenced, like
+    /// `ID` in most schemas, are not part of it.
+    types: IndexMap<&'a str, &'a TypeDefinition>,
+    /// The directives defined in the schema, followed by the built-in
+    /// directives.
+    directives: Vec<&'a DirectiveDefinition>,
+}
+
+impl<'a> SchemaIntrospection<'a> {
+    pub(crate) fn new(schema: &'a Schema) -> Self {
+        let mut directives: Vec<_> = schema
+            .directive_definitions()
+            .filter(|directive| !directive.is_built_in())
+            .collect();
+        directives.extend(
+            ["include", "skip", "deprecated", "specifiedBy"]
+                .iter()
+                .filter_map(|name| schema.directive_definition(name))
+                .filter(|directive| directive.is_built_in()),
+        );
+
+        let mut introspection = Self {
+            schema,
+            types: schema
+                .types()
+                .filter(|ty| !ty.is_built_in())
+                .map(|ty| (ty.name(), ty))
+                .collect(),
+            directives,
+        };
+        // Each type is moved to the end when it is collected, after the
+        // types referenced before it, like graphql-js does with a `Set`.
+        let defined: Vec<_> = introspection.types.keys().copied().collect();
+        for name in defined {
+            introspection.types.shift_remove(name);
+            introspection.collect(name);
+        }
+        for operation_type in [
+            OperationType::Query,
+            OperationType::Mutation,
+            OperationType::Subscription,
+        ] {
+            if let Some(root) = schema.root_operation_name(operation_type) {
+                introspection.collect(root);
+            }
+        }
+        for directive in introspection.directives.clone() {
+            for argument in directive.arguments() {
+                introspection.collect(argument.ty().name());
+            }
+        }
+        introspection.collect("__Schema");
+        introspection
+    }
+
+    /// Add the type called `name` and the types it references, depth first.
+    fn collect(&mut self, name: &str) {
+        let schema = self.schema;
+        let ty = match schema.type_by_name(name) {
+            Some(ty) if !self.types.contains_key(name) => ty,
+            _ => return,
+        };
+        self.types.insert(ty.name(), ty);
+        match ty {
+            TypeDefinition::Union(union_ty) => {
+                for member in union_ty.members() {
+                    self.collect(member);
+                }
+            }
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) => {
+                for interface in ty.implements_interfaces() {
+                    self.collect(interface);
+                }
+                for field in ty.fields() {
+                    self.collect(field.ty().name());
+                    for argument in field.arguments() {
+                        self.collect(argument.ty().name());
+                    }
+                }
+            }
+            TypeDefinition::InputObject(input_object) => {
+                for field in input_object.fields() {
+                    self.collect(field.ty().name());
+                }
+            }
+            TypeDefinition::Scalar(_) | TypeDefinition::Enum(_) => {}
+        }
+    }
+
+    /// Get the `__Schema` object.
+    pub(crate) fn schema(&self) -> ResolvedValue<'_> {
+        ResolvedValue::object(SchemaResolver(self))
+    }
+
+    /// Get the `__Type` object of the type called `name`, or `null` if the
+    /// schema does not reference such a type.
+    pub(crate) fn type_by_name(&self, name: &str) -> ResolvedValue<'_> {
+        match self.types.get(name) {
+            Some(ty) => self.ty(IntrospectedType::Named(ty)),
+            None => ResolvedValue::null(),
+        }
+    }
+
+    fn ty(&self, ty: IntrospectedType<'a>) -> ResolvedValue<'_> {
+        ResolvedValue::object(TypeResolver {
+            introspection: self,
+            ty,
+        })
+    }
+
+    /// Get the `__Type` object of a type reference, or `null` if the named
+    /// type is not defined.
+    fn type_reference(&self, ty: &'a Type) -> ResolvedValue<'_> {
+        match ty {
+            Type::Named(name) => match self.schema.type_by_name(name) {
+                Some(ty) => self.ty(IntrospectedType::Named(ty)),
+                None => ResolvedValue::null(),
+            },
+            Type::List(item) => self.ty(IntrospectedType::List(item)),
+            Type::NonNull(ty) => self.ty(IntrospectedType::NonNull(ty)),
+        }
+    }
+
+    fn named_type(&self, name: &str) -> ResolvedValue<'_> {
+        match self.schema.type_by_name(name) {
+            Some(ty) => self.ty(IntrospectedType::Named(ty)),
+            None => ResolvedValue::null(),
+        }
+    }
+
+    fn input_values(
+        &self,
+        values: &'a [InputValueDefinition],
+        arguments: &JsonMap,
+    ) -> ResolvedValue<'_> {
+        let include_deprecated = include_deprecated(arguments);
+        ResolvedValue::list(
+            values
+                .iter()
+                .filter(move |value| {
+                    include_deprecated || deprecation_reason(value.directives()).is_none()
+                })
+                .map(|value| {
+                    ResolvedValue::object(InputValueResolver {
+                        introspection: self,
+                        value,
+                    })
+                }),
+        )
+    }
+}
+
+/// A type as introspection sees it: a named type, or a list or non-null
+/// wrapper around a type reference.
+#[derive(Clone, Copy)]
+enum IntrospectedType<'a> {
+    Named(&'a TypeDefinition),
+    /// A list of items of the given type.
+    List(&'a Type),
+    /// A non-null version of the given type.
+    NonNull(&'a Type),
+}
+
+struct SchemaResolver<'a>(&'a SchemaIntrospection<'a>);
+
+impl Resolver for SchemaResolver<'_> {
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        _arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        let introspection = self.0;
+        let root = |operation_type| match introspection.schema.root_operation_name(operation_type) {
+            Some(root) => introspection.named_type(root),
+            None => ResolvedValue::null(),
+        };
+        Ok(match field_name {
+            "description" => ResolvedValue::leaf(introspection.schema.description()),
+            "types" => ResolvedValue::list(
+                introspection
+                    .types
+                    .values()
+                    .map(|ty| introspection.ty(IntrospectedType::Named(ty))),
+            ),
+            "queryType" => root(OperationType::Query),
+            "mutationType" => root(OperationType::Mutation),
+            "subscriptionType" => root(OperationType::Subscription),
+            "directives" => ResolvedValue::list(introspection.directives.iter().map(|directive| {
+                ResolvedValue::object(DirectiveResolver {
+                    introspection,
+                    directive,
+                })
+            })),
+            _ => ResolvedValue::null(),
+        })
+    }
+}
+
+struct TypeResolver<'a> {
+    introspection: &'a SchemaIntrospection<'a>,
+    ty: IntrospectedType<'a>,
+}
+
+impl Resolver for TypeResolver<'_> {
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        let introspection = self.introspection;
+        let ty = match self.ty {
+            IntrospectedType::Named(ty) => ty,
+            IntrospectedType::List(item) => {
+                return Ok(match field_name {
+                    "kind" => ResolvedValue::leaf("LIST"),
+                    "ofType" => introspection.type_reference(item),
+                    _ => ResolvedValue::null(),
+                })
+            }
+            IntrospectedType::NonNull(ty) => {
+                return Ok(match field_name {
+                    "kind" => ResolvedValue::leaf("NON_NULL"),
+                    "ofType" => introspection.type_reference(ty),
+                    _ => ResolvedValue::null(),
+                })
+            }
+        };
+        Ok(match (field_name, ty) {
+            ("kind", ty) => ResolvedValue::leaf(match ty {
+                TypeDefinition::Scalar(_) => "SCALAR",
+                TypeDefinition::Object(_) => "OBJECT",
+                TypeDefinition::Interface(_) => "INTERFACE",
+                TypeDefinition::Union(_) => "UNION",
+                TypeDefinition::Enum(_) => "ENUM",
+                TypeDefinition::InputObject(_) => "INPUT_OBJECT",
+            }),
+            ("name", ty) => ResolvedValue::leaf(ty.name()),
+            ("description", ty) => ResolvedValue::leaf(ty.description()),
+            ("specifiedByURL", TypeDefinition::Scalar(scalar)) => {
+                let url = scalar
+                    .directives()
+                    .iter()
+                    .find(|directive| directive.name() == "specifiedBy")
+                    .and_then(|directive| match directive.argument("url") {
+                        Some(Value::String(url)) => Some(url.as_str()),
+                        _ => None,
+                    });
+                ResolvedValue::leaf(url)
+            }
+            ("fields", TypeDefinition::Object(_) | TypeDefinition::Interface(_)) => {
+                let include_deprecated = include_deprecated(arguments);
+                ResolvedValue::list(
+                    ty.fields()
+                        .iter()
+                        .filter(|field| {
+                            include_deprecated || deprecation_reason(field.directives()).is_none()
+                        })
+                        .map(|field| {
+                            ResolvedValue::object(FieldResolver {
+                                introspection,
+                                field,
+                            })
+                        }),
+                )
+            }
+            ("interfaces", TypeDefinition::Object(_) | TypeDefinition::Interface(_)) => {
+                ResolvedValue::list(
+                    ty.implements_interfaces()
+                        .iter()
+                        .map(|name| introspection.named_type(name)),
+                )
+            }
+            ("possibleTypes", TypeDefinition::Union(union_ty)) => ResolvedValue::list(
+                union_ty
+                    .members()
+                    .iter()
+                    .map(|name| introspection.named_type(name)),
+            ),
+            ("possibleTypes", TypeDefinition::Interface(interface)) => ResolvedValue::list(
+                introspection
+                    .types
+                    .values()
+                    .filter(|ty| {
+                        matches!(ty, TypeDefinition::Object(object)
+                            if object.implements_interfaces().iter().any(|name| name == interface.name()))
+                    })
+                    .map(|ty| introspection.ty(IntrospectedType::Named(ty))),
+            ),
+            ("enumValues", TypeDefinition::Enum(enum_ty)) => {
+                let include_deprecated = include_deprecated(arguments);
+                ResolvedValue::list(
+                    enum_ty
+                        .values()
+                        .iter()
+                        .filter(|value| {
+                            include_deprecated || deprecation_reason(value.directives()).is_none()
+                        })
+                        .map(|value| ResolvedValue::object(EnumValueResolver(value))),
+                )
+            }
+            ("inputFields", TypeDefinition::InputObject(input_object)) => {
+                introspection.input_values(input_object.fields(), arguments)
+            }
+            _ => ResolvedValue::null(),
+        })
+    }
+}
+
+struct FieldResolver<'a> {
+    introspection: &'a SchemaIntrospection<'a>,
+    field: &'a FieldDefinition,
+}
+
+impl Resolver for FieldResolver<'_> {
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        let field = self.field;
+        Ok(match field_name {
+            "name" => ResolvedValue::leaf(field.name()),
+            "description" => ResolvedValue::leaf(field.description()),
+            "args" => self
+                .introspection
+                .input_values(field.arguments(), arguments),
+            "type" => self.introspection.type_reference(field.ty()),
+            "isDeprecated" => ResolvedValue::leaf(deprecation_reason(field.directives()).is_some()),
+            "deprecationReason" => ResolvedValue::leaf(deprecation_reason(field.directives())),
+            _ => ResolvedValue::null(),
+        })
+    }
+}
+
+struct InputValueResolver<'a> {
+    introspection: &'a SchemaIntrospection<'a>,
+    value: &'a InputValueDefinition,
+}
+
+impl Resolver for InputValueResolver<'_> {
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        _arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        let value = self.value;
+        Ok(match field_name {
+            "name" => ResolvedValue::leaf(value.name()),
+            "description" => ResolvedValue::leaf(value.description()),
+            "type" => self.introspection.type_reference(value.ty()),
+            "defaultValue" => ResolvedValue::leaf(value.default_value().and_then(|default| {
+                let literal = coerce(self.introspection.schema, value.ty(), default, 0)?;
+                Some(literal.to_string())
+            })),
+            "isDeprecated" => ResolvedValue::leaf(deprecation_reason(value.directives()).is_some()),
+            "deprecationReason" => ResolvedValue::leaf(deprecation_reason(value.directives())),
+            _ => ResolvedValue::null(),
+        })
+    }
+}
+
+struct EnumValueResolver<'a>(&'a EnumValueDefinition);
+
+impl Resolver for EnumValueResolver<'_> {
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        _arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        let value = self.0;
+        Ok(match field_name {
+            "name" => ResolvedValue::leaf(value.value()),
+            "description" => ResolvedValue::leaf(value.description()),
+            "isDeprecated" => ResolvedValue::leaf(deprecation_reason(value.directives()).is_some()),
+            "deprecationReason" => ResolvedValue::leaf(deprecation_reason(value.directives())),
+            _ => ResolvedValue::null(),
+        })
+    }
+}
+
+struct DirectiveResolver<'a> {
+    introspection: &'a SchemaIntrospection<'a>,
+    directive: &'a DirectiveDefinition,
+}
+
+impl Resolver for DirectiveResolver<'_> {
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError> {
+        let directive = self.directive;
+        Ok(match field_name {
+            "name" => ResolvedValue::leaf(directive.name()),
+            "description" => ResolvedValue::leaf(directive.description()),
+            "isRepeatable" => ResolvedValue::leaf(directive.is_repeatable()),
+            "locations" => ResolvedValue::list(
+                directive
+                    .locations()
+                    .iter()
+                    .map(|location| ResolvedValue::leaf(location.name())),
+            ),
+            "args" => self
+                .introspection
+                .input_values(directive.arguments(), arguments),
+            _ => ResolvedValue::null(),
+        })
+    }
+}
+
+fn include_deprecated(arguments: &JsonMap) -> bool {
+    arguments.get("includeDeprecated") == Some(&JsonValue::Bool(true))
+}
+
+/// Get the reason an element is deprecated for, if it is. An explicit `null`
+/// reason means that the element is not deprecated.
+fn deprecation_reason(directives: &[Directive]) -> Option<&str> {
+    let deprecated = directives
+        .iter()
+        .find(|directive| directive.name() == "deprecated")?;
+    match deprecated.argument("reason") {
+        None => Some(DEFAULT_DEPRECATION_REASON),
+        Some(Value::String(reason)) => Some(reason),
+        Some(_) => None,
+    }
+}
+
+/// A value in the form graphql-js prints default values in.
+#[derive(Debug, PartialEq)]
+enum Literal {
+    Null,
+    Number(String),
+    String(String),
+    Boolean(bool),
+    Enum(String),
+    List(Vec<Literal>),
+    Object(Vec<(String, Literal)>),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Null => f.write_str("null"),
+            Literal::Number(text) | Literal::Enum(text) => f.write_str(text),
+            Literal::String(string) => {
+                f.write_str("\"")?;
+                for c in string.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\u{8}' => f.write_str("\\b")?,
+                        '\t' => f.write_str("\\t")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\u{c}' => f.write_str("\\f")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\0'..='\u{1f}' | '\u{7f}'..='\u{9f}' => write!(f, "\\u{:04X}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                f.write_str("\"")
+            }
+            Literal::Boolean(boolean) => write!(f, "{}", boolean),
+            Literal::List(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Literal::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Input objects whose fields have default values of the same input object
+/// type could otherwise be expanded forever.
+const MAX_DEPTH: usize = 64;
+
+/// Coerce a default value to its type, and convert it back to a literal.
+/// Returns `None` if the value is not valid for the type, in which case
+/// there is no default value.
+///
+/// This is what graphql-js does with `valueFromAST` when building a schema,
+/// and then with `astFromValue` when introspecting it: input objects get the
+/// default values of their missing fields, single values for list types
+/// become lists, and values are printed the way JavaScript prints them, so
+/// that a `Float` default of `1.0` becomes `1`.
+fn coerce(schema: &Schema, ty: &Type, value: &Value, depth: usize) -> Option<Literal> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    match (ty, value) {
+        (_, Value::Variable(_)) => None,
+        (Type::NonNull(_), Value::Null) => None,
+        (Type::NonNull(ty), value) => coerce(schema, ty, value, depth),
+        (_, Value::Null) => Some(Literal::Null),
+        (Type::List(item), Value::List(values)) => values
+            .iter()
+            .map(|value| coerce(schema, item, value, depth + 1))
+            .collect::<Option<_>>()
+            .map(Literal::List),
+        (Type::List(item), value) => {
+            let value = coerce(schema, item, value, depth + 1)?;
+            Some(Literal::List(vec![value]))
+        }
+        (Type::Named(name), value) => match schema.type_by_name(name)? {
+            TypeDefinition::Scalar(_) => coerce_scalar(name, value),
+            TypeDefinition::Enum(enum_ty) => match value {
+                Value::Enum(name) if enum_ty.value(name).is_some() => {
+                    Some(Literal::Enum(name.clone()))
+                }
+                _ => None,
+            },
+            TypeDefinition::InputObject(input_object) => {
+                let fields = match value {
+                    Value::Object(fields) => fields,
+                    _ => return None,
+                };
+                let mut literal = Vec::new();
+                for field in input_object.fields() {
+                    // The last value wins if a field is provided multiple
+                    // times.
+                    let value = fields
+                        .iter()
+                        .rev()
+                        .find(|(name, value)| {
+                            name == field.name() && !matches!(value, Value::Variable(_))
+                        })
+                        .map(|(_, value)| value);
+                    let value = match value {
+                        Some(value) => coerce(schema, field.ty(), value, depth + 1)?,
+                        None => match field
+                            .default_value()
+                            .and_then(|default| coerce(schema, field.ty(), default, depth + 1))
+                        {
+                            Some(default) => default,
+                            None if field.ty().is_non_null() => return None,
+                            None => continue,
+                        },
+                    };
+                    literal.push((field.name().to_string(), value));
+                }
+                Some(Literal::Object(literal))
+            }
+            _ => None,
+        },
+    }
+}
+
+fn coerce_scalar(name: &str, value: &Value) -> Option<Literal> {
+    match (name, value) {
+        ("Int", Value::Int(text)) => Some(Literal::Number(text.parse::<i32>().ok()?.to_string())),
+        ("Float", Value::Int(text) | Value::Float(text)) => number(text.parse().ok()?),
+        ("String", Value::String(string)) => Some(Literal::String(string.clone())),
+        ("Boolean", Value::Boolean(boolean)) => Some(Literal::Boolean(*boolean)),
+        // IDs that look like integers are printed as integers.
+        ("ID", Value::Int(text)) => Some(Literal::Number(text.clone())),
+        ("ID", Value::String(string)) if is_integer(string) => {
+            Some(Literal::Number(string.clone()))
+        }
+        ("ID", Value::String(string)) => Some(Literal::String(string.clone())),
+        ("Int" | "Float" | "String" | "Boolean" | "ID", _) => None,
+        // Custom scalars keep the JavaScript value of the literal, which
+        // cannot be printed back for lists and objects.
+        (_, Value::Int(text) | Value::Float(text)) => number(text.parse().ok()?),
+        (_, Value::String(string) | Value::Enum(string)) => Some(Literal::String(string.clone())),
+        (_, Value::Boolean(boolean)) => Some(Literal::Boolean(*boolean)),
+        _ => None,
+    }
+}
+
+fn number(value: f64) -> Option<Literal> {
+    value.is_finite().then(|| Literal::Number(js_number(value)))
+}
+
+fn is_integer(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    match digits.as_bytes() {
+        [b'0'] => true,
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    }
+}
+
+/// Format a number like JavaScript's `String(number)` does.
+fn js_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if (1e-6..1e21).contains(&value.abs()) {
+        return value.to_string();
+    }
+    let exponential = format!("{:e}", value);
+    match exponential.split_once('e') {
+        Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+            format!("{}e+{}", mantissa, exponent)
+        }
+        _ => exponential,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use apollo_compiler::{ExecutableDocument, Schema};
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value as JsonValue};
+
+    use crate::{execute_introspection, JsonMap};
+
+    const SCHEMA: &str = r#"
+scalar Date @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT
+type Query {
+  node(id: ID = 1): Node
+  pets(filter: Filter = { name: "a" }, first: Int = 10 @deprecated): [Pet] @deprecated(reason: "Use `node`.")
+}
+interface Node { id: ID! }
+interface Pet implements Node { id: ID! name: String }
+type Cat implements Pet & Node @tag(name: "cat") { id: ID! name: String lives: Int }
+union Any = Cat
+enum Color { RED GREEN @deprecated }
+input Filter { name: String! color: Color = RED born: Date old: Int @deprecated(reason: null) }
+"#;
+
+    fn execute(query: &str) -> JsonValue {
+        execute_with(query, None, JsonMap::new())
+    }
+
+    fn execute_with(query: &str, operation_name: Option<&str>, variables: JsonMap) -> JsonValue {
+        let schema = Schema::parse(SCHEMA);
+        assert_eq!(schema.validate(), []);
+        let document = ExecutableDocument::parse("query.graphql", query);
+        execute_introspection(&schema, &document, operation_name, &variables).to_json()
+    }
+
+    fn default_value(input_type: &str, default: &str) -> JsonValue {
+        let schema = Schema::parse(&format!(
+            r#"
+type Query {{ field(arg: {} = {}): String }}
+input Point {{ x: Float! y: Float = 0 label: String }}
+enum Color {{ RED }}
+scalar Json
+"#,
+            input_type, default
+        ));
+        let document = ExecutableDocument::parse(
+            "query.graphql",
+            r#"{ __type(name: "Query") { fields { args { defaultValue } } } }"#,
+        );
+        let response = execute_introspection(&schema, &document, None, &JsonMap::new()).to_json();
+        response["data"]["__type"]["fields"][0]["args"][0]["defaultValue"].clone()
+    }
+
+    #[test]
+    fn it_orders_types_like_graphql_js() {
+        let response = execute("{ __schema { types { name } } }");
+        let types: Vec<_> = response["data"]["__schema"]["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                "Date",
+                "Query",
+                "ID",
+                "Int",
+                "Node",
+                "Pet",
+                "String",
+                "Cat",
+                "Any",
+                "Color",
+                "Filter",
+                "Boolean",
+                "__Schema",
+                "__Type",
+                "__TypeKind",
+                "__Field",
+                "__InputValue",
+                "__EnumValue",
+                "__Directive",
+                "__DirectiveLocation",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_introspects_types() {
+        assert_eq!(
+            execute(
+                r#"
+{
+  __typename
+  pet: __type(name: "Pet") {
+    kind name interfaces { name } possibleTypes { name }
+    fields { name type { kind name ofType { kind name } } }
+  }
+  any: __type(name: "Any") { kind possibleTypes { name } fields { name } }
+  date: __type(name: "Date") { kind specifiedByURL }
+  missing: __type(name: "Float") { name }
+}
+"#
+            ),
+            json!({
+                "data": {
+                    "__typename": "Query",
+                    "pet": {
+                        "kind": "INTERFACE",
+                        "name": "Pet",
+                        "interfaces": [{ "name": "Node" }],
+                        "possibleTypes": [{ "name": "Cat" }],
+                        "fields": [
+                            {
+                                "name": "id",
+                                "type": {
+                                    "kind": "NON_NULL",
+                                    "name": null,
+                                    "ofType": { "kind": "SCALAR", "name": "ID" }
+                                }
+                            },
+                            {
+                                "name": "name",
+                                "type": { "kind": "SCALAR", "name": "String", "ofType": null }
+                            }
+                        ]
+                    },
+                    "any": { "kind": "UNION", "possibleTypes": [{ "name": "Cat" }], "fields": null },
+                    "date": {
+                        "kind": "SCALAR",
+                        "specifiedByURL": "https://tools.ietf.org/html/rfc3339"
+                    },
+                    "missing": null
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn it_filters_deprecated_elements() {
+        let query = r#"
+query($all: Boolean = false) {
+  query: __type(name: "Query") {
+    fields(includeDeprecated: $all) {
+      name isDeprecated deprecationReason
+      args(includeDeprecated: $all) { name isDeprecated deprecationReason }
+    }
+  }
+  color: __type(name: "Color") { enumValues(includeDeprecated: $all) { name isDeprecated } }
+  filter: __type(name: "Filter") { inputFields(includeDeprecated: $all) { name } }
+}
+"#;
+        assert_eq!(
+            execute(query),
+            json!({
+                "data": {
+                    "query": {
+                        "fields": [{
+                            "name": "node",
+                            "isDeprecated": false,
+                            "deprecationReason": null,
+                            "args": [{ "name": "id", "isDeprecated": false, "deprecationReason": null }]
+                        }]
+                    },
+                    "color": { "enumValues": [{ "name": "RED", "isDeprecated": false }] },
+                    "filter": {
+                        "inputFields": [{ "name": "name" }, { "name": "color" }, { "name": "born" }, { "name": "old" }]
+                    }
+                }
+            })
+        );
+
+        let mut variables = JsonMap::new();
+        variables.insert("all".to_string(), json!(true));
+        let response = execute_with(query, None, variables);
+        assert_eq!(
+            response["data"]["query"]["fields"][1],
+            json!({
+                "name": "pets",
+                "isDeprecated": true,
+                "deprecationReason": "Use `node`.",
+                "args": [
+                    { "name": "filter", "isDeprecated": false, "deprecationReason": null },
+                    { "name": "first", "isDeprecated": true, "deprecationReason": "No longer supported" }
+                ]
+            })
+        );
+        assert_eq!(
+            response["data"]["color"]["enumValues"][1],
+            json!({ "name": "GREEN", "isDeprecated": true })
+        );
+    }
+
+    #[test]
+    fn it_introspects_directives() {
+        let response = execute(
+            r#"
+{
+  __schema {
+    queryType { name } mutationType { name }
+    directives { name isRepeatable locations args { name type { name ofType { name } } defaultValue } }
+  }
+}
+"#,
+        );
+        let schema = &response["data"]["__schema"];
+        assert_eq!(schema["queryType"], json!({ "name": "Query" }));
+        assert_eq!(schema["mutationType"], JsonValue::Null);
+        let directives: Vec<_> = schema["directives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|directive| directive["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            directives,
+            ["tag", "include", "skip", "deprecated", "specifiedBy"]
+        );
+        assert_eq!(
+            schema["directives"][0],
+            json!({
+                "name": "tag",
+                "isRepeatable": true,
+                "locations": ["FIELD_DEFINITION", "OBJECT"],
+                "args": [{
+                    "name": "name",
+                    "type": { "name": null, "ofType": { "name": "String" } },
+                    "defaultValue": null
+                }]
+            })
+        );
+        assert_eq!(
+            schema["directives"][3]["args"][0]["defaultValue"],
+            json!("\"No longer supported\"")
+        );
+    }
+
+    #[test]
+    fn it_prints_default_values_like_graphql_js() {
+        assert_eq!(default_value("Int", "-0"), json!("0"));
+        assert_eq!(default_value("Int", "3000000000"), JsonValue::Null);
+        assert_eq!(default_value("Float", "1.0"), json!("1"));
+        assert_eq!(default_value("Float", "1.5e-7"), json!("1.5e-7"));
+        assert_eq!(default_value("Float", "1e21"), json!("1e+21"));
+        assert_eq!(default_value("Float", "0.000001"), json!("0.000001"));
+        assert_eq!(default_value("ID", "\"12\""), json!("12"));
+        assert_eq!(default_value("ID", "\"012\""), json!("\"012\""));
+        assert_eq!(
+            default_value("String", r#""a \"b\" \\ \n \u0001 \u007F é""#),
+            json!(r#""a \"b\" \\ \n \u0001 \u007F é""#)
+        );
+        assert_eq!(default_value("String", "null"), json!("null"));
+        assert_eq!(default_value("String!", "null"), JsonValue::Null);
+        assert_eq!(default_value("[Int]", "1"), json!("[1]"));
+        assert_eq!(default_value("[Int!]", "[1, null]"), JsonValue::Null);
+        assert_eq!(default_value("[Color]", "[RED, RED]"), json!("[RED, RED]"));
+        assert_eq!(
+            default_value("Point", "{ label: \"o\", x: 1 }"),
+            json!("{x: 1, y: 0, label: \"o\"}")
+        );
+        assert_eq!(default_value("Point", "{ y: 1 }"), JsonValue::Null);
+        assert_eq!(default_value("Json", "RED"), json!("\"RED\""));
+        assert_eq!(default_value("Json", "{ a: 1 }"), JsonValue::Null);
+    }
+
+    #[test]
+    fn it_reports_request_errors() {
+        assert_eq!(
+            execute("{ __typename node(id: 1) { id } }"),
+            json!({
+                "errors": [{
+                    "message": "the field `node` is not an introspection field",
+                    "locations": [{ "line": 1, "column": 14 }],
+                }]
+            })
+        );
+        assert_eq!(
+            execute("query A { __typename } query B { __typename }"),
+            json!({
+                "errors": [{
+                    "message": "an operation name is required when the document contains multiple operations"
+                }]
+            })
+        );
+        assert_eq!(
+            execute_with("query A { __typename }", Some("B"), JsonMap::new()),
+            json!({ "errors": [{ "message": "the operation `B` is not defined" }] })
+        );
+        assert_eq!(
+            execute("mutation { __typename }"),
+            json!({
+                "errors": [{
+                    "message": "the schema does not define a mutation root operation type",
+                    "locations": [{ "line": 1, "column": 1 }],
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn it_introspects_the_schema_description() {
+        let schema = Schema::parse(
+            r#"
+"""
+The API of the pet shop.
+"""
+schema { query: Query }
+type Query { a: Int }
+"#,
+        );
+        let document = ExecutableDocument::parse("query.graphql", "{ __schema { description } }");
+        let response = execute_introspection(&schema, &document, None, &JsonMap::new());
+        assert_eq!(
+            response.to_json(),
+            json!({ "data": { "__schema": { "description": "The API of the pet shop." } } })
+        );
+    }
+
+    #[test]
+    fn it_executes_the_introspection_query() {
+        let response = execute(
+            r#"
+query IntrospectionQuery {
+  __schema {
+    description
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name description isRepeatable locations
+      args(includeDeprecated: true) { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind name description specifiedByURL
+  fields(includeDeprecated: true) {
+    name description
+    args(includeDeprecated: true) { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated deprecationReason
+  }
+  inputFields(includeDeprecated: true) { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) { name description isDeprecated deprecationReason }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name description type { ...TypeRef } defaultValue isDeprecated deprecationReason
+}
+
+fragment TypeRef on __Type {
+  kind name
+  ofType { kind name ofType { kind name ofType { kind name } } }
+}
+"#,
+        );
+        let schema = &response["data"]["__schema"];
+        assert_eq!(schema["description"], JsonValue::Null);
+        assert_eq!(schema["types"].as_array().unwrap().len(), 20);
+        assert_eq!(
+            schema["types"][1]["fields"][1]["args"][0],
+            json!({
+                "name": "filter",
+                "description": null,
+                "type": { "kind": "INPUT_OBJECT", "name": "Filter", "ofType": null },
+                "defaultValue": "{name: \"a\", color: RED}",
+                "isDeprecated": false,
+                "deprecationReason": null
+            })
+        );
+        assert_eq!(
+            schema["types"][7],
+            json!({
+                "kind": "OBJECT",
+                "name": "Cat",
+                "description": null,
+                "specifiedByURL": null,
+                "fields": [
+                    {
+                        "name": "id",
+                        "description": null,
+                        "args": [],
+                        "type": {
+                            "kind": "NON_NULL",
+                            "name": null,
+                            "ofType": { "kind": "SCALAR", "name": "ID", "ofType": null }
+                        },
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    },
+                    {
+                        "name": "name",
+                        "description": null,
+                        "args": [],
+                        "type": { "kind": "SCALAR", "name": "String", "ofType": null },
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    },
+                    {
+                        "name": "lives",
+                        "description": null,
+                        "args": [],
+                        "type": { "kind": "SCALAR", "name": "Int", "ofType": null },
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    }
+                ],
+                "inputFields": null,
+                "interfaces": [
+                    { "kind": "INTERFACE", "name": "Pet", "ofType": null },
+                    { "kind": "INTERFACE", "name": "Node", "ofType": null }
+                ],
+                "enumValues": null,
+                "possibleTypes": null
+            })
+        );
+        assert_eq!(
+            schema["directives"][4],
+            json!({
+                "name": "specifiedBy",
+                "description": "Exposes a URL that specifies the behavior of this scalar.",
+                "isRepeatable": false,
+                "locations": ["SCALAR"],
+                "args": [{
+                    "name": "url",
+                    "description": "The URL that specifies the behavior of this scalar.",
+                    "type": {
+                        "kind": "NON_NULL",
+                        "name": null,
+                        "ofType": { "kind": "SCALAR", "name": "String", "ofType": null }
+                    },
+                    "defaultValue": null,
+                    "isDeprecated": false,
+                    "deprecationReason": null
+                }]
+            })
+        );
+    }
+}
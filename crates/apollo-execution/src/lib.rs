@@ -0,0 +1,203 @@
+//! <div align="center">
+//!   <h1><code>apollo-execution</code></h1>
+//!
+//!   <p>
+//!     <strong>Execution of GraphQL operations against a schema.</strong>
+//!   </p>
+//!   <p>
+//!     <a href="https://crates.io/crates/apollo-execution">
+//!         <img src="https://img.shields.io/crates/v/apollo-execution.svg?style=flat-square" alt="Crates.io" />
+//!     </a>
+//!     <a href="https://crates.io/crates/apollo-execution">
+//!         <img src="https://img.shields.io/crates/d/apollo-execution.svg?style=flat-square" alt="Download" />
+//!     </a>
+//!     <a href="https://docs.rs/apollo-execution/">
+//!         <img src="https://img.shields.io/static/v1?label=docs&message=apollo-execution&color=blue&style=flat-square" alt="docs.rs docs" />
+//!     </a>
+//!   </p>
+//! </div>
+//!
+//! `apollo-execution` executes GraphQL operations against a schema built by
+//! [`apollo-compiler`], and produces JSON responses. It lets you run GraphQL
+//! in-process, in services and in test suites.
+//!
+//! ## Features
+//! * Execution of queries and mutations with [`execute`], with the values of
+//!   fields coming from your own [`Resolver`]s, or [`AsyncResolver`]s with
+//!   [`execute_async`]
+//! * Concurrent resolution of the fields of queries, and serial execution of
+//!   the root fields of mutations
+//! * Subscriptions with [`subscribe`], which turns the stream of values of
+//!   the subscribed root field, from your [`SubscriptionResolver`], into a
+//!   stream of responses
+//! * Interfaces and unions, whose object types are given by the
+//!   `resolve_type` hook of resolvers
+//! * Completion of lists and non-null values, with `null` propagating from
+//!   a non-null field to its nearest nullable parent
+//! * [`Response`]s that serialize to JSON in the order of the spec, with
+//!   errors that have the locations of their fields in the document, their
+//!   paths in the response and extensions
+//! * Execution of introspection queries, like the `IntrospectionQuery` of
+//!   GraphQL tools, against a schema parsed from SDL, with
+//!   [`execute_introspection`]
+//! * Introspection results that are the same as the ones of graphql-js for
+//!   the same SDL, including the order of types and the printing of default
+//!   values
+//! * `includeDeprecated` on fields, arguments, input fields and enum values,
+//!   `specifiedByURL`, `isRepeatable` and the interfaces of interfaces
+//! * Fragments, `@skip` and `@include`, and variables with default values
+//! * Coercion of variable and argument values to their input types, and of
+//!   the values of scalar and enum fields to the types of their fields
+//!
+//! ## Getting started
+//! Add this to your `Cargo.toml` to start using `apollo-execution`:
+//! ```toml
+//! # Just an example, change to the necessary package version.
+//! [dependencies]
+//! apollo-execution = "0.1.0"
+//! ```
+//!
+//! Or using [cargo-edit]:
+//! ```bash
+//! cargo add apollo-execution
+//! ```
+//!
+//! ## Examples
+//! ### Executing a query
+//! ```rust
+//! use apollo_compiler::{ExecutableDocument, Schema};
+//! use apollo_execution::{execute, FieldError, JsonMap, ResolvedValue, Resolver};
+//! use serde_json::json;
+//!
+//! struct Query;
+//!
+//! impl Resolver for Query {
+//!     fn resolve_field<'a>(
+//!         &'a self,
+//!         field_name: &str,
+//!         _arguments: &JsonMap,
+//!     ) -> Result<ResolvedValue<'a>, FieldError> {
+//!         match field_name {
+//!             "pets" => Ok(ResolvedValue::list([ResolvedValue::object(Cat)])),
+//!             _ => Ok(ResolvedValue::null()),
+//!         }
+//!     }
+//! }
+//!
+//! struct Cat;
+//!
+//! impl Resolver for Cat {
+//!     fn resolve_type(&self) -> Option<&str> {
+//!         Some("Cat")
+//!     }
+//!
+//!     fn resolve_field<'a>(
+//!         &'a self,
+//!         field_name: &str,
+//!         _arguments: &JsonMap,
+//!     ) -> Result<ResolvedValue<'a>, FieldError> {
+//!         match field_name {
+//!             "name" => Ok(ResolvedValue::leaf("Tom")),
+//!             "lives" => Ok(ResolvedValue::leaf(9)),
+//!             _ => Ok(ResolvedValue::null()),
+//!         }
+//!     }
+//! }
+//!
+//! let schema = Schema::parse(
+//!     r#"
+//! type Query {
+//!   pets: [Pet]
+//! }
+//!
+//! interface Pet {
+//!   name: String
+//! }
+//!
+//! type Cat implements Pet {
+//!   name: String
+//!   lives: Int
+//! }
+//! "#,
+//! );
+//! let document = ExecutableDocument::parse(
+//!     "query.graphql",
+//!     "{ pets { __typename name ... on Cat { lives } } }",
+//! );
+//!
+//! let response = execute(&schema, &document, None, &JsonMap::new(), &Query);
+//! assert_eq!(
+//!     response.to_json(),
+//!     json!({
+//!         "data": {
+//!             "pets": [{ "__typename": "Cat", "name": "Tom", "lives": 9 }]
+//!         }
+//!     })
+//! );
+//! ```
+//!
+//! ### Executing an introspection query
+//! ```rust
+//! use apollo_compiler::{ExecutableDocument, Schema};
+//! use apollo_execution::{execute_introspection, JsonMap};
+//! use serde_json::json;
+//!
+//! let schema = Schema::parse(
+//!     r#"
+//! type Query {
+//!   pets: [Pet]
+//! }
+//!
+//! interface Pet {
+//!   name: String
+//! }
+//!
+//! type Cat implements Pet {
+//!   name: String
+//!   lives: Int
+//! }
+//! "#,
+//! );
+//! let document = ExecutableDocument::parse(
+//!     "query.graphql",
+//!     r#"{ __type(name: "Pet") { kind possibleTypes { name } } }"#,
+//! );
+//!
+//! let response = execute_introspection(&schema, &document, None, &JsonMap::new());
+//! assert_eq!(
+//!     response.to_json(),
+//!     json!({
+//!         "data": {
+//!             "__type": { "kind": "INTERFACE", "possibleTypes": [{ "name": "Cat" }] }
+//!         }
+//!     })
+//! );
+//! ```
+//!
+//! ## License
+//! Licensed under either of
+//!
+//! - Apache License, Version 2.0 ([LICENSE-APACHE] or <https://www.apache.org/licenses/LICENSE-2.0>)
+//! - MIT license ([LICENSE-MIT] or <https://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! [`apollo-compiler`]: https://docs.rs/apollo-compiler
+//! [cargo-edit]: https://github.com/killercup/cargo-edit
+//! [LICENSE-APACHE]: https://github.com/apollographql/apollo-rs/blob/main/crates/apollo-execution/LICENSE-APACHE
+//! [LICENSE-MIT]:https://github.com/apollographql/apollo-rs/blob/main/crates/apollo-execution/LICENSE-MIT
+
+mod execution;
+mod introspection;
+mod resolver;
+mod response;
+mod subscription;
+
+pub use crate::execution::{execute, execute_async};
+pub use crate::introspection::execute_introspection;
+pub use crate::resolver::{
+    AsyncResolver, BoxFuture, BoxStream, FieldError, JsonMap, ResolvedValue, Resolver,
+    SubscriptionResolver,
+};
+pub use crate::response::{GraphQLError, Location, PathSegment, Response};
+pub use crate::subscription::subscribe;
@@ -0,0 +1,243 @@
+//! The values that selection sets are executed on.
+
+use std::{future::Future, pin::Pin};
+
+use futures::Stream;
+use serde_json::{Map, Value as JsonValue};
+
+/// A JSON object, with its keys in insertion order.
+pub type JsonMap = Map<String, JsonValue>;
+
+/// A boxed future returned by [`AsyncResolver`]s. It does not need to be
+/// `Send`, as operations are executed in the task that awaits them.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A boxed stream returned by [`SubscriptionResolver`]s. Like [`BoxFuture`],
+/// it does not need to be `Send`.
+pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + 'a>>;
+
+/// An object value whose fields can be selected, with the values of its
+/// fields available right away.
+///
+/// ## Example
+/// ```rust
+/// use apollo_execution::{FieldError, JsonMap, ResolvedValue, Resolver};
+///
+/// struct Cat {
+///     name: String,
+///     lives: i32,
+/// }
+///
+/// impl Resolver for Cat {
+///     fn resolve_type(&self) -> Option<&str> {
+///         Some("Cat")
+///     }
+///
+///     fn resolve_field<'a>(
+///         &'a self,
+///         field_name: &str,
+///         _arguments: &JsonMap,
+///     ) -> Result<ResolvedValue<'a>, FieldError> {
+///         match field_name {
+///             "name" => Ok(ResolvedValue::leaf(self.name.as_str())),
+///             "lives" => Ok(ResolvedValue::leaf(self.lives)),
+///             _ => Err(FieldError::new(format!("unknown field `{}`", field_name))),
+///         }
+///     }
+/// }
+/// ```
+pub trait Resolver {
+    /// Get the name of the object type of this value, if it is known.
+    ///
+    /// This is the `resolve_type` hook of abstract types: when a field's
+    /// type is an interface or a union, it decides which of the possible
+    /// object types the value has. It is not used for fields of an object
+    /// type, so it only needs to be implemented by values that are returned
+    /// for fields of abstract types.
+    fn resolve_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the value of the field called `field_name`, given its argument
+    /// values. Arguments that are not provided have their default values,
+    /// if any.
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &str,
+        arguments: &JsonMap,
+    ) -> Result<ResolvedValue<'a>, FieldError>;
+}
+
+/// An object value whose fields can be selected, with the values of its
+/// fields computed asynchronously.
+///
+/// The fields of a selection set are resolved concurrently, except for the
+/// root fields of mutations, which are resolved one after the other.
+///
+/// ## Example
+/// ```rust
+/// use apollo_execution::{AsyncResolver, BoxFuture, FieldError, JsonMap, ResolvedValue};
+///
+/// struct Query;
+///
+/// impl AsyncResolver for Query {
+///     fn resolve_field<'a>(
+///         &'a self,
+///         field_name: &'a str,
+///         _arguments: &'a JsonMap,
+///     ) -> BoxFuture<'a, Result<ResolvedValue<'a>, FieldError>> {
+///         Box::pin(async move {
+///             match field_name {
+///                 "greeting" => Ok(ResolvedValue::leaf("hello")),
+///                 _ => Ok(ResolvedValue::null()),
+///             }
+///         })
+///     }
+/// }
+/// ```
+pub trait AsyncResolver {
+    /// Get the name of the object type of this value, if it is known. See
+    /// [`Resolver::resolve_type`].
+    fn resolve_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the value of the field called `field_name`, given its argument
+    /// values. Arguments that are not provided have their default values,
+    /// if any.
+    fn resolve_field<'a>(
+        &'a self,
+        field_name: &'a str,
+        arguments: &'a JsonMap,
+    ) -> BoxFuture<'a, Result<ResolvedValue<'a>, FieldError>>;
+}
+
+/// The root object of subscriptions, whose fields are streams of values.
+///
+/// ## Example
+/// ```rust
+/// use apollo_execution::{BoxStream, FieldError, JsonMap, ResolvedValue, SubscriptionResolver};
+/// use futures::stream;
+///
+/// struct Subscription;
+///
+/// impl SubscriptionResolver for Subscription {
+///     fn subscribe_field<'a>(
+///         &'a self,
+///         field_name: &str,
+///         arguments: &JsonMap,
+///     ) -> Result<BoxStream<'a, Result<ResolvedValue<'a>, FieldError>>, FieldError> {
+///         match field_name {
+///             "countdown" => {
+///                 let from = arguments["from"].as_i64().unwrap();
+///                 let values = (0..=from).rev().map(|n| Ok(ResolvedValue::leaf(n)));
+///                 Ok(Box::pin(stream::iter(values)))
+///             }
+///             _ => Err(FieldError::new(format!("cannot subscribe to `{}`", field_name))),
+///         }
+///     }
+/// }
+/// ```
+pub trait SubscriptionResolver {
+    /// Get the stream of the values of the field called `field_name`, given
+    /// its argument values. Each value of the stream is an event of the
+    /// subscription, for which the field is completed into a response.
+    ///
+    /// Values that are errors become field errors in their responses. The
+    /// subscription ends with the stream, and the stream is dropped when the
+    /// subscription is.
+    fn subscribe_field<'a>(
+        &'a self,
+        field_name: &str,
+        arguments: &JsonMap,
+    ) -> Result<BoxStream<'a, Result<ResolvedValue<'a>, FieldError>>, FieldError>;
+}
+
+/// The value of a field, before it is completed according to the type of
+/// the field.
+pub enum ResolvedValue<'a> {
+    /// A scalar or enum value, or `null`. A JSON array is a list of leaf
+    /// values.
+    Leaf(JsonValue),
+    /// An object, whose fields are selected by the sub-selections of the
+    /// field.
+    Object(Box<dyn Resolver + 'a>),
+    /// An object whose fields are resolved asynchronously.
+    AsyncObject(Box<dyn AsyncResolver + 'a>),
+    /// A list of values.
+    List(Vec<ResolvedValue<'a>>),
+}
+
+impl<'a> ResolvedValue<'a> {
+    /// Create a `null` value.
+    pub fn null() -> Self {
+        Self::Leaf(JsonValue::Null)
+    }
+
+    /// Create a scalar or enum value.
+    pub fn leaf(value: impl Into<JsonValue>) -> Self {
+        Self::Leaf(value.into())
+    }
+
+    /// Create an object value.
+    pub fn object(object: impl Resolver + 'a) -> Self {
+        Self::Object(Box::new(object))
+    }
+
+    /// Create an object value whose fields are resolved asynchronously.
+    pub fn async_object(object: impl AsyncResolver + 'a) -> Self {
+        Self::AsyncObject(Box::new(object))
+    }
+
+    /// Create a list value from its items.
+    pub fn list(items: impl IntoIterator<Item = ResolvedValue<'a>>) -> Self {
+        Self::List(items.into_iter().collect())
+    }
+
+    pub(crate) fn is_null(&self) -> bool {
+        matches!(self, Self::Leaf(JsonValue::Null))
+    }
+}
+
+/// An error raised while resolving a field. The field's value becomes
+/// `null`, and the error is added to the response.
+///
+/// ## Example
+/// ```rust
+/// use apollo_execution::FieldError;
+///
+/// let error = FieldError::new("the pet is not found").extension("code", "NOT_FOUND");
+/// assert_eq!(error.message(), "the pet is not found");
+/// assert_eq!(error.extensions()["code"], "NOT_FOUND");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    message: String,
+    extensions: JsonMap,
+}
+
+impl FieldError {
+    /// Create an error with a message for the response.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            extensions: JsonMap::new(),
+        }
+    }
+
+    /// Add an entry to the `extensions` of the error in the response.
+    pub fn extension(mut self, key: impl Into<String>, value: impl Into<JsonValue>) -> Self {
+        self.extensions.insert(key.into(), value.into());
+        self
+    }
+
+    /// Get the error's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the `extensions` of the error in the response.
+    pub fn extensions(&self) -> &JsonMap {
+        &self.extensions
+    }
+}
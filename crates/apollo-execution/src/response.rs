@@ -0,0 +1,171 @@
+//! The response to a GraphQL request.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Response>.
+
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::resolver::JsonMap;
+
+/// The response to the execution of an operation.
+///
+/// It serializes to JSON in the order of the spec, with `errors` first when
+/// there are any, then `data`, then `extensions` when there are any. The
+/// keys of `data` are in the order of the operation's selections.
+///
+/// ## Example
+/// ```rust
+/// use apollo_execution::{GraphQLError, Response};
+/// use serde_json::json;
+///
+/// let response = Response {
+///     data: Some(json!({ "me": null })),
+///     errors: vec![GraphQLError::new("the field is broken")],
+///     extensions: Default::default(),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&response).unwrap(),
+///     r#"{"errors":[{"message":"the field is broken"}],"data":{"me":null}}"#
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Response {
+    /// The errors raised during the request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<GraphQLError>,
+    /// The result of the execution of the operation. This is `None` if
+    /// the request failed before the execution started, and `null` if a
+    /// field error propagated up to the root of the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<JsonValue>,
+    /// Additional entries of the response, for implementors to extend the
+    /// protocol.
+    #[serde(skip_serializing_if = "JsonMap::is_empty")]
+    pub extensions: JsonMap,
+}
+
+impl Response {
+    /// Create the response to a request that failed before the execution
+    /// of the operation started.
+    pub fn from_error(error: GraphQLError) -> Self {
+        Self {
+            errors: vec![error],
+            data: None,
+            extensions: JsonMap::new(),
+        }
+    }
+
+    /// Convert the response to JSON.
+    pub fn to_json(&self) -> JsonValue {
+        serde_json::to_value(self).expect("responses can always be serialized")
+    }
+}
+
+/// An error in a response, raised by a request or by a field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphQLError {
+    /// The description of the error.
+    pub message: String,
+    /// The locations in the document of the fields or operation the error
+    /// is about.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Location>,
+    /// The path in `data` of the field the error was raised for, for field
+    /// errors.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<PathSegment>,
+    /// Additional entries of the error, like an error code.
+    #[serde(skip_serializing_if = "JsonMap::is_empty")]
+    pub extensions: JsonMap,
+}
+
+impl GraphQLError {
+    /// Create an error with a message, without locations, path or
+    /// extensions.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            locations: Vec::new(),
+            path: Vec::new(),
+            extensions: JsonMap::new(),
+        }
+    }
+}
+
+impl fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for GraphQLError {}
+
+/// A position in a GraphQL document. Lines and columns start at 1, and
+/// columns are counted in UTF-16 code units, like in graphql-js.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A segment of the path to a field in a response: the response name of a
+/// field, or the index of an item in a list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl From<&str> for PathSegment {
+    fn from(name: &str) -> Self {
+        Self::Field(name.to_string())
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{GraphQLError, Location, Response};
+    use crate::JsonMap;
+
+    #[test]
+    fn it_serializes_in_spec_order() {
+        let mut extensions = JsonMap::new();
+        extensions.insert("code".to_string(), json!("BROKEN"));
+        let response = Response {
+            data: Some(json!({ "b": 1, "a": [null] })),
+            errors: vec![GraphQLError {
+                message: "the field is broken".to_string(),
+                locations: vec![Location { line: 1, column: 7 }],
+                path: vec!["a".into(), 0.into()],
+                extensions: extensions.clone(),
+            }],
+            extensions,
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            concat!(
+                r#"{"errors":[{"message":"the field is broken","locations":[{"line":1,"column":7}],"#,
+                r#""path":["a",0],"extensions":{"code":"BROKEN"}}],"#,
+                r#""data":{"b":1,"a":[null]},"extensions":{"code":"BROKEN"}}"#
+            )
+        );
+
+        let response = Response::from_error(GraphQLError::new("the operation is invalid"));
+        assert_eq!(
+            response.to_json(),
+            json!({ "errors": [{ "message": "the operation is invalid" }] })
+        );
+    }
+}
@@ -0,0 +1,405 @@
+//! Execution of subscriptions.
+//!
+//! See <https://spec.graphql.org/October2021/#sec-Subscription>.
+
+use std::collections::HashSet;
+
+use apollo_compiler::{
+    hir::{FieldDefinition, OperationType},
+    ExecutableDocument, Schema,
+};
+use apollo_parser::ast;
+use futures::{future, stream, StreamExt};
+use indexmap::IndexMap;
+
+use crate::{
+    execution::{operation_type, ExecutionContext},
+    resolver::{BoxStream, FieldError, JsonMap, ResolvedValue, SubscriptionResolver},
+    response::{GraphQLError, PathSegment, Response},
+};
+
+/// Subscribe to a subscription operation, and get the stream of its
+/// responses.
+///
+/// The document should be valid, see [`apollo_compiler::validate`]. The
+/// operation's root field is subscribed to with `root`, and each value of
+/// the field's stream is completed into a response, as [`execute`] does
+/// for queries. The stream of responses ends when the field's stream does,
+/// and dropping it drops the field's stream.
+///
+/// If the subscription cannot be created, like when the operation selects
+/// more than one root field or the field cannot be subscribed to, the stream
+/// only yields a response with the error.
+///
+/// [`execute`]: crate::execute
+///
+/// ## Example
+/// ```rust
+/// use apollo_compiler::{ExecutableDocument, Schema};
+/// use apollo_execution::{
+///     subscribe, BoxStream, FieldError, JsonMap, ResolvedValue, SubscriptionResolver,
+/// };
+/// use futures::{executor::block_on, stream, StreamExt};
+/// use serde_json::json;
+///
+/// struct Subscription;
+///
+/// impl SubscriptionResolver for Subscription {
+///     fn subscribe_field<'a>(
+///         &'a self,
+///         _field_name: &str,
+///         arguments: &JsonMap,
+///     ) -> Result<BoxStream<'a, Result<ResolvedValue<'a>, FieldError>>, FieldError> {
+///         let from = arguments["from"].as_i64().unwrap();
+///         let values = (0..=from).rev().map(|n| Ok(ResolvedValue::leaf(n)));
+///         Ok(Box::pin(stream::iter(values)))
+///     }
+/// }
+///
+/// let schema = Schema::parse(
+///     "type Query { a: Int } type Subscription { countdown(from: Int!): Int! }",
+/// );
+/// let document = ExecutableDocument::parse("query.graphql", "subscription { countdown(from: 2) }");
+///
+/// let responses = subscribe(&schema, &document, None, &JsonMap::new(), &Subscription);
+/// let responses: Vec<_> = block_on(responses.map(|response| response.to_json()).collect());
+/// assert_eq!(
+///     responses,
+///     [
+///         json!({ "data": { "countdown": 2 } }),
+///         json!({ "data": { "countdown": 1 } }),
+///         json!({ "data": { "countdown": 0 } }),
+///     ]
+/// );
+/// ```
+pub fn subscribe<'a>(
+    schema: &'a Schema,
+    document: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &JsonMap,
+    root: &'a dyn SubscriptionResolver,
+) -> BoxStream<'a, Response> {
+    match Subscription::new(schema, document, operation_name, variables, root) {
+        Ok(subscription) => Box::pin(stream::unfold(
+            subscription,
+            |mut subscription| async move {
+                let event = subscription.source.next().await?;
+                let response = subscription.execute_event(event).await;
+                Some((response, subscription))
+            },
+        )),
+        Err(error) => Box::pin(stream::once(future::ready(Response::from_error(*error)))),
+    }
+}
+
+/// A subscription to the root field of an operation.
+struct Subscription<'a> {
+    context: ExecutionContext<'a>,
+    root_type: &'a str,
+    response_name: String,
+    /// The selections of the root field, with the same response name.
+    fields: Vec<ast::Field>,
+    definition: &'a FieldDefinition,
+    /// The stream of the values of the root field.
+    source: BoxStream<'a, Result<ResolvedValue<'a>, FieldError>>,
+}
+
+impl<'a> Subscription<'a> {
+    /// Subscribe to the root field of an operation, which is the spec's
+    /// `CreateSourceEventStream`.
+    fn new(
+        schema: &'a Schema,
+        document: &'a ExecutableDocument,
+        operation_name: Option<&str>,
+        variables: &JsonMap,
+        root: &'a dyn SubscriptionResolver,
+    ) -> Result<Self, Box<GraphQLError>> {
+        let (context, operation) =
+            ExecutionContext::new(schema, document, operation_name, variables)?;
+        if operation_type(&operation) != OperationType::Subscription {
+            let message = "only subscription operations can be subscribed to";
+            return Err(Box::new(context.error(message, &operation)));
+        }
+        let (root_type, selection_set) = context.root(&operation)?;
+
+        let mut fields = IndexMap::new();
+        context.collect_fields(root_type, &selection_set, &mut HashSet::new(), &mut fields);
+        if fields.len() != 1 {
+            let message = format!(
+                "a subscription must select exactly one root field, but it selects {}",
+                fields.len()
+            );
+            return Err(Box::new(context.error(message, &operation)));
+        }
+        let (response_name, fields) = fields.swap_remove_index(0).unwrap();
+        let name = fields[0]
+            .name()
+            .map(|name| name.text().to_string())
+            .unwrap_or_default();
+        if name.starts_with("__") {
+            let message = format!(
+                "a subscription must not select the introspection field `{}` as its root field",
+                name
+            );
+            return Err(Box::new(context.error(message, &fields[0])));
+        }
+        let definition = match context.schema.field(root_type, &name) {
+            Some(definition) => definition,
+            None => {
+                let message = format!("the field `{}.{}` is not defined", root_type, name);
+                return Err(Box::new(context.error(message, &fields[0])));
+            }
+        };
+
+        let source = context
+            .argument_values(definition.arguments(), &fields[0])
+            .and_then(|arguments| root.subscribe_field(&name, &arguments));
+        let source = match source {
+            Ok(source) => source,
+            Err(error) => {
+                let path = vec![PathSegment::Field(response_name)];
+                return Err(Box::new(context.response_error(error, &fields, path)));
+            }
+        };
+        Ok(Self {
+            context,
+            root_type,
+            response_name,
+            fields,
+            definition,
+            source,
+        })
+    }
+
+    /// Get the response for a value of the root field's stream, which is
+    /// the spec's `MapSourceToResponseEvent`.
+    async fn execute_event(&self, event: Result<ResolvedValue<'_>, FieldError>) -> Response {
+        let path = vec![PathSegment::Field(self.response_name.clone())];
+        let value = self
+            .context
+            .complete_field(self.root_type, self.definition, &self.fields, event, path)
+            .await;
+        let data = value.map(|value| {
+            let mut data = JsonMap::new();
+            data.insert(self.response_name.clone(), value);
+            data
+        });
+        self.context.response(data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::Cell, rc::Rc};
+
+    use apollo_compiler::{ExecutableDocument, Schema};
+    use futures::{executor::block_on, stream, StreamExt};
+    use pretty_assertions::assert_eq;
+    use serde_json::{json, Value as JsonValue};
+
+    use crate::{
+        subscribe, BoxStream, FieldError, JsonMap, ResolvedValue, Resolver, SubscriptionResolver,
+    };
+
+    const SCHEMA: &str = r#"
+type Query { a: Int }
+type Subscription {
+  newPet: Pet
+  count(from: Int = 0): Int!
+  forever: Int
+  closed: Int
+}
+interface Pet { name: String! }
+type Cat implements Pet { name: String! lives: Int }
+"#;
+
+    /// Sets a flag when it is dropped.
+    struct DropGuard(Rc<Cell<bool>>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    struct Subscription {
+        dropped: Rc<Cell<bool>>,
+    }
+
+    impl SubscriptionResolver for Subscription {
+        fn subscribe_field<'a>(
+            &'a self,
+            field_name: &str,
+            arguments: &JsonMap,
+        ) -> Result<BoxStream<'a, Result<ResolvedValue<'a>, FieldError>>, FieldError> {
+            match field_name {
+                "newPet" => Ok(Box::pin(stream::iter([
+                    Ok(ResolvedValue::object(Cat("Tom"))),
+                    Err(FieldError::new("the pet ran away")),
+                    Ok(ResolvedValue::object(Cat("Felix"))),
+                ]))),
+                "count" => {
+                    let from = arguments["from"].as_i64().unwrap();
+                    Ok(Box::pin(stream::iter([
+                        Ok(ResolvedValue::leaf(from)),
+                        Ok(ResolvedValue::null()),
+                    ])))
+                }
+                "forever" => {
+                    let guard = DropGuard(self.dropped.clone());
+                    Ok(Box::pin(stream::repeat_with(move || {
+                        let _ = &guard;
+                        Ok(ResolvedValue::leaf(1))
+                    })))
+                }
+                _ => Err(FieldError::new("the field is closed").extension("code", "CLOSED")),
+            }
+        }
+    }
+
+    struct Cat(&'static str);
+
+    impl Resolver for Cat {
+        fn resolve_type(&self) -> Option<&str> {
+            Some("Cat")
+        }
+
+        fn resolve_field<'a>(
+            &'a self,
+            field_name: &str,
+            _arguments: &JsonMap,
+        ) -> Result<ResolvedValue<'a>, FieldError> {
+            Ok(match field_name {
+                "name" => ResolvedValue::leaf(self.0),
+                "lives" => ResolvedValue::leaf(9),
+                _ => ResolvedValue::null(),
+            })
+        }
+    }
+
+    fn subscribe_with(query: &str, root: &Subscription) -> Vec<JsonValue> {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse("query.graphql", query);
+        let responses = subscribe(&schema, &document, None, &JsonMap::new(), root);
+        block_on(responses.map(|response| response.to_json()).collect())
+    }
+
+    fn root() -> Subscription {
+        Subscription {
+            dropped: Rc::new(Cell::new(false)),
+        }
+    }
+
+    #[test]
+    fn it_executes_the_selection_set_for_each_event() {
+        let responses = subscribe_with(
+            "subscription { pet: newPet { __typename name ... on Cat { lives } } }",
+            &root(),
+        );
+        assert_eq!(
+            responses,
+            [
+                json!({ "data": { "pet": { "__typename": "Cat", "name": "Tom", "lives": 9 } } }),
+                json!({
+                    "errors": [{
+                        "message": "the pet ran away",
+                        "locations": [{ "line": 1, "column": 16 }],
+                        "path": ["pet"],
+                    }],
+                    "data": { "pet": null }
+                }),
+                json!({ "data": { "pet": { "__typename": "Cat", "name": "Felix", "lives": 9 } } }),
+            ]
+        );
+
+        let responses = subscribe_with("subscription { count(from: 3) }", &root());
+        assert_eq!(
+            responses,
+            [
+                json!({ "data": { "count": 3 } }),
+                json!({
+                    "errors": [{
+                        "message": "cannot return null for the non-null field `Subscription.count`",
+                        "locations": [{ "line": 1, "column": 16 }],
+                        "path": ["count"],
+                    }],
+                    "data": null
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_reports_invalid_subscriptions() {
+        assert_eq!(
+            subscribe_with("subscription { count newPet { name } }", &root()),
+            [json!({
+                "errors": [{
+                    "message": "a subscription must select exactly one root field, but it selects 2",
+                    "locations": [{ "line": 1, "column": 1 }],
+                }]
+            })]
+        );
+        assert_eq!(
+            subscribe_with(
+                "subscription($skip: Boolean = true) { count newPet @skip(if: $skip) { name } }",
+                &root()
+            ),
+            [
+                json!({ "data": { "count": 0 } }),
+                json!({
+                    "errors": [{
+                        "message": "cannot return null for the non-null field `Subscription.count`",
+                        "locations": [{ "line": 1, "column": 39 }],
+                        "path": ["count"],
+                    }],
+                    "data": null
+                }),
+            ]
+        );
+        assert_eq!(
+            subscribe_with("subscription { __typename }", &root()),
+            [json!({
+                "errors": [{
+                    "message": "a subscription must not select the introspection field `__typename` as its root field",
+                    "locations": [{ "line": 1, "column": 16 }],
+                }]
+            })]
+        );
+        assert_eq!(
+            subscribe_with("{ a }", &root()),
+            [json!({
+                "errors": [{
+                    "message": "only subscription operations can be subscribed to",
+                    "locations": [{ "line": 1, "column": 1 }],
+                }]
+            })]
+        );
+        assert_eq!(
+            subscribe_with("subscription { closed }", &root()),
+            [json!({
+                "errors": [{
+                    "message": "the field is closed",
+                    "locations": [{ "line": 1, "column": 16 }],
+                    "path": ["closed"],
+                    "extensions": { "code": "CLOSED" },
+                }]
+            })]
+        );
+    }
+
+    #[test]
+    fn it_drops_the_source_stream_with_the_response_stream() {
+        let schema = Schema::parse(SCHEMA);
+        let document = ExecutableDocument::parse("query.graphql", "subscription { forever }");
+        let root = root();
+        let mut responses = subscribe(&schema, &document, None, &JsonMap::new(), &root);
+        for _ in 0..3 {
+            let response = block_on(responses.next()).unwrap();
+            assert_eq!(response.to_json(), json!({ "data": { "forever": 1 } }));
+        }
+        assert!(!root.dropped.get());
+        drop(responses);
+        assert!(root.dropped.get());
+    }
+}
@@ -24,11 +24,67 @@ fn parse_query(query: &str) {
     }
 }
 
+fn parse_schema(schema: &str) {
+    let parser = apollo_parser::Parser::new(schema);
+    let tree = parser.parse();
+
+    if tree.errors().len() != 0 {
+        panic!("error parsing schema: {:?}", tree.errors());
+    }
+
+    for definition in tree.document().definitions() {
+        black_box(definition);
+    }
+}
+
+/// A schema of roughly 375KB, made up of copies of a supergraph schema.
+fn large_schema() -> String {
+    include_str!("../test_data/parser/ok/0032_supergraph.graphql").repeat(50)
+}
+
+/// A query of roughly 200KB with many aliased fields, arguments and nested
+/// selection sets.
+fn large_query() -> String {
+    let mut query = String::from("query LargeQuery($first: Int, $after: String) {\n");
+    for i in 0..500 {
+        query.push_str(&format!(
+            "  products{i}: topProducts(first: $first, after: $after, filter: {{ inStock: true, tags: [\"a\", \"b\"] }}) {{\n    \
+               id\n    name\n    price @include(if: true)\n    \
+               reviews(first: 10) {{ id body author {{ id username }} }}\n  }}\n"
+        ));
+    }
+    query.push('}');
+    query
+}
+
 fn bench_parser_peek_n(c: &mut Criterion) {
     let query = "query ExampleQuery($topProductsFirst: Int) {\n  me { \n    id\n  }\n  topProducts(first:  $topProductsFirst) {\n    name\n    price\n    inStock\n weight\n test test test test test test test test test test test test }\n}";
 
     c.bench_function("parser_peek_n", move |b| b.iter(|| parse_query(query)));
 }
 
-criterion_group!(benches, bench_parser_peek_n);
+fn bench_large_schema(c: &mut Criterion) {
+    let schema = large_schema();
+
+    let mut group = c.benchmark_group("large_schema");
+    group.throughput(Throughput::Bytes(schema.len() as u64));
+    group.bench_function("parse", |b| b.iter(|| parse_schema(&schema)));
+    group.finish();
+}
+
+fn bench_large_query(c: &mut Criterion) {
+    let query = large_query();
+
+    let mut group = c.benchmark_group("large_query");
+    group.throughput(Throughput::Bytes(query.len() as u64));
+    group.bench_function("parse", |b| b.iter(|| parse_query(&query)));
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parser_peek_n,
+    bench_large_schema,
+    bench_large_query
+);
 criterion_main!(benches);
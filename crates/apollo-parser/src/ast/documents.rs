@@ -0,0 +1,159 @@
+//! Views over a `Document` that only contain the definitions allowed in
+//! executable documents or in type system documents.
+
+use crate::ast::{self, AstChildren};
+
+/// A view over a `Document` that only contains operations and fragments.
+///
+/// Use this with a tree parsed with `ParseMode::Executable` to ignore any
+/// type system definitions, which were reported as errors.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{ast, ParseMode, Parser};
+///
+/// let input = "query { me } type Query { me: String } fragment F on Query { me }";
+/// let ast = Parser::new(input).mode(ParseMode::Executable).parse();
+/// assert_eq!(1, ast.errors().len());
+///
+/// let doc = ast::ExecutableDocument::from(ast.document());
+/// assert_eq!(doc.definitions().count(), 2);
+/// assert_eq!(doc.operations().count(), 1);
+/// assert_eq!(doc.fragments().count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExecutableDocument {
+    document: ast::Document,
+}
+
+impl ExecutableDocument {
+    /// Get the document this is a view over, including all its definitions.
+    pub fn document(&self) -> &ast::Document {
+        &self.document
+    }
+
+    /// Iterate over the operation and fragment definitions.
+    pub fn definitions(&self) -> impl Iterator<Item = ast::Definition> {
+        self.document
+            .definitions()
+            .filter(ast::Definition::is_executable)
+    }
+
+    /// Iterate over the operation definitions.
+    pub fn operations(&self) -> AstChildren<ast::OperationDefinition> {
+        ast::support::children(&self.document.syntax)
+    }
+
+    /// Iterate over the fragment definitions.
+    pub fn fragments(&self) -> AstChildren<ast::FragmentDefinition> {
+        ast::support::children(&self.document.syntax)
+    }
+}
+
+impl From<ast::Document> for ExecutableDocument {
+    fn from(document: ast::Document) -> Self {
+        Self { document }
+    }
+}
+
+/// A view over a `Document` that only contains type system definitions and
+/// extensions.
+///
+/// Use this with a tree parsed with `ParseMode::TypeSystem` to ignore any
+/// operations and fragments, which were reported as errors.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{ast, ParseMode, Parser};
+///
+/// let input = "type Query { me: String } query { me } extend type Query { you: String }";
+/// let ast = Parser::new(input).mode(ParseMode::TypeSystem).parse();
+/// assert_eq!(1, ast.errors().len());
+///
+/// let doc = ast::TypeSystemDocument::from(ast.document());
+/// assert_eq!(doc.definitions().count(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeSystemDocument {
+    document: ast::Document,
+}
+
+impl TypeSystemDocument {
+    /// Get the document this is a view over, including all its definitions.
+    pub fn document(&self) -> &ast::Document {
+        &self.document
+    }
+
+    /// Iterate over the type system definitions and extensions.
+    pub fn definitions(&self) -> impl Iterator<Item = ast::Definition> {
+        self.document
+            .definitions()
+            .filter(|def| !def.is_executable())
+    }
+}
+
+impl From<ast::Document> for TypeSystemDocument {
+    fn from(document: ast::Document) -> Self {
+        Self { document }
+    }
+}
+
+impl ast::Definition {
+    /// Check whether this is an operation or a fragment definition, which
+    /// are the definitions allowed in executable documents.
+    pub fn is_executable(&self) -> bool {
+        matches!(
+            self,
+            ast::Definition::OperationDefinition(_) | ast::Definition::FragmentDefinition(_)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ast, ParseMode, Parser, TextEdit};
+
+    #[test]
+    fn it_only_iterates_allowed_definitions() {
+        let input = r#"
+"A schema"
+schema { query: Query }
+query { me }
+extend scalar Date @specifiedBy(url: "https://example.com")
+fragment F on Query { me }
+{ you }
+"#;
+        let ast = Parser::new(input).parse();
+        assert_eq!(0, ast.errors().len());
+        assert_eq!(ast.document().definitions().count(), 5);
+
+        let executable = ast::ExecutableDocument::from(ast.document());
+        assert!(executable.definitions().all(|def| def.is_executable()));
+        assert_eq!(executable.definitions().count(), 3);
+        assert_eq!(executable.operations().count(), 2);
+
+        let type_system = ast::TypeSystemDocument::from(ast.document());
+        assert_eq!(type_system.definitions().count(), 2);
+    }
+
+    #[test]
+    fn it_reports_definitions_not_allowed_by_the_mode() {
+        let input = "type Query { me: String } query { me } { you } fragment F on Query { me }";
+
+        let ast = Parser::new(input).mode(ParseMode::Executable).parse();
+        let errors: Vec<_> = ast.errors().map(|err| err.data()).collect();
+        assert_eq!(errors, ["type"]);
+
+        let ast = Parser::new(input).mode(ParseMode::TypeSystem).parse();
+        let errors: Vec<_> = ast.errors().map(|err| err.data()).collect();
+        assert_eq!(errors, ["query", "{", "fragment"]);
+        // Definitions that are not allowed are still part of the tree.
+        assert_eq!(ast.document().definitions().count(), 4);
+        // Reparsing keeps the mode.
+        let ast = ast.reparse(TextEdit::insert(0, " "));
+        assert_eq!(3, ast.errors().len());
+
+        let ast = Parser::new(input).parse();
+        assert_eq!(0, ast.errors().len());
+    }
+}
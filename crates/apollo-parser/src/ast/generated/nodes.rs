@@ -86,6 +86,9 @@ impl DirectiveDefinition {
     pub fn arguments_definition(&self) -> Option<ArgumentsDefinition> {
         support::child(&self.syntax)
     }
+    pub fn repeatable_token(&self) -> Option<SyntaxToken> {
+        support::token(&self.syntax, S![repeatable])
+    }
     pub fn on_token(&self) -> Option<SyntaxToken> {
         support::token(&self.syntax, S![on])
     }
@@ -98,6 +101,9 @@ pub struct SchemaDefinition {
     pub(crate) syntax: SyntaxNode,
 }
 impl SchemaDefinition {
+    pub fn description(&self) -> Option<Description> {
+        support::child(&self.syntax)
+    }
     pub fn schema_token(&self) -> Option<SyntaxToken> {
         support::token(&self.syntax, S![schema])
     }
@@ -703,6 +709,9 @@ impl VariableDefinition {
     pub fn default_value(&self) -> Option<DefaultValue> {
         support::child(&self.syntax)
     }
+    pub fn directives(&self) -> Option<Directives> {
+        support::child(&self.syntax)
+    }
 }
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DefaultValue {
@@ -4,7 +4,9 @@
 //! with a few exceptions. For example, for easy of querying the AST we do not
 //! separate `Definition` into `ExecutableDefinition` and
 //! `TypeSystemDefinitionOrExtension`. Instead, all possible definitions and
-//! extensions can be accessed with `Definition`.
+//! extensions can be accessed with `Definition`. The `ExecutableDocument` and
+//! `TypeSystemDocument` views over a `Document` only iterate over the
+//! definitions of either kind.
 //!
 //! Each struct in this module has getter methods to access information that's
 //! part of its node. For example, as per spec a `UnionTypeDefinition` is defined as follows:
@@ -102,13 +104,17 @@
 //! ```
 //!
 //! [GraphQL grammar]: https://spec.graphql.org/October2021/#sec-Document-Syntax
+mod documents;
 mod generated;
 mod node_ext;
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, ops::Range};
 
-use crate::{SyntaxKind, SyntaxNode, SyntaxNodeChildren, SyntaxToken};
+use crate::{
+    LineCol, LineColUtf16, LineIndex, SyntaxKind, SyntaxNode, SyntaxNodeChildren, SyntaxToken,
+};
 
+pub use documents::{ExecutableDocument, TypeSystemDocument};
 pub use generated::nodes::*;
 
 /// The main trait to go from untyped `SyntaxNode`  to a typed ast. The
@@ -139,6 +145,34 @@ pub trait AstNode {
     {
         Self::cast(self.syntax().clone_subtree()).unwrap()
     }
+
+    /// Get the node's start and end line and column, with columns counted in
+    /// UTF-8 bytes. The `LineIndex` is available from
+    /// `SyntaxTree::line_index()`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ast::{self, AstNode}, LineCol, Parser};
+    ///
+    /// let ast = Parser::new("type Query {\n  me: User\n}").parse();
+    /// let doc = ast.document();
+    /// let def = doc.definitions().next().unwrap();
+    ///
+    /// let location = def.location(ast.line_index());
+    /// assert_eq!(location.start, LineCol { line: 0, col: 0 });
+    /// assert_eq!(location.end, LineCol { line: 2, col: 1 });
+    /// ```
+    fn location(&self, line_index: &LineIndex) -> Range<LineCol> {
+        let range = self.syntax().text_range();
+        line_index.range(range.start().into()..range.end().into())
+    }
+
+    /// Get the node's start and end line and column, with columns counted in
+    /// UTF-16 code units.
+    fn location_utf16(&self, line_index: &LineIndex) -> Range<LineColUtf16> {
+        let range = self.syntax().text_range();
+        line_index.range_utf16(range.start().into()..range.end().into())
+    }
 }
 
 /// Like `AstNode`, but wraps tokens rather than interior nodes.
@@ -1,8 +1,14 @@
-// This lint is here as we don't really need users to convert String/i64/f64
+// This lint is here as we don't really need users to convert String/bool
 // into an AST Node. Should this change, we can remove this lint again.
 #![allow(clippy::from_over_into)]
 
-use crate::{ast, ast::AstNode, SyntaxNode, TokenText};
+use std::borrow::Cow;
+
+use rowan::{GreenNodeData, NodeOrToken};
+
+use crate::{
+    ast, ast::AstNode, lexer::hex_code_point, Error, ErrorKind, SyntaxKind, SyntaxNode, TokenText,
+};
 
 impl ast::Name {
     pub fn text(&self) -> TokenText {
@@ -26,26 +32,110 @@ impl ast::EnumValue {
     }
 }
 
+impl ast::StringValue {
+    /// Get the value of the string.
+    ///
+    /// Escape sequences are decoded, and block strings have their common
+    /// indentation and leading and trailing blank lines removed, as described
+    /// in the [specification]. The value is borrowed from the tree if it does
+    /// not need any changes.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ast, ast::AstNode, Parser};
+    ///
+    /// let input = r#"query { a(b: "caf\u00e9\n") }"#;
+    /// let ast = Parser::new(input).parse();
+    /// let string_value = ast
+    ///     .document()
+    ///     .syntax()
+    ///     .descendants()
+    ///     .find_map(ast::StringValue::cast)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(string_value.value().unwrap(), "café\n");
+    /// ```
+    ///
+    /// [specification]: https://spec.graphql.org/October2021/#sec-String-Value.Semantics
+    pub fn value(&self) -> Result<Cow<'_, str>, Error> {
+        string_value(self.syntax())
+    }
+}
+
+impl ast::Description {
+    /// Get the value of the description's string. See
+    /// [`StringValue::value`](ast::StringValue::value).
+    pub fn value(&self) -> Result<Cow<'_, str>, Error> {
+        string_value(self.syntax())
+    }
+}
+
+/// Invalid escape sequences are kept as they are. Use
+/// [`StringValue::value`](ast::StringValue::value) to detect them.
 impl Into<String> for ast::StringValue {
     fn into(self) -> String {
-        let text = text_of_first_token(self.syntax());
-        text.trim_start_matches('"')
-            .trim_end_matches('"')
-            .to_string()
+        match self.value() {
+            Ok(value) => value.into_owned(),
+            Err(_) => {
+                let text = text_of_first_token(self.syntax());
+                text.trim_start_matches('"')
+                    .trim_end_matches('"')
+                    .to_string()
+            }
+        }
     }
 }
 
-impl Into<i64> for ast::IntValue {
-    fn into(self) -> i64 {
-        let text = text_of_first_token(self.syntax());
-        text.parse().expect("Cannot parse IntValue")
+impl ast::IntValue {
+    /// Get the source text of the integer.
+    ///
+    /// This can be used to parse integers that are too large for an `i64`
+    /// with an arbitrary precision number type.
+    pub fn text(&self) -> TokenText {
+        text_of_first_token(self.syntax())
     }
 }
 
-impl Into<f64> for ast::FloatValue {
-    fn into(self) -> f64 {
-        let text = text_of_first_token(self.syntax());
-        text.parse().expect("Cannot parse FloatValue")
+impl ast::FloatValue {
+    /// Get the source text of the float.
+    pub fn text(&self) -> TokenText {
+        text_of_first_token(self.syntax())
+    }
+}
+
+/// Convert an `IntValue` to the range of the GraphQL `Int` type, a signed
+/// 32-bit integer.
+impl TryFrom<ast::IntValue> for i32 {
+    type Error = Error;
+
+    fn try_from(value: ast::IntValue) -> Result<Self, Self::Error> {
+        parse_number(value.syntax(), "Int value out of range for i32")
+    }
+}
+
+impl TryFrom<ast::IntValue> for i64 {
+    type Error = Error;
+
+    fn try_from(value: ast::IntValue) -> Result<Self, Self::Error> {
+        parse_number(value.syntax(), "Int value out of range for i64")
+    }
+}
+
+/// Int values are valid input for the GraphQL `Float` type. Integers with
+/// more significant digits than an `f64` can represent are rounded.
+impl TryFrom<ast::IntValue> for f64 {
+    type Error = Error;
+
+    fn try_from(value: ast::IntValue) -> Result<Self, Self::Error> {
+        parse_float(value.syntax())
+    }
+}
+
+impl TryFrom<ast::FloatValue> for f64 {
+    type Error = Error;
+
+    fn try_from(value: ast::FloatValue) -> Result<Self, Self::Error> {
+        parse_float(value.syntax())
     }
 }
 
@@ -56,6 +146,217 @@ impl Into<bool> for ast::BooleanValue {
     }
 }
 
+fn parse_number<T: std::str::FromStr>(node: &SyntaxNode, message: &str) -> Result<T, Error> {
+    text_of_first_token(node)
+        .parse()
+        .map_err(|_| out_of_range(node, message))
+}
+
+/// GraphQL does not allow infinite or NaN floats, so values that overflow an
+/// `f64` are errors.
+fn parse_float(node: &SyntaxNode) -> Result<f64, Error> {
+    let message = "Float value out of range for f64";
+    let float: f64 = parse_number(node, message)?;
+    if float.is_finite() {
+        Ok(float)
+    } else {
+        Err(out_of_range(node, message))
+    }
+}
+
+fn out_of_range(node: &SyntaxNode, message: &str) -> Error {
+    let text = text_of_first_token(node);
+    Error::with_loc(message, text.to_string(), node.text_range().start().into())
+        .with_kind(ErrorKind::NumberOutOfRange)
+}
+
+/// Decode the first STRING token in `node`.
+fn string_value(node: &SyntaxNode) -> Result<Cow<'_, str>, Error> {
+    let index: usize = node.text_range().start().into();
+    // Borrow the text from the tree whenever possible, so that values that do
+    // not need decoding are not copied.
+    match node.green() {
+        Cow::Borrowed(green) => decode_string(first_string_token(green), index),
+        Cow::Owned(green) => decode_string(first_string_token(&green), index)
+            .map(|value| Cow::Owned(value.into_owned())),
+    }
+}
+
+fn first_string_token(node: &GreenNodeData) -> &str {
+    match node.children().next() {
+        Some(NodeOrToken::Node(node)) => first_string_token(node),
+        Some(NodeOrToken::Token(token))
+            if token.kind() == rowan::SyntaxKind(SyntaxKind::STRING as u16) =>
+        {
+            token.text()
+        }
+        _ => "",
+    }
+}
+
+fn decode_string(text: &str, index: usize) -> Result<Cow<'_, str>, Error> {
+    if let Some(raw) = text
+        .strip_prefix(r#"""""#)
+        .and_then(|text| text.strip_suffix(r#"""""#))
+    {
+        return Ok(block_string_value(raw));
+    }
+
+    match text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+    {
+        Some(raw) if !raw.contains('\\') => Ok(Cow::Borrowed(raw)),
+        Some(raw) => unescape(raw, index + 1).map(Cow::Owned),
+        None => Err(
+            Error::with_loc("unterminated string value", text.to_string(), index)
+                .with_kind(ErrorKind::UnterminatedString),
+        ),
+    }
+}
+
+/// Decode the escape sequences of a single line string. `index` is the offset
+/// of `raw` in the input, for errors.
+fn unescape(raw: &str, index: usize) -> Result<String, Error> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.char_indices();
+
+    while let Some((start, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let invalid = |end: usize| {
+            let end = end.min(raw.len());
+            Error::with_loc(
+                "invalid escape sequence",
+                raw[start..end].to_string(),
+                index + start,
+            )
+            .with_kind(ErrorKind::InvalidEscapeSequence)
+        };
+
+        let decoded = match chars.next() {
+            Some((_, '"')) => '"',
+            Some((_, '\\')) => '\\',
+            Some((_, '/')) => '/',
+            Some((_, 'b')) => '\u{0008}',
+            Some((_, 'f')) => '\u{000C}',
+            Some((_, 'n')) => '\n',
+            Some((_, 'r')) => '\r',
+            Some((_, 't')) => '\t',
+            Some((_, 'u')) if raw[start + 2..].starts_with('{') => {
+                let end = raw[start..]
+                    .find('}')
+                    .map(|end| start + end)
+                    .ok_or_else(|| invalid(raw.len()))?;
+                let code = Some(&raw[start + 3..end])
+                    .filter(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+                    .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid(end + 1))?;
+                chars.nth(end - start - 2);
+                code
+            }
+            Some((_, 'u')) => {
+                let code = hex_code_point(&raw[start + 2..]).ok_or_else(|| invalid(start + 6))?;
+                chars.nth(3);
+                match code {
+                    0xD800..=0xDBFF => {
+                        // A leading surrogate must be followed by an escaped
+                        // trailing surrogate.
+                        let trailing = raw[start + 6..]
+                            .strip_prefix("\\u")
+                            .and_then(hex_code_point)
+                            .filter(|code| (0xDC00..=0xDFFF).contains(code))
+                            .ok_or_else(|| invalid(start + 6))?;
+                        chars.nth(5);
+                        let code = 0x10000 + ((code - 0xD800) << 10) + (trailing - 0xDC00);
+                        char::from_u32(code).ok_or_else(|| invalid(start + 12))?
+                    }
+                    code => char::from_u32(code).ok_or_else(|| invalid(start + 6))?,
+                }
+            }
+            Some((end, c)) => return Err(invalid(end + c.len_utf8())),
+            None => return Err(invalid(raw.len())),
+        };
+        value.push(decoded);
+    }
+
+    Ok(value)
+}
+
+/// See: https://spec.graphql.org/October2021/#BlockStringValue()
+fn block_string_value(raw: &str) -> Cow<'_, str> {
+    // A single line without escapes is its own value, unless it's blank.
+    if !raw.contains(['\n', '\r']) && !raw.contains(r#"\""""#) {
+        if raw.trim_matches(is_whitespace).is_empty() {
+            return Cow::Borrowed("");
+        }
+        return Cow::Borrowed(raw);
+    }
+
+    let raw = raw.replace(r#"\""""#, r#"""""#);
+    let lines = split_lines(&raw);
+
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter_map(|line| {
+            let indent = line.len() - line.trim_start_matches(is_whitespace).len();
+            (indent < line.len()).then_some(indent)
+        })
+        .min();
+
+    let lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match common_indent {
+            // Whitespace is ASCII, so this is a char boundary.
+            Some(indent) if i > 0 => &line[indent.min(line.len())..],
+            _ => line,
+        })
+        .collect();
+
+    let is_blank = |line: &&str| line.trim_matches(is_whitespace).is_empty();
+    let start = lines
+        .iter()
+        .position(|line| !is_blank(line))
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|line| !is_blank(line))
+        .map_or(start, |end| end + 1);
+
+    Cow::Owned(lines[start..end].join("\n"))
+}
+
+/// Split `s` on `\n`, `\r\n` and `\r`.
+fn split_lines(s: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '\n' || c == '\r' {
+            lines.push(&s[start..i]);
+            start = i + 1;
+            if c == '\r' && matches!(chars.peek(), Some((_, '\n'))) {
+                chars.next();
+                start += 1;
+            }
+        }
+    }
+    lines.push(&s[start..]);
+
+    lines
+}
+
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
 fn text_of_first_token(node: &SyntaxNode) -> TokenText {
     let first_token = node
         .green()
@@ -67,3 +368,214 @@ fn text_of_first_token(node: &SyntaxNode) -> TokenText {
 
     TokenText(first_token)
 }
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use crate::{ast, ast::AstNode, ErrorKind, Parser};
+
+    fn string_values(input: &str) -> Vec<ast::StringValue> {
+        let ast = Parser::new(input).parse();
+        assert_eq!(ast.errors().len(), 0);
+        ast.document()
+            .syntax()
+            .descendants()
+            .filter_map(ast::StringValue::cast)
+            .collect()
+    }
+
+    fn value(input: &str) -> String {
+        let query = format!("query {{ a(b: {}) }}", input);
+        let string_value = string_values(&query).remove(0);
+        let value = string_value.value().unwrap().into_owned();
+        value
+    }
+
+    #[test]
+    fn it_does_not_create_names_without_a_name_token() {
+        let inputs = [
+            "schema { query: }",
+            "type Query { a(: Int): Int }",
+            "query($: Int) { a }",
+            "{ a(x: $) }",
+            "{ a @ }",
+            "{ d { ... on { b } } }",
+            "enum E { }",
+            "directive @ on FIELD",
+        ];
+        for input in inputs {
+            let ast = Parser::new(input).parse();
+            assert!(ast.errors().len() > 0, "{}", input);
+            for name in ast
+                .document()
+                .syntax()
+                .descendants()
+                .filter_map(ast::Name::cast)
+            {
+                // This panics on a NAME node without a token.
+                assert!(!name.text().is_empty(), "{}", input);
+            }
+        }
+
+        let ast = Parser::new("schema { query: }").parse();
+        let root = ast
+            .document()
+            .syntax()
+            .descendants()
+            .find_map(ast::RootOperationTypeDefinition::cast)
+            .unwrap();
+        assert!(root.named_type().unwrap().name().is_none());
+    }
+
+    #[test]
+    fn it_decodes_escape_sequences() {
+        assert_eq!(value(r#""plain""#), "plain");
+        assert_eq!(value(r#""a\\b\/c""#), "a\\b/c");
+        assert_eq!(value(r#""\b\f\n\r\t""#), "\u{8}\u{c}\n\r\t");
+        assert_eq!(value(r#""caf\u00E9""#), "café");
+        assert_eq!(value(r#""\u{1F600} \u{00000041}""#), "😀 A");
+        assert_eq!(value(r#""a \"quoted\" word""#), "a \"quoted\" word");
+        assert_eq!(value(r#""\uD83D\uDE00""#), "😀");
+        assert_eq!(value(r#""""#), "");
+    }
+
+    #[test]
+    fn it_borrows_values_without_escapes() {
+        let values = string_values(r#"query { a(b: "plain", c: """block""") }"#);
+        assert!(matches!(values[0].value(), Ok(Cow::Borrowed("plain"))));
+        assert!(matches!(values[1].value(), Ok(Cow::Borrowed("block"))));
+    }
+
+    #[test]
+    fn it_errors_on_invalid_escape_sequences() {
+        // The lexer rejects these strings, so decode them directly.
+        let cases = [
+            (r#""a\qb""#, r"\q", 15),
+            (r#""\u00G1""#, r"\u00G1", 14),
+            (r#""\uD800""#, r"\uD800", 14),
+            (r#""\uD800\u0041""#, r"\uD800", 14),
+            (r#""\uDE00""#, r"\uDE00", 14),
+            (r#""\u{110000}""#, r"\u{110000}", 14),
+            (r#""\u{D800}""#, r"\u{D800}", 14),
+            (r#""\u{}""#, r"\u{}", 14),
+        ];
+
+        for (input, data, index) in cases {
+            let err = super::decode_string(input, 13).unwrap_err();
+            assert_eq!(err.kind(), &ErrorKind::InvalidEscapeSequence, "{}", input);
+            assert_eq!(err.data(), data, "{}", input);
+            assert_eq!(err.index(), index, "{}", input);
+        }
+    }
+
+    #[test]
+    fn it_removes_block_string_indentation() {
+        let input =
+            "\"\"\"\n    \n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  \"\"\"";
+        assert_eq!(value(input), "Hello,\n  World!\n\nYours,\n  GraphQL.");
+
+        assert_eq!(
+            value("\"\"\"  first\n    second\r\n    third\"\"\""),
+            "  first\nsecond\nthird"
+        );
+        assert_eq!(value("\"\"\"a \\\"\"\" b\"\"\""), "a \"\"\" b");
+        assert_eq!(value("\"\"\"  \t \"\"\""), "");
+        assert_eq!(value("\"\"\"\\n\"\"\""), "\\n");
+    }
+
+    #[test]
+    fn it_decodes_descriptions() {
+        let input = r#"
+"""
+  The query type.
+"""
+type Query {
+  "The current \"user\"."
+  me: User
+}
+"#;
+        let ast = Parser::new(input).parse();
+        let descriptions: Vec<_> = ast
+            .document()
+            .syntax()
+            .descendants()
+            .filter_map(ast::Description::cast)
+            .collect();
+
+        assert_eq!(descriptions[0].value().unwrap(), "The query type.");
+        assert_eq!(
+            descriptions[0].string_value().unwrap().value().unwrap(),
+            "The query type."
+        );
+        assert_eq!(descriptions[1].value().unwrap(), "The current \"user\".");
+    }
+
+    fn values(input: &str) -> Vec<ast::Value> {
+        let query = format!("query {{ a(b: {}) }}", input);
+        let ast = Parser::new(&query).parse();
+        assert_eq!(ast.errors().len(), 0);
+        ast.document()
+            .syntax()
+            .descendants()
+            .filter_map(ast::ListValue::cast)
+            .next()
+            .unwrap()
+            .values()
+            .collect()
+    }
+
+    fn int_value(value: &ast::Value) -> ast::IntValue {
+        match value {
+            ast::Value::IntValue(int) => int.clone(),
+            _ => panic!("expected an IntValue"),
+        }
+    }
+
+    #[test]
+    fn it_converts_int_values() {
+        let values = values("[-10, 2147483648, 99999999999999999999]");
+
+        assert_eq!(i32::try_from(int_value(&values[0])), Ok(-10));
+        assert_eq!(i64::try_from(int_value(&values[0])), Ok(-10));
+
+        let err = i32::try_from(int_value(&values[1])).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::NumberOutOfRange);
+        assert_eq!(err.data(), "2147483648");
+        assert_eq!(err.index(), 19);
+        assert_eq!(i64::try_from(int_value(&values[1])), Ok(2147483648));
+
+        let big = int_value(&values[2]);
+        assert_eq!(
+            i64::try_from(big.clone()).unwrap_err().kind(),
+            &ErrorKind::NumberOutOfRange
+        );
+        assert_eq!(f64::try_from(big.clone()), Ok(1e20));
+        assert_eq!(big.text().as_ref(), "99999999999999999999");
+    }
+
+    #[test]
+    fn it_converts_float_values() {
+        let values = values("[-1.123E4, 1e400]");
+        let floats: Vec<_> = values
+            .into_iter()
+            .map(|value| match value {
+                ast::Value::FloatValue(float) => float,
+                _ => panic!("expected a FloatValue"),
+            })
+            .collect();
+
+        assert_eq!(f64::try_from(floats[0].clone()), Ok(-1.123E4));
+        assert_eq!(floats[0].text().as_ref(), "-1.123E4");
+        let err = f64::try_from(floats[1].clone()).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::NumberOutOfRange);
+        assert_eq!(err.data(), "1e400");
+    }
+
+    #[test]
+    fn it_converts_string_values_into_strings() {
+        let string_value = string_values(r#"query { a(b: "a\nb") }"#).remove(0);
+        let decoded: String = string_value.into();
+        assert_eq!(decoded, "a\nb");
+    }
+}
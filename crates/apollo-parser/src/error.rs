@@ -1,4 +1,6 @@
-use std::fmt;
+use std::{fmt, ops::Range};
+
+use crate::{LineCol, LineIndex, TokenKind};
 
 /// An `Error` type for operations performed in the lexer and the parser.
 ///
@@ -36,30 +38,77 @@ use std::fmt;
 
 #[derive(PartialEq, Eq, Clone)]
 pub struct Error {
+    pub(crate) kind: ErrorKind,
     pub(crate) message: String,
-    pub(crate) data: String,
+    pub(crate) data: Box<str>,
     pub(crate) index: usize,
+    /// Whether the error was found at the end of the input, in which case
+    /// `data` is `EOF` and does not occur in the input.
+    pub(crate) eof: bool,
+    pub(crate) location: Option<Range<LineCol>>,
 }
 
 impl Error {
     /// Create a new instance of `Error`.
     pub fn new<S: Into<String>>(message: S, data: String) -> Self {
         Self {
+            kind: ErrorKind::Custom,
             message: message.into(),
-            data,
+            data: data.into(),
             index: 0,
+            eof: false,
+            location: None,
         }
     }
 
     /// Create a new instance of `Error` with a `Location`.
     pub fn with_loc<S: Into<String>>(message: S, data: String, index: usize) -> Self {
         Self {
+            kind: ErrorKind::Custom,
             message: message.into(),
-            data,
+            data: data.into(),
             index,
+            eof: false,
+            location: None,
         }
     }
 
+    /// Set the error's kind.
+    pub(crate) fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Mark the error as found at the end of the input.
+    pub(crate) fn with_eof(mut self, eof: bool) -> Self {
+        self.eof = eof;
+        self
+    }
+
+    /// Get a reference to the error's kind.
+    ///
+    /// Unlike the error's message, the kind is meant to be matched on, for
+    /// example to offer a quick-fix or to display a translated message.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ErrorKind, Parser, TokenKind};
+    ///
+    /// let input = "query { me { name }";
+    /// let ast = Parser::new(input).parse();
+    ///
+    /// let err = ast.errors().next().unwrap();
+    /// assert_eq!(
+    ///     err.kind(),
+    ///     &ErrorKind::UnexpectedToken {
+    ///         expected: vec![TokenKind::RCurly]
+    ///     }
+    /// );
+    /// ```
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
     /// Get a reference to the error's data. This is usually the token that
     /// `apollo-parser` has found to be lexically or syntactically incorrect.
     pub fn data(&self) -> &str {
@@ -76,25 +125,187 @@ impl Error {
     pub fn message(&self) -> &str {
         self.message.as_ref()
     }
+
+    /// Get the error's start and end line and column in a given input.
+    ///
+    /// This is set for all errors returned from a `SyntaxTree`, and is `None`
+    /// for errors that were created manually.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{LineCol, Parser};
+    ///
+    /// let input = "type Query {\n  me: \n}";
+    /// let ast = Parser::new(input).parse();
+    ///
+    /// let err = ast.errors().next().unwrap();
+    /// let location = err.location().unwrap();
+    /// assert_eq!(location.start, LineCol { line: 2, col: 0 });
+    /// ```
+    pub fn location(&self) -> Option<Range<LineCol>> {
+        self.location.clone()
+    }
+
+    /// Get the byte range of the error in a given input.
+    ///
+    /// This is the range of the error's data, except for errors at the end of
+    /// the input, which have an empty range.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::Parser;
+    ///
+    /// let input = "query { me { name } ";
+    /// let ast = Parser::new(input).parse();
+    ///
+    /// let err = ast.errors().next().unwrap();
+    /// assert_eq!(err.data(), "EOF");
+    /// assert_eq!(err.span(), input.len()..input.len());
+    /// ```
+    pub fn span(&self) -> Range<usize> {
+        if self.eof {
+            self.index..self.index
+        } else {
+            self.index..self.index + self.data.len()
+        }
+    }
+
+    /// Compute the error's location using a `LineIndex` of its input.
+    pub(crate) fn with_line_index(mut self, line_index: &LineIndex) -> Self {
+        self.location = Some(line_index.range(self.span()));
+        self
+    }
+}
+
+/// The kind of an [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A character that cannot start any token, for example `%`.
+    UnexpectedCharacter,
+    /// A string value that ends before its closing quote.
+    UnterminatedString,
+    /// An escape sequence in a string value that is not valid, for example
+    /// `\q` or an unpaired surrogate like `\uD800`.
+    InvalidEscapeSequence,
+    /// An Int or Float value that does not fit in the number type it is
+    /// converted to.
+    NumberOutOfRange,
+    /// A spread operator that does not have exactly three dots.
+    UnterminatedSpread,
+    /// An Int or Float value that is malformed, for example `1.2.3`.
+    InvalidNumber,
+    /// A token was found where one of the `expected` tokens should have been.
+    ///
+    /// Keywords are lexed as [`TokenKind::Name`], so an expected `on` or
+    /// `implements` is reported as an expected `Name`.
+    UnexpectedToken {
+        /// The kinds of tokens that would have been valid at this position.
+        expected: Vec<TokenKind>,
+    },
+    /// A Name that contains invalid characters, or a reserved Name used where
+    /// it is not allowed, for example a Fragment named `on`.
+    InvalidName,
+    /// A Name that is not a valid Directive Location.
+    InvalidDirectiveLocation,
+    /// A top-level definition or extension was expected.
+    MissingDefinition,
+    /// A description in front of a definition that cannot have one, like an
+    /// operation or a fragment.
+    UnexpectedDescription,
+    /// An `extend` keyword that is not followed by an extensible type system
+    /// definition, for example `extend Cat`.
+    InvalidExtension,
+    /// A definition that is not allowed by the document's
+    /// [`ParseMode`](crate::ParseMode), for example a type definition in an
+    /// executable document.
+    UnexpectedDefinition,
+    /// One of the [`ParserLimits`](crate::ParserLimits) was exceeded, and
+    /// parsing stopped.
+    LimitExceeded,
+    /// An error that was created with [`Error::new`] or [`Error::with_loc`].
+    Custom,
 }
 
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let start = self.index;
-        let end = self.index + self.data.len();
-
-        if &self.data == "EOF" {
-            write!(
-                f,
-                "ERROR@{}:{} {:?} {}",
-                start, start, self.message, self.data
-            )
-        } else {
-            write!(
-                f,
-                "ERROR@{}:{} {:?} {}",
-                start, end, self.message, self.data
-            )
-        }
+        let Range { start, end } = self.span();
+
+        write!(
+            f,
+            "ERROR@{}:{} {:?} {}",
+            start, end, self.message, self.data
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ErrorKind, LineCol, Parser, TokenKind};
+
+    fn kinds(input: &str) -> Vec<ErrorKind> {
+        let ast = Parser::new(input).parse();
+        ast.errors().map(|err| err.kind().clone()).collect()
+    }
+
+    #[test]
+    fn it_sets_lexer_error_kinds() {
+        assert_eq!(kinds("query { a %}"), vec![ErrorKind::UnexpectedCharacter]);
+        assert_eq!(
+            kinds("query { a(b: \"c) }")[0],
+            ErrorKind::UnterminatedString
+        );
+        assert_eq!(kinds("query { a(b: 1.2.3) }")[0], ErrorKind::InvalidNumber);
+        assert_eq!(kinds("query { ..a }")[0], ErrorKind::UnterminatedSpread);
+    }
+
+    #[test]
+    fn it_sets_parser_error_kinds() {
+        assert_eq!(
+            kinds("type Query { me: }"),
+            vec![ErrorKind::UnexpectedToken {
+                expected: vec![TokenKind::Name, TokenKind::LBracket]
+            }]
+        );
+        assert_eq!(
+            kinds("fragment on on User { id }")[0],
+            ErrorKind::InvalidName
+        );
+        assert_eq!(
+            kinds("directive @a on NOWHERE")[0],
+            ErrorKind::InvalidDirectiveLocation
+        );
+        assert_eq!(kinds("foo query { a }"), vec![ErrorKind::MissingDefinition]);
+        assert_eq!(kinds("extend Cat"), vec![ErrorKind::InvalidExtension]);
+    }
+
+    #[test]
+    fn it_gives_errors_at_the_end_of_the_input_an_empty_location() {
+        let ast = Parser::new("query { me").parse();
+        let err = ast.errors().next().unwrap();
+        assert_eq!(err.data(), "EOF");
+        assert_eq!(
+            err.location(),
+            Some(LineCol { line: 0, col: 10 }..LineCol { line: 0, col: 10 })
+        );
+        assert_eq!(
+            format!("{:?}", err),
+            r#"ERROR@10:10 "expected R_CURLY, got EOF" EOF"#
+        );
+
+        // A Name that reads `EOF` is not the end of the input.
+        let ast = Parser::new("query Q EOF { me }").parse();
+        let err = ast.errors().next().unwrap();
+        assert_eq!(err.data(), "EOF");
+        assert_eq!(
+            err.location(),
+            Some(LineCol { line: 0, col: 8 }..LineCol { line: 0, col: 11 })
+        );
+    }
+
+    #[test]
+    fn it_sets_custom_error_kind() {
+        let err = crate::Error::new("custom", "data".to_string());
+        assert_eq!(err.kind(), &ErrorKind::Custom);
     }
 }
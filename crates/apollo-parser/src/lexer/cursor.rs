@@ -3,6 +3,7 @@ use std::str::Chars;
 use crate::Error;
 /// Peekable iterator over a char sequence.
 pub(crate) struct Cursor<'a> {
+    input: &'a str,
     chars: Chars<'a>,
     pub(crate) err: Option<Error>,
 }
@@ -10,6 +11,7 @@ pub(crate) struct Cursor<'a> {
 impl<'a> Cursor<'a> {
     pub(crate) fn new(input: &'a str) -> Cursor<'a> {
         Cursor {
+            input,
             chars: input.chars(),
             err: None,
         }
@@ -46,6 +48,23 @@ impl<'a> Cursor<'a> {
         Some(c)
     }
 
+    /// Moves past the next `n` characters.
+    pub(crate) fn bump_n(&mut self, n: usize) {
+        for _ in 0..n {
+            self.bump();
+        }
+    }
+
+    /// Get the input that is yet to be consumed.
+    pub(crate) fn remaining(&self) -> &'a str {
+        self.chars.as_str()
+    }
+
+    /// Get the first `len` bytes of the input.
+    pub(crate) fn slice(&self, len: usize) -> &'a str {
+        &self.input[..len]
+    }
+
     /// Get current error object in the cursor.
     pub(crate) fn err(&mut self) -> Option<Error> {
         self.err.clone()
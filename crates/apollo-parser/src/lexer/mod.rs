@@ -2,72 +2,168 @@ mod cursor;
 mod token;
 mod token_kind;
 
-use std::slice::Iter;
-
-use crate::{lexer::cursor::Cursor, Error};
+use crate::{lexer::cursor::Cursor, Error, ErrorKind, LimitTracker};
 
 pub use token::Token;
 pub use token_kind::TokenKind;
-/// Parses tokens into text.
-pub(crate) struct Lexer {
-    tokens: Vec<Token>,
-    errors: Vec<Error>,
+
+/// Lexes GraphQL input into tokens.
+///
+/// `Lexer` is an iterator: tokens are produced on demand, and borrow their data
+/// from the input. Lexing errors are produced in place of the invalid input,
+/// and lexing continues after them. After the last token, the lexer produces a
+/// single EOF token.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{Lexer, TokenKind};
+///
+/// let kinds: Vec<TokenKind> = Lexer::new("{ a, b }")
+///     .skip_trivia()
+///     .filter_map(Result::ok)
+///     .map(|token| token.kind())
+///     .collect();
+///
+/// assert_eq!(
+///     kinds,
+///     [
+///         TokenKind::LCurly,
+///         TokenKind::Name,
+///         TokenKind::Name,
+///         TokenKind::RCurly,
+///         TokenKind::Eof,
+///     ]
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Lexer<'a> {
+    /// The input that is yet to be lexed.
+    input: &'a str,
+    /// Offset of `input` in the original input.
+    index: usize,
+    finished: bool,
+    skip_trivia: bool,
+    token_limit: LimitTracker,
 }
 
-impl Lexer {
+impl<'a> Lexer<'a> {
     /// Create a new instance of `Lexer`.
-    pub fn new(mut input: &str) -> Self {
+    pub fn new(input: &'a str) -> Self {
+        Self::with_limit(input, usize::MAX)
+    }
+
+    /// Create a new instance of `Lexer` that stops lexing after `limit`
+    /// tokens.
+    pub fn with_limit(input: &'a str, limit: usize) -> Self {
+        Self {
+            input,
+            index: 0,
+            finished: false,
+            skip_trivia: false,
+            token_limit: LimitTracker::new(limit),
+        }
+    }
+
+    /// Skip whitespace, comments and commas instead of producing tokens for
+    /// them.
+    ///
+    /// Skipped tokens still count towards the token limit.
+    pub fn skip_trivia(mut self) -> Self {
+        self.skip_trivia = true;
+        self
+    }
+
+    /// Lex the whole input, and return the tokens and the errors separately.
+    pub(crate) fn lex(self) -> (Vec<Token<'a>>, Vec<Error>) {
         let mut tokens = Vec::new();
         let mut errors = Vec::new();
 
-        let mut index = 0;
+        for item in self {
+            match item {
+                Ok(token) => tokens.push(token),
+                Err(err) => errors.push(err),
+            }
+        }
 
-        while !input.is_empty() {
-            let old_input = input;
+        (tokens, errors)
+    }
 
-            if old_input.len() == input.len() {
-                let mut c = Cursor::new(input);
-                let r = c.advance();
+    /// Skip the rest of the input. The next token is EOF.
+    pub(crate) fn stop(&mut self) {
+        self.input = "";
+    }
 
-                match r {
-                    Ok(mut token) => {
-                        token.index = index;
-                        index += token.data.len();
+    /// Get the lexer's token limit tracker.
+    pub(crate) fn token_limit(&self) -> LimitTracker {
+        self.token_limit
+    }
+}
 
-                        input = &input[token.data.len()..];
-                        tokens.push(token);
-                    }
-                    Err(mut err) => {
-                        err.index = index;
-                        index += err.data.len();
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, Error>;
 
-                        input = &input[err.data.len()..];
-                        errors.push(err);
-                    }
-                }
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.advance()?;
+            match &item {
+                Ok(token) if self.skip_trivia && is_trivia(token.kind) => continue,
+                _ => return Some(item),
             }
         }
+    }
+}
 
-        let mut eof = Token::new(TokenKind::Eof, String::from("EOF"));
-        eof.index = index;
-        tokens.push(eof);
+impl<'a> Lexer<'a> {
+    fn advance(&mut self) -> Option<Result<Token<'a>, Error>> {
+        if self.finished {
+            return None;
+        }
 
-        Self { tokens, errors }
-    }
+        if self.input.is_empty() {
+            self.finished = true;
+            let mut eof = Token::new(TokenKind::Eof, "EOF");
+            eof.index = self.index;
+            return Some(Ok(eof));
+        }
 
-    /// Get a reference to the lexer's tokens.
-    pub(crate) fn tokens(&self) -> &[Token] {
-        self.tokens.as_slice()
-    }
+        let mut c = Cursor::new(self.input);
+        match c.advance() {
+            Ok(mut token) => {
+                if self.token_limit.check_and_increment() {
+                    let err = Error::with_loc(
+                        "token limit reached, aborting lexing",
+                        token.data.to_string(),
+                        self.index,
+                    )
+                    .with_kind(ErrorKind::LimitExceeded);
+                    self.stop();
+                    return Some(Err(err));
+                }
 
-    /// Get a reference to the lexer's tokens.
-    pub(crate) fn errors(&self) -> Iter<'_, Error> {
-        self.errors.iter()
+                token.index = self.index;
+                self.index += token.data.len();
+                self.input = &self.input[token.data.len()..];
+                Some(Ok(token))
+            }
+            Err(mut err) => {
+                err.index = self.index;
+                self.index += err.data.len();
+                self.input = &self.input[err.data.len()..];
+                Some(Err(err))
+            }
+        }
     }
 }
 
-impl Cursor<'_> {
-    fn advance(&mut self) -> Result<Token, Error> {
+fn is_trivia(kind: TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Whitespace | TokenKind::Comment | TokenKind::Comma
+    )
+}
+
+impl<'a> Cursor<'a> {
+    fn advance(&mut self) -> Result<Token<'a>, Error> {
         let first_char = self.bump().unwrap();
 
         match first_char {
@@ -78,177 +174,268 @@ impl Cursor<'_> {
             c if is_ident_char(c) => self.ident(c),
             c @ '-' | c @ '+' => self.number(c),
             c if is_digit_char(c) => self.number(c),
-            '!' => Ok(Token::new(TokenKind::Bang, first_char.into())),
-            '$' => Ok(Token::new(TokenKind::Dollar, first_char.into())),
-            '&' => Ok(Token::new(TokenKind::Amp, first_char.into())),
-            '(' => Ok(Token::new(TokenKind::LParen, first_char.into())),
-            ')' => Ok(Token::new(TokenKind::RParen, first_char.into())),
-            ':' => Ok(Token::new(TokenKind::Colon, first_char.into())),
-            ',' => Ok(Token::new(TokenKind::Comma, first_char.into())),
-            '=' => Ok(Token::new(TokenKind::Eq, first_char.into())),
-            '@' => Ok(Token::new(TokenKind::At, first_char.into())),
-            '[' => Ok(Token::new(TokenKind::LBracket, first_char.into())),
-            ']' => Ok(Token::new(TokenKind::RBracket, first_char.into())),
-            '{' => Ok(Token::new(TokenKind::LCurly, first_char.into())),
-            '|' => Ok(Token::new(TokenKind::Pipe, first_char.into())),
-            '}' => Ok(Token::new(TokenKind::RCurly, first_char.into())),
-            c => Err(Error::new("Unexpected character", c.to_string())),
+            '!' => Ok(Token::new(TokenKind::Bang, self.slice(1))),
+            '$' => Ok(Token::new(TokenKind::Dollar, self.slice(1))),
+            '&' => Ok(Token::new(TokenKind::Amp, self.slice(1))),
+            '(' => Ok(Token::new(TokenKind::LParen, self.slice(1))),
+            ')' => Ok(Token::new(TokenKind::RParen, self.slice(1))),
+            ':' => Ok(Token::new(TokenKind::Colon, self.slice(1))),
+            ',' => Ok(Token::new(TokenKind::Comma, self.slice(1))),
+            '=' => Ok(Token::new(TokenKind::Eq, self.slice(1))),
+            '@' => Ok(Token::new(TokenKind::At, self.slice(1))),
+            '[' => Ok(Token::new(TokenKind::LBracket, self.slice(1))),
+            ']' => Ok(Token::new(TokenKind::RBracket, self.slice(1))),
+            '{' => Ok(Token::new(TokenKind::LCurly, self.slice(1))),
+            '|' => Ok(Token::new(TokenKind::Pipe, self.slice(1))),
+            '}' => Ok(Token::new(TokenKind::RCurly, self.slice(1))),
+            c => Err(Error::new("Unexpected character", c.to_string())
+                .with_kind(ErrorKind::UnexpectedCharacter)),
         }
     }
 
-    fn string_value(&mut self, first_char: char) -> Result<Token, Error> {
-        // TODO @lrlna: consider using a 'terminated' bool to store whether a string
-        // character or block character are terminated (rust's lexer does this).
-        let mut buf = String::new();
-        buf.push(first_char); // the first " we already matched on
+    fn string_value(&mut self, first_char: char) -> Result<Token<'a>, Error> {
+        let mut len = first_char.len_utf8();
 
-        let c = match self.bump() {
-            None => {
-                return Err(Error::new(
-                    "unexpected end of data while lexing string value",
-                    "\"".to_string(),
-                ));
+        if ('"', '"') == (self.first(), self.second()) {
+            self.bump();
+            self.bump();
+            return self.block_string_value(len + 2);
+        }
+
+        loop {
+            let c = self.first();
+            if self.is_eof() || is_line_terminator(c) {
+                return Err(
+                    Error::new("unterminated string value", self.slice(len).to_string())
+                        .with_kind(ErrorKind::UnterminatedString),
+                );
             }
-            Some(c) => c,
-        };
 
-        match c {
-            '"' => self.block_string_value(buf, c),
-            t => {
-                buf.push(t);
-
-                while !self.is_eof() {
-                    let c = self.bump().unwrap();
-                    if c == '"' {
-                        buf.push(c);
-                        break;
-                    } else if is_escaped_char(c)
-                        || is_source_char(c) && c != '\\' && c != '"' && !is_line_terminator(c)
-                    {
-                        buf.push(c);
-                    // TODO @lrlna: this should error if c == \ or has a line terminator
-                    } else {
-                        break;
-                    }
+            match c {
+                '"' => {
+                    len += c.len_utf8();
+                    self.bump();
+                    break;
+                }
+                '\\' => len += self.escape_sequence(),
+                c => {
+                    len += c.len_utf8();
+                    self.bump();
                 }
-
-                Ok(Token::new(TokenKind::StringValue, buf))
             }
         }
+
+        if let Some(mut err) = self.err() {
+            err.data = self.slice(len).into();
+            return Err(err);
+        }
+
+        Ok(Token::new(TokenKind::StringValue, self.slice(len)))
     }
 
-    fn block_string_value(&mut self, mut buf: String, char: char) -> Result<Token, Error> {
-        buf.push(char); // the second " we already matched on
+    /// Lex an escape sequence in a string value, starting at its `\`, and
+    /// return its length. Invalid escape sequences add an error, but lexing
+    /// continues until the end of the string.
+    fn escape_sequence(&mut self) -> usize {
+        let escape = self.remaining();
+        self.bump();
+
+        let c = self.first();
+        if is_escaped_char(c) {
+            self.bump();
+            return 2;
+        }
+        if c == 'u' {
+            self.bump();
+            return self.unicode_escape(escape);
+        }
 
-        let c = match self.bump() {
+        // A line terminator or EOF ends the string, so it's not part of the
+        // escape sequence.
+        let mut len = 1;
+        if !self.is_eof() && !is_line_terminator(c) {
+            len += c.len_utf8();
+            self.bump();
+        }
+        self.add_escape_err("invalid escape sequence", &escape[..len]);
+        len
+    }
+
+    /// Lex the rest of a `\uXXXX` or `\u{X...}` escape sequence. `escape`
+    /// starts at the `\`.
+    fn unicode_escape(&mut self, escape: &'a str) -> usize {
+        let mut len = 2;
+
+        if self.first() == '{' {
+            len += 1;
+            self.bump();
+
+            let mut code_point: u32 = 0;
+            let mut has_digit = false;
+            while let Some(digit) = self.first().to_digit(16) {
+                code_point = code_point.saturating_mul(16).saturating_add(digit);
+                has_digit = true;
+                len += 1;
+                self.bump();
+            }
+
+            if !has_digit || self.first() != '}' {
+                self.add_escape_err("invalid unicode escape sequence", &escape[..len]);
+                return len;
+            }
+            len += 1;
+            self.bump();
+
+            // Surrogates can only be escaped in pairs of fixed width escapes.
+            if char::from_u32(code_point).is_none() {
+                self.add_escape_err("invalid unicode escape sequence", &escape[..len]);
+            }
+            return len;
+        }
+
+        let code_point = match hex_code_point(&escape[len..]) {
+            Some(code_point) => code_point,
             None => {
-                return Ok(Token::new(TokenKind::StringValue, buf));
+                self.add_escape_err("invalid unicode escape sequence", &escape[..len]);
+                return len;
             }
-            Some(c) => c,
         };
+        len += 4;
+        self.bump_n(4);
+
+        match code_point {
+            0xD800..=0xDBFF => {
+                // A leading surrogate must be followed by an escaped trailing
+                // surrogate.
+                let trailing = escape[len..]
+                    .strip_prefix("\\u")
+                    .and_then(hex_code_point)
+                    .filter(|code_point| (0xDC00..=0xDFFF).contains(code_point));
+                if trailing.is_some() {
+                    len += 6;
+                    self.bump_n(6);
+                } else {
+                    self.add_escape_err("unpaired surrogate in unicode escape", &escape[..len]);
+                }
+            }
+            0xDC00..=0xDFFF => {
+                self.add_escape_err("unpaired surrogate in unicode escape", &escape[..len]);
+            }
+            _ => {}
+        }
 
-        if let first_char @ '"' = c {
-            buf.push(first_char);
+        len
+    }
 
-            while !self.is_eof() {
+    fn add_escape_err(&mut self, message: &str, escape: &str) {
+        // Only the first invalid escape sequence of a string is reported.
+        if self.err.is_none() {
+            self.add_err(
+                Error::new(format!("{} `{}`", message, escape), escape.to_string())
+                    .with_kind(ErrorKind::InvalidEscapeSequence),
+            );
+        }
+    }
+
+    fn block_string_value(&mut self, mut len: usize) -> Result<Token<'a>, Error> {
+        loop {
+            let rest = self.remaining();
+            if rest.is_empty() {
+                return Err(Error::new(
+                    "unterminated block string value",
+                    self.slice(len).to_string(),
+                )
+                .with_kind(ErrorKind::UnterminatedString));
+            }
+
+            if rest.starts_with(r#"\""""#) {
+                len += 4;
+                self.bump_n(4);
+            } else if rest.starts_with(r#"""""#) {
+                len += 3;
+                self.bump_n(3);
+                break;
+            } else {
                 let c = self.bump().unwrap();
-                if c == '"' {
-                    buf.push(c);
-                    if ('"', '"') == (self.first(), self.second()) {
-                        buf.push(self.first());
-                        buf.push(self.second());
-                        self.bump();
-                        self.bump();
-                        break;
-                    }
-                } else if is_source_char(c) {
-                    buf.push(c);
-                } else {
-                    break;
-                }
+                len += c.len_utf8();
             }
         }
 
-        Ok(Token::new(TokenKind::StringValue, buf))
+        Ok(Token::new(TokenKind::StringValue, self.slice(len)))
     }
 
-    fn comment(&mut self, first_char: char) -> Result<Token, Error> {
-        let mut buf = String::new();
-        buf.push(first_char);
+    fn comment(&mut self, first_char: char) -> Result<Token<'a>, Error> {
+        let mut len = first_char.len_utf8();
 
         while !self.is_eof() {
             let first = self.bump().unwrap();
             if !is_line_terminator(first) {
-                buf.push(first);
+                len += first.len_utf8();
             } else {
                 break;
             }
         }
 
-        Ok(Token::new(TokenKind::Comment, buf))
+        Ok(Token::new(TokenKind::Comment, self.slice(len)))
     }
 
-    fn spread_operator(&mut self, first_char: char) -> Result<Token, Error> {
-        let mut buf = String::new();
-        buf.push(first_char);
+    fn spread_operator(&mut self, first_char: char) -> Result<Token<'a>, Error> {
+        let mut len = first_char.len_utf8();
 
         match (self.first(), self.second()) {
             ('.', '.') => {
-                buf.push('.');
-                buf.push('.');
+                len += '.'.len_utf8();
+                len += '.'.len_utf8();
                 self.bump();
                 self.bump();
             }
-            (a, b) => self.add_err(Error::new(
-                "Unterminated spread operator",
-                format!(".{}{}", a, b),
-            )),
+            (a, b) => self.add_err(
+                Error::new("Unterminated spread operator", format!(".{}{}", a, b))
+                    .with_kind(ErrorKind::UnterminatedSpread),
+            ),
         }
 
         if let Some(mut err) = self.err() {
-            err.data = buf;
+            err.data = self.slice(len).into();
             return Err(err);
         }
 
-        Ok(Token::new(TokenKind::Spread, buf))
+        Ok(Token::new(TokenKind::Spread, self.slice(len)))
     }
 
-    fn whitespace(&mut self, first_char: char) -> Result<Token, Error> {
-        let mut buf = String::new();
-        buf.push(first_char);
+    fn whitespace(&mut self, first_char: char) -> Result<Token<'a>, Error> {
+        let mut len = first_char.len_utf8();
 
         while !self.is_eof() {
             let first = self.bump().unwrap();
             if is_whitespace(first) {
-                buf.push(first);
+                len += first.len_utf8();
             } else {
                 break;
             }
         }
 
-        Ok(Token::new(TokenKind::Whitespace, buf))
+        Ok(Token::new(TokenKind::Whitespace, self.slice(len)))
     }
 
-    fn ident(&mut self, first_char: char) -> Result<Token, Error> {
-        let mut buf = String::new();
-        buf.push(first_char);
+    fn ident(&mut self, first_char: char) -> Result<Token<'a>, Error> {
+        let mut len = first_char.len_utf8();
 
         while !self.is_eof() {
             let first = self.first();
             if is_ident_char(first) || is_digit_char(first) {
-                buf.push(first);
+                len += first.len_utf8();
                 self.bump();
             } else {
                 break;
             }
         }
 
-        Ok(Token::new(TokenKind::Name, buf))
+        Ok(Token::new(TokenKind::Name, self.slice(len)))
     }
 
-    fn number(&mut self, first_digit: char) -> Result<Token, Error> {
-        let mut buf = String::new();
-        buf.push(first_digit);
+    fn number(&mut self, first_digit: char) -> Result<Token<'a>, Error> {
+        let mut len = first_digit.len_utf8();
 
         let mut has_exponent = false;
         let mut has_fractional = false;
@@ -258,55 +445,70 @@ impl Cursor<'_> {
             let first = self.first();
             match first {
                 'e' | 'E' => {
-                    buf.push(first);
+                    len += first.len_utf8();
                     self.bump();
                     if !has_digit {
-                        self.add_err(Error::new(
-                            format!("Unexpected character `{}` in exponent", first),
-                            first.to_string(),
-                        ));
+                        self.add_err(
+                            Error::new(
+                                format!("Unexpected character `{}` in exponent", first),
+                                first.to_string(),
+                            )
+                            .with_kind(ErrorKind::InvalidNumber),
+                        );
                     }
                     if has_exponent {
-                        self.add_err(Error::new(
-                            format!("Unexpected character `{}`", first),
-                            first.to_string(),
-                        ));
+                        self.add_err(
+                            Error::new(
+                                format!("Unexpected character `{}`", first),
+                                first.to_string(),
+                            )
+                            .with_kind(ErrorKind::InvalidNumber),
+                        );
                     }
                     has_exponent = true;
                     if matches!(self.first(), '+' | '-') {
-                        buf.push(self.first());
+                        len += self.first().len_utf8();
                         self.bump();
                     }
                 }
                 '.' => {
-                    buf.push(first);
+                    len += first.len_utf8();
                     self.bump();
 
                     if !has_digit {
-                        self.add_err(Error::new(
-                            format!("Unexpected character `{}` before a digit", first),
-                            first.to_string(),
-                        ));
+                        self.add_err(
+                            Error::new(
+                                format!("Unexpected character `{}` before a digit", first),
+                                first.to_string(),
+                            )
+                            .with_kind(ErrorKind::InvalidNumber),
+                        );
                     }
 
                     if has_fractional {
-                        self.add_err(Error::new(
-                            format!("Unexpected character `{}`", first),
-                            first.to_string(),
-                        ));
+                        self.add_err(
+                            Error::new(
+                                format!("Unexpected character `{}`", first),
+                                first.to_string(),
+                            )
+                            .with_kind(ErrorKind::InvalidNumber),
+                        );
                     }
 
                     if has_exponent {
-                        self.add_err(Error::new(
-                            format!("Unexpected character `{}`", first),
-                            first.to_string(),
-                        ));
+                        self.add_err(
+                            Error::new(
+                                format!("Unexpected character `{}`", first),
+                                first.to_string(),
+                            )
+                            .with_kind(ErrorKind::InvalidNumber),
+                        );
                     }
 
                     has_fractional = true;
                 }
                 first if is_digit_char(first) => {
-                    buf.push(first);
+                    len += first.len_utf8();
                     self.bump();
                     has_digit = true;
                 }
@@ -315,14 +517,14 @@ impl Cursor<'_> {
         }
 
         if let Some(mut err) = self.err() {
-            err.data = buf;
+            err.data = self.slice(len).into();
             return Err(err);
         }
 
         if has_exponent || has_fractional {
-            Ok(Token::new(TokenKind::Float, buf))
+            Ok(Token::new(TokenKind::Float, self.slice(len)))
         } else {
-            Ok(Token::new(TokenKind::Int, buf))
+            Ok(Token::new(TokenKind::Int, self.slice(len)))
         }
     }
 }
@@ -364,7 +566,7 @@ fn is_line_terminator(c: char) -> bool {
 }
 
 fn is_digit_char(c: char) -> bool {
-    matches!(c, '0'..='9')
+    c.is_ascii_digit()
 }
 
 // EscapedCharacter
@@ -373,10 +575,13 @@ fn is_escaped_char(c: char) -> bool {
     matches!(c, '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')
 }
 
-// SourceCharacter
-//     /[\u0009\u000A\u000D\u0020-\uFFFF]/
-fn is_source_char(c: char) -> bool {
-    matches!(c, '\t' | '\r' | '\n' | '\u{0020}'..='\u{FFFF}')
+/// Parse the four hex digits at the start of `s`.
+pub(crate) fn hex_code_point(s: &str) -> Option<u32> {
+    let digits = s.get(..4)?;
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(digits, 16).ok()
 }
 
 #[cfg(test)]
@@ -391,8 +596,104 @@ mod test {
         """
         name: String @join__field(graph: PRODUCTS)
         "#;
-        let lexer_1 = Lexer::new(gql_1);
-        dbg!(lexer_1.tokens);
-        dbg!(lexer_1.errors);
+        let (tokens, errors) = Lexer::new(gql_1).lex();
+        dbg!(tokens);
+        dbg!(errors);
+    }
+
+    #[test]
+    fn it_lexes_on_demand() {
+        let mut lexer = Lexer::new("query { a }");
+        let token = lexer.next().unwrap().unwrap();
+        assert_eq!(token.kind(), TokenKind::Name);
+        assert_eq!(token.data(), "query");
+        // Only the first token has been consumed from the input.
+        assert_eq!(lexer.input, " { a }");
+
+        let rest: Vec<_> = lexer.map(|token| token.unwrap().kind()).collect();
+        assert_eq!(rest.len(), 7);
+        assert_eq!(rest.last(), Some(&TokenKind::Eof));
+    }
+
+    #[test]
+    fn it_borrows_token_data_from_the_input() {
+        let input = String::from("query { a }");
+        let tokens: Vec<Token<'_>> = {
+            let lexer = Lexer::new(&input);
+            lexer.map(|token| token.unwrap()).collect()
+        };
+
+        // The tokens outlive the lexer, and point into the input.
+        let range = input.as_bytes().as_ptr_range();
+        for token in &tokens[..tokens.len() - 1] {
+            assert!(range.contains(&token.data().as_ptr()));
+            assert_eq!(
+                &input[token.index()..token.index() + token.data().len()],
+                token.data()
+            );
+        }
+    }
+
+    #[test]
+    fn it_produces_eof_once() {
+        let mut lexer = Lexer::new("a ");
+        assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::Name);
+        assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::Whitespace);
+
+        let eof = lexer.next().unwrap().unwrap();
+        assert_eq!(eof.kind(), TokenKind::Eof);
+        assert_eq!(eof.index(), 2);
+        assert!(lexer.next().is_none());
+        assert!(lexer.next().is_none());
+
+        let tokens: Vec<_> = Lexer::new("").map(|token| token.unwrap()).collect();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].kind(), TokenKind::Eof);
+        assert_eq!(tokens[0].index(), 0);
+    }
+
+    #[test]
+    fn it_produces_eof_after_the_token_limit() {
+        let mut lexer = Lexer::with_limit("a b c", 2);
+        assert_eq!(lexer.next().unwrap().unwrap().data(), "a");
+        assert_eq!(lexer.next().unwrap().unwrap().data(), " ");
+
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::LimitExceeded);
+        // The rest of the input is skipped.
+        assert_eq!(lexer.next().unwrap().unwrap().kind(), TokenKind::Eof);
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    fn it_skips_trivia() {
+        let input = "# comment\n{ a, b }";
+        let tokens: Vec<_> = Lexer::new(input)
+            .skip_trivia()
+            .map(|token| token.unwrap())
+            .map(|token| (token.kind(), token.data(), token.index()))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (TokenKind::LCurly, "{", 10),
+                (TokenKind::Name, "a", 12),
+                (TokenKind::Name, "b", 15),
+                (TokenKind::RCurly, "}", 17),
+                (TokenKind::Eof, "EOF", 18),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_produces_errors_in_place() {
+        let items: Vec<_> = Lexer::new("a ? b").skip_trivia().collect();
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0].as_ref().unwrap().data(), "a");
+        assert_eq!(
+            items[1].as_ref().unwrap_err().kind(),
+            &ErrorKind::UnexpectedCharacter
+        );
+        assert_eq!(items[2].as_ref().unwrap().data(), "b");
     }
 }
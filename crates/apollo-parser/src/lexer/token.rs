@@ -3,15 +3,17 @@ use std::fmt;
 use crate::TokenKind;
 
 /// A token generated by the lexer.
-#[derive(Clone)]
-pub struct Token {
+///
+/// The token's data is borrowed from the lexer's input.
+#[derive(Clone, Copy)]
+pub struct Token<'a> {
     pub(crate) kind: TokenKind,
-    pub(crate) data: String,
+    pub(crate) data: &'a str,
     pub(crate) index: usize,
 }
 
-impl Token {
-    pub(crate) fn new(kind: TokenKind, data: String) -> Self {
+impl<'a> Token<'a> {
+    pub(crate) fn new(kind: TokenKind, data: &'a str) -> Self {
         Self {
             kind,
             data,
@@ -25,8 +27,8 @@ impl Token {
     }
 
     /// Get a reference to the token's data.
-    pub fn data(&self) -> &str {
-        self.data.as_str()
+    pub fn data(&self) -> &'a str {
+        self.data
     }
 
     /// Get a reference to the token's loc.
@@ -35,7 +37,7 @@ impl Token {
     }
 }
 
-impl fmt::Debug for Token {
+impl fmt::Debug for Token<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let start = self.index;
         let end = self.index + self.data.len();
@@ -22,9 +22,11 @@
 //! * Error resilience
 //!   * lexing and parsing does not fail or `panic` if a lexical or a syntax error is found
 //! * The AST produced is lossless, meaning all ignored tokens like whitespace
-//! and commas are kept in the tree
-//! * GraphQL lexer
+//!   and commas are kept in the tree
+//! * GraphQL lexer, usable on its own as a streaming iterator over tokens
 //! * GraphQL parser
+//! * Line and column positions for errors and nodes, in UTF-8 and UTF-16
+//! * Recursion and token limits for parsing untrusted input
 //!
 //! ## Getting started
 //! Add this to your `Cargo.toml` to start using `apollo-parser`:
@@ -173,14 +175,16 @@ mod tests;
 
 pub mod ast;
 mod error;
+mod limit;
+mod line_index;
 mod parser;
 
-#[cfg(test)]
-pub(crate) use crate::lexer::Lexer;
-pub(crate) use crate::lexer::{Token, TokenKind};
 pub(crate) use crate::parser::{
     SyntaxElement, SyntaxKind, SyntaxNode, SyntaxNodeChildren, SyntaxToken, TokenText,
 };
 
-pub use crate::error::Error;
-pub use crate::parser::{Parser, SyntaxTree};
+pub use crate::error::{Error, ErrorKind};
+pub use crate::lexer::{Lexer, Token, TokenKind};
+pub use crate::limit::{LimitTracker, ParserLimits};
+pub use crate::line_index::{LineCol, LineColUtf16, LineIndex};
+pub use crate::parser::{ParseMode, Parser, SyntaxTree, TextEdit};
@@ -0,0 +1,213 @@
+/// Resource limits applied while lexing and parsing.
+///
+/// Nested selection sets, list and object values, and list types are parsed
+/// recursively, so deeply nested input can otherwise overflow the stack. The
+/// token limit caps the amount of work done on very large inputs. Once a
+/// limit is reached, lexing or parsing stops and an error with
+/// [`ErrorKind::LimitExceeded`](crate::ErrorKind::LimitExceeded) is recorded.
+///
+/// Limits are opt-in: the default limits, used by `Parser::new`, are
+/// unbounded. Set a recursion limit when parsing untrusted input.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{Parser, ParserLimits};
+///
+/// let limits = ParserLimits {
+///     recursion_limit: 2,
+///     token_limit: 100,
+/// };
+/// let ast = Parser::with_limits("query { a { b { c } } }", limits).parse();
+///
+/// assert_eq!(ast.errors().len(), 1);
+/// assert_eq!(ast.recursion_limit().high(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// Maximum nesting of selection sets, list and object values, and list
+    /// types.
+    pub recursion_limit: usize,
+    /// Maximum number of tokens the lexer produces, including whitespace and
+    /// comments.
+    pub token_limit: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        Self {
+            recursion_limit: usize::MAX,
+            token_limit: usize::MAX,
+        }
+    }
+}
+
+/// Tracks how close a `SyntaxTree` got to one of its `ParserLimits`.
+#[derive(Debug, Clone, Copy)]
+pub struct LimitTracker {
+    current: usize,
+    high: usize,
+    limit: usize,
+}
+
+impl LimitTracker {
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            current: 0,
+            high: 0,
+            limit,
+        }
+    }
+
+    /// Get the highest value reached. This is above the limit if the limit
+    /// was exceeded.
+    pub fn high(&self) -> usize {
+        self.high
+    }
+
+    /// Get the configured limit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Check whether the limit was exceeded.
+    pub fn limited(&self) -> bool {
+        self.high > self.limit
+    }
+
+    /// Increment the current value, and return `true` if this exceeds the
+    /// limit.
+    pub(crate) fn check_and_increment(&mut self) -> bool {
+        self.current += 1;
+        self.high = self.high.max(self.current);
+        self.current > self.limit
+    }
+
+    pub(crate) fn decrement(&mut self) {
+        self.current -= 1;
+    }
+
+    /// Create a tracker that already reached `high`.
+    pub(crate) fn with_high(limit: usize, high: usize) -> Self {
+        Self {
+            current: 0,
+            high,
+            limit,
+        }
+    }
+}
+
+// The current value is only meaningful while parsing, so two trackers are
+// equal if they reached the same value for the same limit.
+impl PartialEq for LimitTracker {
+    fn eq(&self, other: &Self) -> bool {
+        self.high == other.high && self.limit == other.limit
+    }
+}
+
+impl Eq for LimitTracker {}
+
+#[cfg(test)]
+mod test {
+    use crate::{ErrorKind, Parser, ParserLimits};
+
+    #[test]
+    fn it_stops_at_the_recursion_limit() {
+        let limits = ParserLimits {
+            recursion_limit: 10,
+            token_limit: usize::MAX,
+        };
+        let cases = [
+            format!("query {}{}", "{ a ".repeat(100), "}".repeat(100)),
+            format!("query {{ a(b: {}1{}) }}", "[".repeat(100), "]".repeat(100)),
+            format!(
+                "query {{ a(b: {}1{}) }}",
+                "{c: ".repeat(100),
+                "}".repeat(100)
+            ),
+            format!(
+                "type Query {{ a: {}Int{} }}",
+                "[".repeat(100),
+                "]".repeat(100)
+            ),
+        ];
+
+        for input in cases {
+            let ast = Parser::with_limits(&input, limits).parse();
+            let errors: Vec<_> = ast.errors().collect();
+            assert_eq!(errors.len(), 1, "{}", input);
+            assert_eq!(errors[0].kind(), &ErrorKind::LimitExceeded);
+            assert_eq!(ast.recursion_limit().high(), 11);
+            assert!(ast.recursion_limit().limited());
+        }
+    }
+
+    #[test]
+    fn it_does_not_overflow_the_stack_on_deeply_nested_input() {
+        let input = format!("query {}{}", "{ a ".repeat(100_000), "}".repeat(100_000));
+        let limits = ParserLimits {
+            recursion_limit: 500,
+            token_limit: usize::MAX,
+        };
+        let ast = Parser::with_limits(&input, limits).parse();
+
+        assert_eq!(ast.errors().len(), 1);
+        assert_eq!(ast.recursion_limit().high(), 501);
+    }
+
+    #[test]
+    fn it_does_not_overflow_the_stack_on_long_lists() {
+        let input = format!(
+            "query {{ {} a(b: 1 {}) }} type Query {{ {} }} enum E {{ {} }}",
+            "a ".repeat(100_000),
+            "c: 1 ".repeat(100_000),
+            "a: Int ".repeat(100_000),
+            "A ".repeat(100_000),
+        );
+        let ast = Parser::new(&input).parse();
+
+        assert_eq!(ast.errors().len(), 0);
+        assert_eq!(ast.recursion_limit().high(), 1);
+    }
+
+    #[test]
+    fn it_does_not_limit_by_default() {
+        let input = format!("query {}{}", "{ a ".repeat(1_000), "}".repeat(1_000));
+        let ast = Parser::new(&input).parse();
+
+        assert_eq!(ast.errors().len(), 0);
+        assert_eq!(ast.recursion_limit().high(), 1_000);
+        assert!(!ast.recursion_limit().limited());
+    }
+
+    #[test]
+    fn it_stops_at_the_token_limit() {
+        let input = "query { a b c d }";
+        let limits = ParserLimits {
+            recursion_limit: 10,
+            token_limit: 8,
+        };
+        let ast = Parser::with_limits(input, limits).parse();
+
+        let errors: Vec<_> = ast.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind(), &ErrorKind::LimitExceeded);
+        assert_eq!(ast.token_limit().high(), 9);
+        // Parsing stops at the last token that was lexed.
+        assert_eq!(ast.document().to_string(), "query { a b ");
+
+        let limits = ParserLimits {
+            recursion_limit: 10,
+            token_limit: 13,
+        };
+        let ast = Parser::with_limits(input, limits).parse();
+        assert_eq!(ast.errors().len(), 0);
+        assert_eq!(ast.token_limit().high(), 13);
+        assert!(!ast.token_limit().limited());
+    }
+
+    #[test]
+    fn it_does_not_hang_on_unterminated_list_values() {
+        let ast = Parser::new("query { a(b: [1 }").parse();
+        assert!(ast.errors().len() > 0);
+    }
+}
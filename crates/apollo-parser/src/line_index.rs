@@ -0,0 +1,262 @@
+//! Conversion between byte offsets and line/column positions.
+//!
+//! The lexer, the parser and rowan all work with byte offsets into the input.
+//! Editors and CI output on the other hand usually want line and column
+//! numbers, and the Language Server Protocol counts columns in UTF-16 code
+//! units. `LineIndex` is built once from the input and answers both questions
+//! without re-scanning the source.
+
+use std::{collections::HashMap, ops::Range};
+
+/// A zero-based line and column position in the input, where the column is
+/// counted in UTF-8 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    /// Zero-based line number.
+    pub line: usize,
+    /// Zero-based column number, in UTF-8 bytes.
+    pub col: usize,
+}
+
+/// A zero-based line and column position in the input, where the column is
+/// counted in UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColUtf16 {
+    /// Zero-based line number.
+    pub line: usize,
+    /// Zero-based column number, in UTF-16 code units.
+    pub col: usize,
+}
+
+/// A multi-byte character on a given line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WideChar {
+    /// Start of the character, in UTF-8 bytes relative to the line start.
+    start: usize,
+    /// End of the character, in UTF-8 bytes relative to the line start.
+    end: usize,
+}
+
+impl WideChar {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    fn len_utf16(&self) -> usize {
+        // Characters that take up four bytes in UTF-8 are encoded as a
+        // surrogate pair in UTF-16.
+        if self.len() == 4 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// Maps byte offsets in the input to line and column positions.
+///
+/// Line terminators are `\n`, `\r\n` and `\r`, as per the [GraphQL
+/// specification](https://spec.graphql.org/October2021/#LineTerminator).
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{LineCol, LineIndex};
+///
+/// let input = "type Query {\n  café: String\n}";
+/// let index = LineIndex::new(input);
+///
+/// let offset = input.find("String").unwrap();
+/// assert_eq!(index.line_col(offset), LineCol { line: 1, col: 9 });
+/// // `é` is two bytes in UTF-8, but a single UTF-16 code unit.
+/// assert_eq!(index.line_col_utf16(offset).col, 8);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Offset of the start of every line but the first one.
+    newlines: Vec<usize>,
+    /// Multi-byte characters, keyed by line number.
+    wide_chars: HashMap<usize, Vec<WideChar>>,
+    /// Length of the input, in bytes.
+    len: usize,
+}
+
+impl LineIndex {
+    /// Create a new `LineIndex` for a given input.
+    pub fn new(text: &str) -> Self {
+        let mut newlines = Vec::new();
+        let mut wide_chars: HashMap<usize, Vec<WideChar>> = HashMap::new();
+
+        let mut line_start = 0;
+        let mut chars = text.char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '\r' if matches!(chars.peek(), Some((_, '\n'))) => {}
+                '\n' | '\r' => {
+                    line_start = offset + 1;
+                    newlines.push(line_start);
+                }
+                c if !c.is_ascii() => {
+                    let start = offset - line_start;
+                    wide_chars
+                        .entry(newlines.len())
+                        .or_default()
+                        .push(WideChar {
+                            start,
+                            end: start + c.len_utf8(),
+                        });
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            newlines,
+            wide_chars,
+            len: text.len(),
+        }
+    }
+
+    /// Get the number of lines in the input.
+    pub fn line_count(&self) -> usize {
+        self.newlines.len() + 1
+    }
+
+    /// Get the UTF-8 line and column of a byte offset.
+    ///
+    /// Offsets past the end of the input are clamped to the end of the input.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.newlines.partition_point(|&start| start <= offset);
+
+        LineCol {
+            line,
+            col: offset - self.line_start(line),
+        }
+    }
+
+    /// Get the UTF-16 line and column of a byte offset.
+    pub fn line_col_utf16(&self, offset: usize) -> LineColUtf16 {
+        self.to_utf16(self.line_col(offset))
+    }
+
+    /// Get the byte offset of a UTF-8 line and column. Returns `None` if the
+    /// line is out of bounds.
+    pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+        if line_col.line >= self.line_count() {
+            return None;
+        }
+
+        Some((self.line_start(line_col.line) + line_col.col).min(self.len))
+    }
+
+    /// Convert a UTF-8 line and column to a UTF-16 one.
+    pub fn to_utf16(&self, line_col: LineCol) -> LineColUtf16 {
+        let mut col = line_col.col;
+        if let Some(wide_chars) = self.wide_chars.get(&line_col.line) {
+            for c in wide_chars {
+                if c.end <= line_col.col {
+                    col -= c.len() - c.len_utf16();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        LineColUtf16 {
+            line: line_col.line,
+            col,
+        }
+    }
+
+    /// Convert a UTF-16 line and column to a UTF-8 one.
+    pub fn to_utf8(&self, line_col: LineColUtf16) -> LineCol {
+        let mut col = line_col.col;
+        if let Some(wide_chars) = self.wide_chars.get(&line_col.line) {
+            for c in wide_chars {
+                if c.start < col {
+                    col += c.len() - c.len_utf16();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        LineCol {
+            line: line_col.line,
+            col,
+        }
+    }
+
+    /// Get the UTF-8 start and end positions of a byte range.
+    pub fn range(&self, range: Range<usize>) -> Range<LineCol> {
+        self.line_col(range.start)..self.line_col(range.end)
+    }
+
+    /// Get the UTF-16 start and end positions of a byte range.
+    pub fn range_utf16(&self, range: Range<usize>) -> Range<LineColUtf16> {
+        self.line_col_utf16(range.start)..self.line_col_utf16(range.end)
+    }
+
+    fn line_start(&self, line: usize) -> usize {
+        match line {
+            0 => 0,
+            line => self.newlines[line - 1],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_computes_line_and_column() {
+        let input = "query {\n  me\n}";
+        let index = LineIndex::new(input);
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_col(0), LineCol { line: 0, col: 0 });
+        assert_eq!(index.line_col(7), LineCol { line: 0, col: 7 });
+        assert_eq!(index.line_col(8), LineCol { line: 1, col: 0 });
+        assert_eq!(index.line_col(10), LineCol { line: 1, col: 2 });
+        assert_eq!(index.line_col(13), LineCol { line: 2, col: 0 });
+        assert_eq!(index.line_col(100), LineCol { line: 2, col: 1 });
+    }
+
+    #[test]
+    fn it_treats_crlf_and_cr_as_line_terminators() {
+        let input = "a\r\nb\rc";
+        let index = LineIndex::new(input);
+
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.line_col(3), LineCol { line: 1, col: 0 });
+        assert_eq!(index.line_col(5), LineCol { line: 2, col: 0 });
+    }
+
+    #[test]
+    fn it_converts_between_utf8_and_utf16() {
+        let input = "\"é😺\" x\n😺 y";
+        let index = LineIndex::new(input);
+
+        let x = input.find('x').unwrap();
+        assert_eq!(index.line_col(x), LineCol { line: 0, col: 9 });
+        assert_eq!(index.line_col_utf16(x), LineColUtf16 { line: 0, col: 6 });
+
+        let y = input.find('y').unwrap();
+        assert_eq!(index.line_col(y), LineCol { line: 1, col: 5 });
+        assert_eq!(index.line_col_utf16(y), LineColUtf16 { line: 1, col: 3 });
+
+        assert_eq!(index.to_utf8(index.line_col_utf16(x)), index.line_col(x));
+        assert_eq!(index.to_utf8(index.line_col_utf16(y)), index.line_col(y));
+    }
+
+    #[test]
+    fn it_converts_line_and_column_to_offset() {
+        let input = "type Query {\n  me: User\n}";
+        let index = LineIndex::new(input);
+
+        let me = input.find("me").unwrap();
+        assert_eq!(index.offset(index.line_col(me)), Some(me));
+        assert_eq!(index.offset(LineCol { line: 3, col: 0 }), None);
+    }
+}
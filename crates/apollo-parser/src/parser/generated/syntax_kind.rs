@@ -155,6 +155,7 @@ pub enum SyntaxKind {
     DIRECTIVE_LOCATION,
     EXECUTABLE_DIRECTIVE_LOCATION,
     TYPE_SYSTEM_DIRECTIVE_LOCATION,
+    ERROR,
     #[doc(hidden)]
     __LAST,
 }
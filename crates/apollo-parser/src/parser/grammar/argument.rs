@@ -8,21 +8,19 @@ use crate::{
 /// *Argument*:
 ///    Name **:** Value
 pub(crate) fn argument(p: &mut Parser, mut is_argument: bool) {
-    if let Some(TokenKind::Name) = p.peek() {
-        let guard = p.start_node(SyntaxKind::ARGUMENT);
+    while let Some(TokenKind::Name) = p.peek() {
+        let _g = p.start_node(SyntaxKind::ARGUMENT);
         name::name(p);
         if let Some(T![:]) = p.peek() {
             p.bump(S![:]);
             value::value(p);
             is_argument = true;
-            if p.peek().is_some() {
-                guard.finish_node();
-                return argument(p, is_argument);
-            }
+        } else {
+            break;
         }
     }
     if !is_argument {
-        p.err("expected an Argument");
+        p.err_expected(&[TokenKind::Name], "expected an Argument");
     }
 }
 
@@ -34,7 +32,7 @@ pub(crate) fn arguments(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::ARGUMENTS);
     p.bump(S!['(']);
     argument(p, false);
-    p.expect(T![')'], S![')']);
+    p.expect_closing(T![')'], S![')']);
 }
 
 /// See: https://spec.graphql.org/October2021/#ArgumentsDefinition
@@ -42,8 +40,8 @@ pub(crate) fn arguments(p: &mut Parser) {
 /// *ArgumentsDefinition*:
 ///     **(** InputValueDefinition* **)**
 pub(crate) fn arguments_definition(p: &mut Parser) {
-    let _g = p.start_node(SyntaxKind::ARGUMENTS);
+    let _g = p.start_node(SyntaxKind::ARGUMENTS_DEFINITION);
     p.bump(S!['(']);
     input::input_value_definition(p, false);
-    p.expect(T![')'], S![')']);
+    p.expect_closing(T![')'], S![')']);
 }
@@ -1,6 +1,6 @@
 use crate::{
-    parser::grammar::{argument, description, input, name},
-    Parser, SyntaxKind, TokenKind, S, T,
+    parser::grammar::{argument, description, name},
+    ErrorKind, Parser, SyntaxKind, TokenKind, S, T,
 };
 
 /// See: https://spec.graphql.org/October2021/#DirectiveDefinition
@@ -14,33 +14,30 @@ pub(crate) fn directive_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("directive") = p.peek_data().as_deref() {
+    if let Some("directive") = p.peek_data() {
         p.bump(SyntaxKind::directive_KW);
     }
 
     match p.peek() {
         Some(T![@]) => p.bump(S![@]),
-        _ => p.err("expected @ symbol"),
+        _ => p.err_expected(&[T![@]], "expected @ symbol"),
     }
     name::name(p);
 
     if let Some(T!['(']) = p.peek() {
-        let _g = p.start_node(SyntaxKind::ARGUMENTS_DEFINITION);
-        p.bump(S!['(']);
-        input::input_value_definition(p, false);
-        p.expect(T![')'], S![')']);
+        argument::arguments_definition(p);
     }
 
     if let Some(node) = p.peek_data() {
-        if node.as_str() == "repeatable" {
+        if node == "repeatable" {
             p.bump(SyntaxKind::repeatable_KW);
         }
     }
 
     if let Some(node) = p.peek_data() {
-        match node.as_str() {
+        match node {
             "on" => p.bump(SyntaxKind::on_KW),
-            _ => p.err("expected Directive Locations"),
+            _ => p.err_expected(&[TokenKind::Name, T![|]], "expected Directive Locations"),
         }
     }
 
@@ -48,7 +45,10 @@ pub(crate) fn directive_definition(p: &mut Parser) {
         let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATIONS);
         directive_locations(p, false);
     } else {
-        p.err("expected valid Directive Location");
+        p.err_expected(
+            &[TokenKind::Name, T![|]],
+            "expected valid Directive Location",
+        );
     }
 }
 
@@ -57,104 +57,54 @@ pub(crate) fn directive_definition(p: &mut Parser) {
 /// *DirectiveLocations*:
 ///     DirectiveLocations **|** DirectiveLocation
 ///     **|**? DirectiveLocation
-pub(crate) fn directive_locations(p: &mut Parser, is_location: bool) {
-    if let Some(T![|]) = p.peek() {
-        p.bump(S![|]);
-        directive_locations(p, is_location)
-    }
+pub(crate) fn directive_locations(p: &mut Parser, mut is_location: bool) {
+    loop {
+        if let Some(T![|]) = p.peek() {
+            p.bump(S![|]);
+        }
 
-    if let Some(TokenKind::Name) = p.peek() {
-        let loc = p.peek_data().unwrap();
-        match loc.as_str() {
-            "QUERY" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::QUERY_KW);
-            }
-            "MUTATION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::MUTATION_KW);
-            }
-            "SUBSCRIPTION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::SUBSCRIPTION_KW);
-            }
-            "FIELD" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::FIELD_KW);
-            }
-            "FRAGMENT_DEFINITION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::FRAGMENT_DEFINITION_KW);
-            }
-            "FRAGMENT_SPREAD" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::FRAGMENT_DEFINITION_KW);
-            }
-            "INLINE_FRAGMENT" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::INLINE_FRAGMENT_KW);
-            }
-            "VARIABLE_DEFINITION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::VARIABLE_DEFINITION_KW);
-            }
-            "SCHEMA" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::SCHEMA_KW);
-            }
-            "SCALAR" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::SCALAR_KW);
-            }
-            "OBJECT" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::OBJECT_KW);
-            }
-            "FIELD_DEFINITION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::FIELD_DEFINITION_KW);
-            }
-            "ARGUMENT_DEFINITION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::ARGUMENT_DEFINITION_KW);
-            }
-            "INTERFACE" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::INTERFACE_KW);
-            }
-            "UNION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::UNION_KW);
-            }
-            "ENUM" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::ENUM_KW);
-            }
-            "ENUM_VALUE" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::ENUM_VALUE_KW);
-            }
-            "INPUT_OBJECT" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::INPUT_OBJECT_KW);
-            }
-            "INPUT_FIELD_DEFINITION" => {
-                let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
-                p.bump(SyntaxKind::INPUT_FIELD_DEFINITION_KW);
-            }
+        let loc = match p.peek() {
+            Some(TokenKind::Name) => p.peek_data().unwrap(),
+            _ => break,
+        };
+        let kind = match loc {
+            "QUERY" => SyntaxKind::QUERY_KW,
+            "MUTATION" => SyntaxKind::MUTATION_KW,
+            "SUBSCRIPTION" => SyntaxKind::SUBSCRIPTION_KW,
+            "FIELD" => SyntaxKind::FIELD_KW,
+            "FRAGMENT_DEFINITION" => SyntaxKind::FRAGMENT_DEFINITION_KW,
+            "FRAGMENT_SPREAD" => SyntaxKind::FRAGMENT_SPREAD_KW,
+            "INLINE_FRAGMENT" => SyntaxKind::INLINE_FRAGMENT_KW,
+            "VARIABLE_DEFINITION" => SyntaxKind::VARIABLE_DEFINITION_KW,
+            "SCHEMA" => SyntaxKind::SCHEMA_KW,
+            "SCALAR" => SyntaxKind::SCALAR_KW,
+            "OBJECT" => SyntaxKind::OBJECT_KW,
+            "FIELD_DEFINITION" => SyntaxKind::FIELD_DEFINITION_KW,
+            "ARGUMENT_DEFINITION" => SyntaxKind::ARGUMENT_DEFINITION_KW,
+            "INTERFACE" => SyntaxKind::INTERFACE_KW,
+            "UNION" => SyntaxKind::UNION_KW,
+            "ENUM" => SyntaxKind::ENUM_KW,
+            "ENUM_VALUE" => SyntaxKind::ENUM_VALUE_KW,
+            "INPUT_OBJECT" => SyntaxKind::INPUT_OBJECT_KW,
+            "INPUT_FIELD_DEFINITION" => SyntaxKind::INPUT_FIELD_DEFINITION_KW,
             _ => {
                 if !is_location {
-                    p.err("expected valid Directive Location");
+                    p.err(
+                        ErrorKind::InvalidDirectiveLocation,
+                        "expected valid Directive Location",
+                    );
                 }
                 return;
             }
-        }
-        if p.peek_data().is_some() {
-            return directive_locations(p, true);
-        }
+        };
+
+        let _g = p.start_node(SyntaxKind::DIRECTIVE_LOCATION);
+        p.bump(kind);
+        is_location = true;
     }
+
     if !is_location {
-        p.err("expected Directive Locations");
+        p.err_expected(&[TokenKind::Name, T![|]], "expected Directive Locations");
     }
 }
 
@@ -183,3 +133,38 @@ pub(crate) fn directives(p: &mut Parser) {
         directive(p);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ast, Parser};
+
+    fn directive_definition(input: &str) -> ast::DirectiveDefinition {
+        let ast = Parser::new(input).parse();
+        assert_eq!(ast.errors().len(), 0);
+        match ast.document().definitions().next().unwrap() {
+            ast::Definition::DirectiveDefinition(def) => def,
+            def => panic!("expected a directive definition, got {:?}", def),
+        }
+    }
+
+    #[test]
+    fn it_accesses_the_repeatable_keyword() {
+        let def = directive_definition("directive @a repeatable on FIELD");
+        assert_eq!(def.repeatable_token().unwrap().text(), "repeatable");
+
+        let def = directive_definition("directive @a on FIELD");
+        assert!(def.repeatable_token().is_none());
+    }
+
+    #[test]
+    fn it_accesses_arguments_definitions() {
+        let def = directive_definition("directive @a(b: Int, c: String) repeatable on FIELD");
+        let names: Vec<_> = def
+            .arguments_definition()
+            .unwrap()
+            .input_value_definitions()
+            .map(|arg| arg.name().unwrap().text().to_string())
+            .collect();
+        assert_eq!(names, ["b", "c"]);
+    }
+}
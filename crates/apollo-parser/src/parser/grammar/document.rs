@@ -3,7 +3,7 @@ use crate::{
         directive, enum_, extensions, fragment, input, interface, object, operation, scalar,
         schema, union_,
     },
-    Parser, SyntaxKind, TokenKind,
+    ErrorKind, ParseMode, Parser, SyntaxKind, TokenKind, T,
 };
 
 /// See: https://spec.graphql.org/October2021/#Document
@@ -17,6 +17,9 @@ pub(crate) fn document(p: &mut Parser) {
         match node {
             TokenKind::StringValue => {
                 let def = p.peek_data_n(2).unwrap();
+                if let Some(definitions) = without_description(def) {
+                    description_err(p, definitions);
+                }
                 select_definition(def, p);
             }
             TokenKind::Name => {
@@ -27,15 +30,54 @@ pub(crate) fn document(p: &mut Parser) {
                 let def = p.peek_data().unwrap();
                 select_definition(def, p);
             }
-            _ => break,
+            TokenKind::Eof => break,
+            _ => skip_to_definition(p, ErrorKind::MissingDefinition, "expected definition"),
         }
     }
 
     doc.finish_node();
 }
 
-fn select_definition(def: String, p: &mut Parser) {
-    match def.as_str() {
+/// Emit an error and skip tokens until the start of the next definition,
+/// wrapping them in an ERROR node.
+pub(crate) fn skip_to_definition(p: &mut Parser, kind: ErrorKind, message: &str) {
+    p.err_and_skip(kind, message, |p| match p.peek() {
+        Some(TokenKind::StringValue) => is_definition(p.peek_data_n(2).unwrap()),
+        Some(TokenKind::Name | TokenKind::LCurly) => is_definition(p.peek_data().unwrap()),
+        _ => false,
+    });
+}
+
+/// Check whether the current token starts a definition, when a definition is
+/// not necessarily expected.
+///
+/// Keywords are also valid Names, so this only matches a definition keyword
+/// followed by a token that cannot follow a Name in other positions, like
+/// `type Query` or `query {`.
+pub(crate) fn at_definition_start(p: &mut Parser) -> bool {
+    match p.peek_data() {
+        Some("extend") => matches!(
+            p.peek_data_n(2),
+            Some("schema" | "scalar" | "type" | "interface" | "union" | "enum" | "input")
+        ),
+        Some("directive") => p.peek_n(2) == Some(T![@]),
+        Some("schema" | "query" | "mutation" | "subscription") => {
+            matches!(
+                p.peek_n(2),
+                Some(TokenKind::Name | T!['{'] | T![@] | T!['('])
+            )
+        }
+        Some(def) if def != "{" && is_definition(def) => p.peek_n(2) == Some(TokenKind::Name),
+        _ => false,
+    }
+}
+
+fn select_definition(def: &str, p: &mut Parser) {
+    if is_definition(def) {
+        check_mode(def, p);
+    }
+
+    match def {
         "directive" => directive::directive_definition(p),
         "enum" => enum_::enum_type_definition(p),
         "extend" => extensions::extensions(p),
@@ -47,13 +89,54 @@ fn select_definition(def: String, p: &mut Parser) {
         "scalar" => scalar::scalar_type_definition(p),
         "schema" => schema::schema_definition(p),
         "union" => union_::union_type_definition(p),
-        _ => p.err_and_pop("expected definition"),
+        _ => skip_to_definition(p, ErrorKind::MissingDefinition, "expected definition"),
+    }
+}
+
+/// Get the name of the definitions that start with `def` if they cannot have
+/// a description.
+fn without_description(def: &str) -> Option<&'static str> {
+    match def {
+        "query" | "mutation" | "subscription" | "{" => Some("operations"),
+        "fragment" => Some("fragments"),
+        _ => None,
     }
 }
 
-pub(crate) fn is_definition(def: String) -> bool {
+/// Report a description in front of a definition that cannot have one, and
+/// wrap it in an ERROR node so that the definition is parsed after it.
+fn description_err(p: &mut Parser, definitions: &str) {
+    p.err(
+        ErrorKind::UnexpectedDescription,
+        &format!("descriptions are not allowed on {}", definitions),
+    );
+    let _g = p.start_node(SyntaxKind::ERROR);
+    p.bump(SyntaxKind::STRING);
+}
+
+/// Report a definition that is not allowed by the parser's `ParseMode`. The
+/// definition is still parsed, so that it ends up in the tree.
+fn check_mode(def: &str, p: &mut Parser) {
+    let executable = matches!(
+        def,
+        "query" | "mutation" | "subscription" | "{" | "fragment"
+    );
+    match (p.mode, executable) {
+        (ParseMode::Executable, false) => p.err(
+            ErrorKind::UnexpectedDefinition,
+            "executable documents must only contain operations and fragments",
+        ),
+        (ParseMode::TypeSystem, true) => p.err(
+            ErrorKind::UnexpectedDefinition,
+            "type system documents must not contain operations or fragments",
+        ),
+        _ => {}
+    }
+}
+
+pub(crate) fn is_definition(def: &str) -> bool {
     matches!(
-        def.as_str(),
+        def,
         "directive"
             | "enum"
             | "extend"
@@ -73,7 +156,7 @@ pub(crate) fn is_definition(def: String) -> bool {
 
 #[cfg(test)]
 mod test {
-    use crate::{ast, Parser};
+    use crate::{ast, ast::AstNode, ErrorKind, ParseMode, Parser};
 
     #[test]
     fn it_creates_error_for_invalid_definition_and_has_nodes_for_valid_definition() {
@@ -90,7 +173,7 @@ uasdf21230jkdw
         let ast = parser.parse();
 
         assert_eq!(ast.errors().len(), 1);
-        assert_eq!(ast.document().definitions().into_iter().count(), 1);
+        assert_eq!(ast.document().definitions().count(), 1);
     }
 
     #[test]
@@ -102,7 +185,64 @@ uasdf21230jkdw
         assert_eq!(ast.errors().len(), 1);
 
         let doc = ast.document();
-        assert!(doc.definitions().into_iter().next().is_none());
+        assert!(doc.definitions().next().is_none());
+    }
+
+    #[test]
+    fn it_reports_descriptions_on_operations_and_fragments() {
+        let cases = [
+            r#""a" { b }"#,
+            r#""a" query { b }"#,
+            r#""""a""" subscription S { b }"#,
+            r#""a" fragment F on T { b }"#,
+        ];
+        for input in cases {
+            for mode in [
+                ParseMode::Mixed,
+                ParseMode::Executable,
+                ParseMode::TypeSystem,
+            ] {
+                let ast = Parser::new(input).mode(mode).parse();
+                let errors: Vec<_> = ast
+                    .errors()
+                    .filter(|err| err.kind() == &ErrorKind::UnexpectedDescription)
+                    .collect();
+                assert_eq!(errors.len(), 1, "{}", input);
+                assert_eq!(errors[0].index(), 0);
+                assert!(errors[0]
+                    .message()
+                    .starts_with("descriptions are not allowed on"));
+                assert_eq!(ast.document().definitions().count(), 1, "{}", input);
+                assert_eq!(ast.document().syntax().to_string(), input);
+            }
+        }
+
+        let ast = Parser::new(r#""a" query { b }"#).parse();
+        assert_eq!(ast.errors().len(), 1);
+        assert_eq!(
+            ast.errors().next().unwrap().message(),
+            "descriptions are not allowed on operations"
+        );
+    }
+
+    #[test]
+    fn it_recovers_at_the_next_definition() {
+        let schema = r#"
+type Query {
+  me: User = 1
+  you: User
+
+type User { name: String }
+
+} garbage
+
+query { me { name } }
+"#;
+        let ast = Parser::new(schema).parse();
+
+        assert_eq!(ast.errors().len(), 2);
+        assert_eq!(ast.document().definitions().count(), 3);
+        assert_eq!(ast.document().syntax().to_string(), schema);
     }
 
     #[test]
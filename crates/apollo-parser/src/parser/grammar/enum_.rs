@@ -16,13 +16,13 @@ pub(crate) fn enum_type_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("enum") = p.peek_data().as_deref() {
+    if let Some("enum") = p.peek_data() {
         p.bump(SyntaxKind::enum_KW);
     }
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -48,7 +48,7 @@ pub(crate) fn enum_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -62,7 +62,10 @@ pub(crate) fn enum_type_extension(p: &mut Parser) {
     }
 
     if !meets_requirements {
-        p.err("expected Directive or Enum Values Definition");
+        p.err_expected(
+            &[T![@], T!['{']],
+            "expected Directive or Enum Values Definition",
+        );
     }
 }
 
@@ -76,10 +79,10 @@ pub(crate) fn enum_values_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name | TokenKind::StringValue) => enum_value_definition(p),
-        _ => p.err("expected Enum Value Definition"),
+        _ => p.err_expected(&[TokenKind::Name], "expected Enum Value Definition"),
     }
 
-    p.expect(T!['}'], S!['}']);
+    p.expect_closing(T!['}'], S!['}']);
 }
 
 /// See: https://spec.graphql.org/October2021/#EnumValueDefinition
@@ -87,8 +90,8 @@ pub(crate) fn enum_values_definition(p: &mut Parser) {
 /// *EnumValueDefinition*:
 ///     Description? EnumValue Directives?
 pub(crate) fn enum_value_definition(p: &mut Parser) {
-    if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
-        let guard = p.start_node(SyntaxKind::ENUM_VALUE_DEFINITION);
+    while let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
+        let _g = p.start_node(SyntaxKind::ENUM_VALUE_DEFINITION);
 
         if let Some(TokenKind::StringValue) = p.peek() {
             description::description(p);
@@ -99,13 +102,5 @@ pub(crate) fn enum_value_definition(p: &mut Parser) {
         if let Some(T![@]) = p.peek() {
             directive::directives(p);
         }
-        if p.peek().is_some() {
-            guard.finish_node();
-            return enum_value_definition(p);
-        }
-    }
-
-    if let Some(T!['}']) = p.peek() {
-        return;
     }
 }
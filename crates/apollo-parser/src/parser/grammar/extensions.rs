@@ -1,12 +1,12 @@
 use crate::{
-    parser::grammar::{enum_, input, interface, object, scalar, schema, union_},
-    Parser,
+    parser::grammar::{document, enum_, input, interface, object, scalar, schema, union_},
+    ErrorKind, Parser,
 };
 
 pub(crate) fn extensions(p: &mut Parser) {
     // we already know the next node is 'extend', check for the node after that
     // to figure out which type system extension to apply.
-    match p.peek_data_n(2).as_deref() {
+    match p.peek_data_n(2) {
         Some("schema") => schema::schema_extension(p),
         Some("scalar") => scalar::scalar_type_extension(p),
         Some("type") => object::object_type_extension(p),
@@ -14,14 +14,17 @@ pub(crate) fn extensions(p: &mut Parser) {
         Some("union") => union_::union_type_extension(p),
         Some("enum") => enum_::enum_type_extension(p),
         Some("input") => input::input_object_type_extension(p),
-        _ => p.err_and_pop("Invalid Type System Extension. This extension cannot be applied."),
+        _ => document::skip_to_definition(
+            p,
+            ErrorKind::InvalidExtension,
+            "Invalid Type System Extension. This extension cannot be applied.",
+        ),
     }
 }
 
 #[cfg(test)]
-
 mod test {
-    use crate::{ast, Parser};
+    use crate::{ast, ErrorKind, Parser};
 
     #[test]
     fn it_queries_graphql_extensions() {
@@ -135,8 +138,12 @@ extend Cat
         let parser = Parser::new(gql);
         let ast = parser.parse();
 
-        assert!(ast.errors().len() == 2);
-        assert_eq!(ast.document().definitions().into_iter().count(), 0);
+        assert!(ast.errors().len() == 1);
+        assert_eq!(
+            ast.errors().next().unwrap().kind(),
+            &ErrorKind::InvalidExtension
+        );
+        assert_eq!(ast.document().definitions().count(), 0);
     }
 
     #[test]
@@ -152,7 +159,7 @@ extend interface NamedEntity {
         let parser = Parser::new(gql);
         let ast = parser.parse();
 
-        assert!(ast.errors().len() == 2);
-        assert_eq!(ast.document().definitions().into_iter().count(), 1);
+        assert!(ast.errors().len() == 1);
+        assert_eq!(ast.document().definitions().count(), 1);
     }
 }
@@ -10,7 +10,7 @@ use crate::{
 /// *Field*:
 ///     Alias? Name Arguments? Directives? SelectionSet?
 pub(crate) fn field(p: &mut Parser) {
-    let guard = p.start_node(SyntaxKind::FIELD);
+    let _g = p.start_node(SyntaxKind::FIELD);
 
     if let Some(TokenKind::Name) = p.peek() {
         if let Some(T![:]) = p.peek_n(2) {
@@ -18,7 +18,7 @@ pub(crate) fn field(p: &mut Parser) {
         }
         name::name(p)
     } else {
-        p.err("expected a Name");
+        p.err_expected(&[TokenKind::Name], "expected a Name");
     }
 
     if let Some(T!['(']) = p.peek() {
@@ -32,18 +32,6 @@ pub(crate) fn field(p: &mut Parser) {
     if let Some(T!['{']) = p.peek() {
         selection::selection_set(p);
     }
-
-    match p.peek() {
-        Some(TokenKind::Name) => {
-            guard.finish_node();
-            field(p)
-        }
-
-        Some(T!['}']) => {
-            guard.finish_node();
-        }
-        _ => guard.finish_node(),
-    }
 }
 
 /// See: https://spec.graphql.org/October2021/#FieldsDefinition
@@ -54,7 +42,7 @@ pub(crate) fn fields_definition(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::FIELDS_DEFINITION);
     p.bump(S!['{']);
     field_definition(p);
-    p.expect(T!['}'], S!['}']);
+    p.expect_closing(T!['}'], S!['}']);
 }
 
 /// See: https://spec.graphql.org/October2021/#FieldDefinition
@@ -62,8 +50,8 @@ pub(crate) fn fields_definition(p: &mut Parser) {
 /// *FieldDefinition*:
 ///     Description? Name ArgumentsDefinition? **:** Type Directives?
 pub(crate) fn field_definition(p: &mut Parser) {
-    if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
-        let guard = p.start_node(SyntaxKind::FIELD_DEFINITION);
+    while let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
+        let _g = p.start_node(SyntaxKind::FIELD_DEFINITION);
 
         if let Some(TokenKind::StringValue) = p.peek() {
             description::description(p);
@@ -83,21 +71,15 @@ pub(crate) fn field_definition(p: &mut Parser) {
                     if let Some(T![@]) = p.peek() {
                         directive::directives(p);
                     }
-                    if p.peek().is_some() {
-                        guard.finish_node();
-                        return field_definition(p);
-                    }
                 }
                 _ => {
-                    p.err("expected a Type");
+                    p.err_expected(&[TokenKind::Name, T!['[']], "expected a Type");
+                    break;
                 }
             }
         } else {
-            p.err("expected a type");
+            p.err_expected(&[TokenKind::Name, T!['[']], "expected a Type");
+            break;
         }
     }
-
-    if let Some(T!['}']) = p.peek() {
-        return;
-    }
 }
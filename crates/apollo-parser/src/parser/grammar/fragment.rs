@@ -1,6 +1,6 @@
 use crate::{
     parser::grammar::{directive, name, selection, ty},
-    Parser, SyntaxKind, TokenKind, S, T,
+    ErrorKind, Parser, SyntaxKind, TokenKind, S, T,
 };
 
 /// See: https://spec.graphql.org/October2021/#FragmentDefinition
@@ -20,7 +20,7 @@ pub(crate) fn fragment_definition(p: &mut Parser) {
 
     match p.peek() {
         Some(T!['{']) => selection::selection_set(p),
-        _ => p.err("expected a Selection Set"),
+        _ => p.err_expected(&[T!['{']], "expected a Selection Set"),
     }
 }
 
@@ -33,11 +33,11 @@ pub(crate) fn fragment_name(p: &mut Parser) {
     match p.peek() {
         Some(TokenKind::Name) => {
             if p.peek_data().unwrap() == "on" {
-                return p.err("Fragment Name cannot be 'on'");
+                return p.err(ErrorKind::InvalidName, "Fragment Name cannot be 'on'");
             }
             name::name(p)
         }
-        _ => p.err("expected Fragment Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected Fragment Name"),
     }
 }
 
@@ -52,11 +52,11 @@ pub(crate) fn type_condition(p: &mut Parser) {
             if p.peek_data().unwrap() == "on" {
                 p.bump(SyntaxKind::on_KW);
             } else {
-                p.err("exptected 'on'");
+                p.err_expected(&[TokenKind::Name], "expected 'on'");
             }
             ty::named_type(p)
         }
-        _ => p.err("expected Type Condition"),
+        _ => p.err_expected(&[TokenKind::Name], "expected Type Condition"),
     }
 }
 
@@ -78,7 +78,7 @@ pub(crate) fn inline_fragment(p: &mut Parser) {
 
     match p.peek() {
         Some(T!['{']) => selection::selection_set(p),
-        _ => p.err("expected Selection Set"),
+        _ => p.err_expected(&[T!['{']], "expected Selection Set"),
     }
 }
 
@@ -94,7 +94,7 @@ pub(crate) fn fragment_spread(p: &mut Parser) {
         Some(TokenKind::Name) => {
             fragment_name(p);
         }
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -15,13 +15,13 @@ pub(crate) fn input_object_type_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("input") = p.peek_data().as_deref() {
+    if let Some("input") = p.peek_data() {
         p.bump(SyntaxKind::input_KW);
     }
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -47,7 +47,7 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -61,7 +61,10 @@ pub(crate) fn input_object_type_extension(p: &mut Parser) {
     }
 
     if !meets_requirements {
-        p.err("expected Directives or an Input Fields Definition");
+        p.err_expected(
+            &[T![@], T!['{']],
+            "expected Directives or an Input Fields Definition",
+        );
     }
 }
 
@@ -73,16 +76,16 @@ pub(crate) fn input_fields_definition(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::INPUT_FIELDS_DEFINITION);
     p.bump(S!['{']);
     input_value_definition(p, false);
-    p.expect(T!['}'], S!['}']);
+    p.expect_closing(T!['}'], S!['}']);
 }
 
 /// See: https://spec.graphql.org/October2021/#InputValueDefinition
 ///
 /// *InputValueDefinition*:
 ///     Description? Name **:** Type DefaultValue? Directives?
-pub(crate) fn input_value_definition(p: &mut Parser, is_input: bool) {
-    if let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
-        let guard = p.start_node(SyntaxKind::INPUT_VALUE_DEFINITION);
+pub(crate) fn input_value_definition(p: &mut Parser, mut is_input: bool) {
+    while let Some(TokenKind::Name | TokenKind::StringValue) = p.peek() {
+        let _g = p.start_node(SyntaxKind::INPUT_VALUE_DEFINITION);
 
         if let Some(TokenKind::StringValue) = p.peek() {
             description::description(p);
@@ -103,19 +106,23 @@ pub(crate) fn input_value_definition(p: &mut Parser, is_input: bool) {
                         directive::directives(p);
                     }
 
-                    if p.peek().is_some() {
-                        guard.finish_node();
-                        return input_value_definition(p, true);
-                    }
+                    is_input = true;
+                }
+                _ => {
+                    p.err_expected(&[TokenKind::Name, T!['[']], "expected a Type");
+                    break;
                 }
-                _ => p.err("expected a Type"),
             }
         } else {
-            p.err("expected a Name");
+            p.err_expected(&[TokenKind::Name], "expected a Name");
+            break;
         }
     }
     // TODO @lrlna: this can be simplified a little bit, and follow the pattern of FieldDefinition
     if !is_input {
-        p.err("expected an Input Value Definition");
+        p.err_expected(
+            &[TokenKind::StringValue, TokenKind::Name],
+            "expected an Input Value Definition",
+        );
     }
 }
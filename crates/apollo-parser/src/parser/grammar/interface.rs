@@ -14,16 +14,16 @@ pub(crate) fn interface_type_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("interface") = p.peek_data().as_deref() {
+    if let Some("interface") = p.peek_data() {
         p.bump(SyntaxKind::interface_KW);
     }
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
-    if let Some("implements") = p.peek_data().as_deref() {
+    if let Some("implements") = p.peek_data() {
         object::implements_interfaces(p);
     }
 
@@ -51,10 +51,10 @@ pub(crate) fn interface_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
-    if let Some("implements") = p.peek_data().as_deref() {
+    if let Some("implements") = p.peek_data() {
         meets_requirements = true;
         object::implements_interfaces(p);
     }
@@ -70,6 +70,9 @@ pub(crate) fn interface_type_extension(p: &mut Parser) {
     }
 
     if !meets_requirements {
-        p.err("exptected an Implements Interfaces, Directives, or a Fields Definition");
+        p.err_expected(
+            &[TokenKind::Name, T![@], T!['{']],
+            "expected an Implements Interfaces, Directives, or a Fields Definition",
+        );
     }
 }
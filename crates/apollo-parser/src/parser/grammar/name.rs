@@ -1,26 +1,35 @@
-use crate::{Parser, SyntaxKind, TokenKind, S};
+use crate::{ErrorKind, Parser, SyntaxKind, TokenKind, S};
 
 /// See: https://spec.graphql.org/October2021/#Name
 ///
 /// *Name*:
 ///     [_A-Za-z][_0-9A-Za-z]
+///
+/// No NAME node is created if the current token is not a Name, so that
+/// `name()` accessors return `None` for missing names.
 pub(crate) fn name(p: &mut Parser) {
-    let _g = p.start_node(SyntaxKind::NAME);
     match p.peek() {
         Some(TokenKind::Name) => {
+            let _g = p.start_node(SyntaxKind::NAME);
             validate_name(p.peek_data().unwrap(), p);
             p.bump(SyntaxKind::IDENT);
         }
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 }
 
-pub(crate) fn validate_name(name: String, p: &mut Parser) {
+pub(crate) fn validate_name(name: &str, p: &mut Parser) {
     if !name.starts_with(is_start_char) {
-        p.err_and_pop("expected Name to start with a letter or an _");
+        p.err(
+            ErrorKind::InvalidName,
+            "expected Name to start with a letter or an _",
+        );
     }
     if name.len() >= 2 && !name[1..].chars().all(is_remainder_char) {
-        p.err_and_pop("Name can only be composed of letters, numbers and _");
+        p.err(
+            ErrorKind::InvalidName,
+            "Name can only be composed of letters, numbers and _",
+        );
     }
 }
 
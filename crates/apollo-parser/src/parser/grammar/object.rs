@@ -16,20 +16,20 @@ pub(crate) fn object_type_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("type") = p.peek_data().as_deref() {
+    if let Some("type") = p.peek_data() {
         p.bump(SyntaxKind::type_KW);
     }
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(TokenKind::Name) = p.peek() {
         if p.peek_data().unwrap() == "implements" {
             implements_interfaces(p);
         } else {
-            p.err("unexpected Name");
+            p.err_expected(&[T![@], T!['{']], "unexpected Name");
         }
     }
 
@@ -59,10 +59,10 @@ pub(crate) fn object_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
-    if let Some("implements") = p.peek_data().as_deref() {
+    if let Some("implements") = p.peek_data() {
         meets_requirements = true;
         implements_interfaces(p);
     }
@@ -78,7 +78,10 @@ pub(crate) fn object_type_extension(p: &mut Parser) {
     }
 
     if !meets_requirements {
-        p.err("expected an Implements Interface, Directives or a Fields Definition");
+        p.err_expected(
+            &[TokenKind::Name, T![@], T!['{']],
+            "expected an Implements Interface, Directives or a Fields Definition",
+        );
     }
 }
 
@@ -94,25 +97,24 @@ pub(crate) fn implements_interfaces(p: &mut Parser) {
     implements_interface(p, false);
 }
 
-fn implements_interface(p: &mut Parser, is_interfaces: bool) {
-    match p.peek() {
-        Some(T![&]) => {
-            p.bump(S![&]);
-            implements_interface(p, is_interfaces)
-        }
-        Some(TokenKind::Name) => {
-            ty::named_type(p);
-            if let Some(node) = p.peek_data() {
-                if !is_definition(node) {
-                    implements_interface(p, true);
+fn implements_interface(p: &mut Parser, mut is_interfaces: bool) {
+    loop {
+        match p.peek() {
+            Some(T![&]) => p.bump(S![&]),
+            Some(TokenKind::Name) => {
+                ty::named_type(p);
+                is_interfaces = true;
+                if let Some(node) = p.peek_data() {
+                    if is_definition(node) {
+                        break;
+                    }
                 }
-
-                return;
             }
-        }
-        _ => {
-            if !is_interfaces {
-                p.err("expected an Object Type Definition");
+            _ => {
+                if !is_interfaces {
+                    p.err_expected(&[TokenKind::Name], "expected an Object Type Definition");
+                }
+                break;
             }
         }
     }
@@ -149,4 +151,29 @@ type Business implements NamedEntity & ValuedEntity & CatEntity {
             }
         }
     }
+
+    #[test]
+    fn it_parses_field_arguments_as_arguments_definitions() {
+        let input = "type Query { user(id: ID!, name: String): User }";
+        let ast = Parser::new(input).parse();
+        assert_eq!(0, ast.errors().len());
+
+        let doc = ast.document();
+        let Some(ast::Definition::ObjectTypeDefinition(def)) = doc.definitions().next() else {
+            panic!("expected an object type definition");
+        };
+        let field = def
+            .fields_definition()
+            .unwrap()
+            .field_definitions()
+            .next()
+            .unwrap();
+        let names: Vec<_> = field
+            .arguments_definition()
+            .unwrap()
+            .input_value_definitions()
+            .map(|arg| arg.name().unwrap().text().to_string())
+            .collect();
+        assert_eq!(names, ["id", "name"]);
+    }
 }
@@ -10,28 +10,29 @@ use crate::{
 ///
 /// *RootOperationTypeDefinition*:
 ///    OperationType **:** NamedType
-pub(crate) fn root_operation_type_definition(p: &mut Parser, is_operation_type: bool) {
-    if let Some(T!['{']) = p.peek() {
-        p.bump(S!['{']);
-    }
+pub(crate) fn root_operation_type_definition(p: &mut Parser, mut is_operation_type: bool) {
+    loop {
+        if let Some(T!['{']) = p.peek() {
+            p.bump(S!['{']);
+        }
 
-    if let Some(TokenKind::Name) = p.peek() {
-        let guard = p.start_node(SyntaxKind::ROOT_OPERATION_TYPE_DEFINITION);
-        operation_type(p);
-        if let Some(T![:]) = p.peek() {
-            p.bump(S![:]);
-            ty::named_type(p);
-            if p.peek().is_some() {
-                guard.finish_node();
-                return root_operation_type_definition(p, true);
+        if let Some(TokenKind::Name) = p.peek() {
+            let _g = p.start_node(SyntaxKind::ROOT_OPERATION_TYPE_DEFINITION);
+            operation_type(p);
+            if let Some(T![:]) = p.peek() {
+                p.bump(S![:]);
+                ty::named_type(p);
+                is_operation_type = true;
+                continue;
+            } else {
+                p.err_expected(&[TokenKind::Name], "expected a Name Type");
             }
-        } else {
-            p.err("expected a Name Type");
         }
+        break;
     }
 
     if !is_operation_type {
-        p.err("expected an Operation Type");
+        p.err_expected(&[TokenKind::Name], "expected an Operation Type");
     }
 }
 
@@ -40,7 +41,6 @@ pub(crate) fn root_operation_type_definition(p: &mut Parser, is_operation_type:
 /// *OperationDefinition*:
 ///    OperationType Name? VariableDefinitions? Directives? SelectionSet
 ///    SelectionSet
-
 pub(crate) fn operation_definition(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::OPERATION_DEFINITION);
 
@@ -65,7 +65,10 @@ pub(crate) fn operation_definition(p: &mut Parser) {
             }
         }
         Some(T!['{']) => selection::selection_set(p),
-        _ => p.err("expected an Operation Type or a Selection Set"),
+        _ => p.err_expected(
+            &[TokenKind::Name, T!['{']],
+            "expected an Operation Type or a Selection Set",
+        ),
     }
 }
 
@@ -76,11 +79,14 @@ pub(crate) fn operation_definition(p: &mut Parser) {
 pub(crate) fn operation_type(p: &mut Parser) {
     if let Some(node) = p.peek_data() {
         let _g = p.start_node(SyntaxKind::OPERATION_TYPE);
-        match node.as_str() {
+        match node {
             "query" => p.bump(SyntaxKind::query_KW),
             "subscription" => p.bump(SyntaxKind::subscription_KW),
             "mutation" => p.bump(SyntaxKind::mutation_KW),
-            _ => p.err("expected either a 'mutation', a 'query', or a 'subscription'"),
+            _ => p.err_expected(
+                &[TokenKind::Name],
+                "expected either a 'mutation', a 'query', or a 'subscription'",
+            ),
         }
     }
 }
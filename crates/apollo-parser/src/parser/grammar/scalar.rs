@@ -14,13 +14,13 @@ pub(crate) fn scalar_type_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("scalar") = p.peek_data().as_deref() {
+    if let Some("scalar") = p.peek_data() {
         p.bump(SyntaxKind::scalar_KW);
     }
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -39,11 +39,11 @@ pub(crate) fn scalar_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     match p.peek() {
         Some(T![@]) => directive::directives(p),
-        _ => p.err("expected Directives"),
+        _ => p.err_expected(&[T![@]], "expected Directives"),
     }
 }
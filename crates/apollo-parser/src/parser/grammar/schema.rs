@@ -14,7 +14,7 @@ pub(crate) fn schema_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("schema") = p.peek_data().as_deref() {
+    if let Some("schema") = p.peek_data() {
         p.bump(SyntaxKind::schema_KW);
     }
 
@@ -24,9 +24,9 @@ pub(crate) fn schema_definition(p: &mut Parser) {
 
     if let Some(T!['{']) = p.peek() {
         operation::root_operation_type_definition(p, false);
-        p.expect(T!['}'], S!['}']);
+        p.expect_closing(T!['}'], S!['}']);
     } else {
-        p.err("expected Root Operation Type Definition");
+        p.err_expected(&[T!['{']], "expected Root Operation Type Definition");
     }
 }
 
@@ -50,10 +50,39 @@ pub(crate) fn schema_extension(p: &mut Parser) {
     if let Some(T!['{']) = p.peek() {
         meets_requirements = true;
         operation::root_operation_type_definition(p, false);
-        p.expect(T!['}'], S!['}']);
+        p.expect_closing(T!['}'], S!['}']);
     }
 
     if !meets_requirements {
-        p.err("expected directives or Root Operation Type Definition");
+        p.err_expected(
+            &[T![@], T!['{']],
+            "expected directives or Root Operation Type Definition",
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ast, Parser};
+
+    #[test]
+    fn it_accesses_the_schema_description() {
+        let input = r#"
+"The API of the shop"
+schema {
+  query: Query
+}
+"#;
+        let ast = Parser::new(input).parse();
+        assert_eq!(ast.errors().len(), 0);
+
+        let doc = ast.document();
+        let Some(ast::Definition::SchemaDefinition(def)) = doc.definitions().next() else {
+            panic!("expected a schema definition");
+        };
+        assert_eq!(
+            def.description().unwrap().value().unwrap(),
+            "The API of the shop"
+        );
     }
 }
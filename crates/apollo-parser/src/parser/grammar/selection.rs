@@ -9,11 +9,30 @@ use crate::{
 ///     **{** Selection* **}**
 pub(crate) fn selection_set(p: &mut Parser) {
     if let Some(T!['{']) = p.peek() {
+        if p.recursion_limit.check_and_increment() {
+            p.limit_err("parser recursion limit reached");
+            return;
+        }
         let _g = p.start_node(SyntaxKind::SELECTION_SET);
         p.bump(S!['{']);
         selection(p);
-        p.expect(T!['}'], S!['}']);
+        p.expect_closing(T!['}'], S!['}']);
+        p.recursion_limit.decrement();
+    }
+}
+
+/// A field set, as used by the `fields` argument of federation directives
+/// like `@key`: the selections of a SelectionSet without its braces.
+///
+/// *FieldSet*:
+///     Selection+
+pub(crate) fn field_set(p: &mut Parser) {
+    let _g = p.start_node(SyntaxKind::SELECTION_SET);
+    if !matches!(p.peek(), Some(TokenKind::Name | T![...])) {
+        p.err_expected(&[TokenKind::Name, T![...]], "expected a Selection");
+        return;
     }
+    selection(p);
 }
 
 /// See: https://spec.graphql.org/October2021/#Selection
@@ -27,12 +46,15 @@ pub(crate) fn selection(p: &mut Parser) {
         match node {
             T![...] => {
                 if let Some(node) = p.peek_data_n(2) {
-                    match node.as_str() {
-                        "on" | "{" => fragment::inline_fragment(p),
+                    match node {
+                        "on" | "{" | "@" => fragment::inline_fragment(p),
                         _ => fragment::fragment_spread(p),
                     }
                 } else {
-                    p.err("expected an Inline Fragment or a Fragment Spread");
+                    p.err_expected(
+                        &[TokenKind::Name, T![@], T!['{']],
+                        "expected an Inline Fragment or a Fragment Spread",
+                    );
                 }
             }
             T!['{'] => {
@@ -116,6 +138,28 @@ mod test {
         }
     }
 
+    #[test]
+    fn inline_fragment_with_directives_in_selection() {
+        let input = "{ ... @include(if: true) { animal } }";
+        let parser = Parser::new(input);
+        let ast = parser.parse();
+        assert_eq!(0, ast.errors().len());
+
+        let doc = ast.document();
+        if let Some(Definition::OperationDefinition(op_def)) = doc.definitions().next() {
+            let selection = op_def.selection_set().unwrap().selections().next();
+            match selection {
+                Some(Selection::InlineFragment(inline_fragment)) => {
+                    assert!(inline_fragment.type_condition().is_none());
+                    assert!(inline_fragment.directives().is_some());
+                }
+                _ => panic!("expected an inline fragment"),
+            }
+        } else {
+            panic!("expected an operation definition");
+        }
+    }
+
     #[test]
     fn do_query_variables_match() {
         let input = "
@@ -140,8 +184,7 @@ query GraphQuery($graph_id: ID!, $variant: String) {
                 let variable_defs = op_def.variable_definitions();
                 let variables: Vec<TokenText> = variable_defs
                     .iter()
-                    .map(|v| v.variable_definitions())
-                    .flatten()
+                    .flat_map(|v| v.variable_definitions())
                     .filter_map(|v| Some(v.variable()?.name()?.text()))
                     .collect();
 
@@ -163,8 +206,7 @@ query GraphQuery($graph_id: ID!, $variant: String) {
                         let arguments = field.arguments();
                         let mut vars: Vec<TokenText> = arguments
                             .iter()
-                            .map(|a| a.arguments())
-                            .flatten()
+                            .flat_map(|a| a.arguments())
                             .filter_map(|v| {
                                 if let Value::Variable(var) = v.value()? {
                                     dbg!(&var.name()?.text());
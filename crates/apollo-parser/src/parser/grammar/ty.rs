@@ -1,6 +1,4 @@
-use std::collections::VecDeque;
-
-use crate::{parser::grammar::name, Parser, SyntaxKind, Token, TokenKind, S, T};
+use crate::{parser::grammar::name, Parser, SyntaxKind, TokenKind, S, T};
 
 /// See: https://spec.graphql.org/October2021/#InputValueDefinition
 ///
@@ -11,80 +9,31 @@ use crate::{parser::grammar::name, Parser, SyntaxKind, Token, TokenKind, S, T};
 ///     NonNullType
 ///         NamedType **!**
 ///         ListType **!**
-
-// NOTE(lrlna): Because Type cannot be parsed in a typical LR fashion, the
-// following parsing rule does not follow the same pattern as all other parsing
-// rules in this library. The parent node type is determined based on what its
-// last possible NonNullType.
 //
-// To make this work, we first collect all types in a double ended queue, and
-// unwrap them once the last possible child has been parsed. Nodes are then
-// created in the processing stage of this parsing rule.
+// NOTE(lrlna): Whether a Type is a NonNullType is only known once its last
+// token has been parsed. A checkpoint is taken before parsing the inner type,
+// so that a NON_NULL_TYPE node can wrap it once we see a `!`.
 pub(crate) fn ty(p: &mut Parser) {
-    let mut types = parse(p);
-
-    process(&mut types, p);
-
-    return;
-
-    fn parse(p: &mut Parser) -> VecDeque<(SyntaxKind, Token)> {
-        let token = p.pop();
-        let mut types = match token.kind() {
-            T!['['] => {
-                let mut types = parse(p);
-                types.push_front((S!['['], token));
-                if let Some(T![']']) = p.peek() {
-                    types.push_back((S![']'], p.pop()));
-                }
-                types
-            }
-            TokenKind::Name => {
-                let mut types = VecDeque::new();
-                types.push_back((SyntaxKind::NAMED_TYPE, token));
-                types
+    let checkpoint = p.checkpoint();
+    match p.peek() {
+        Some(T!['[']) => {
+            if p.recursion_limit.check_and_increment() {
+                p.limit_err("parser recursion limit reached");
+                return;
             }
-            // TODO(@lrlna): this should not panic
-            token => panic!("unexpected token, {:?}", token),
-        };
-
-        if let Some(T![!]) = p.peek() {
-            types.push_front((SyntaxKind::NON_NULL_TYPE, p.pop()));
+            let _g = p.start_node(SyntaxKind::LIST_TYPE);
+            p.bump(S!['[']);
+            ty(p);
+            p.expect_closing(T![']'], S![']']);
+            p.recursion_limit.decrement();
         }
-
-        types
+        Some(TokenKind::Name) => named_type(p),
+        _ => p.err_expected(&[TokenKind::Name, T!['[']], "expected a Type"),
     }
 
-    fn process(types: &mut VecDeque<(SyntaxKind, Token)>, p: &mut Parser) {
-        match types.pop_front() {
-            Some((kind @ S!['['], token)) => {
-                let _list_g = p.start_node(SyntaxKind::LIST_TYPE);
-                p.push_ast(kind, token);
-                process(types, p);
-                if let Some((_kind @ S![']'], _token)) = peek(types) {
-                    process(types, p);
-                }
-            }
-            Some((kind @ SyntaxKind::NON_NULL_TYPE, _)) => {
-                let _non_null_g = p.start_node(kind);
-                process(types, p);
-            }
-            // Cannot use `name::name` or `named_type` function here as we
-            // cannot bump from this function. Instead, the process function has
-            // already popped Tokens off the token vec, and we are simply adding
-            // to the AST.
-            Some((SyntaxKind::NAMED_TYPE, token)) => {
-                let named_g = p.start_node(SyntaxKind::NAMED_TYPE);
-                let name_g = p.start_node(SyntaxKind::NAME);
-                name::validate_name(token.data().to_string(), p);
-                p.push_ast(SyntaxKind::IDENT, token);
-                name_g.finish_node();
-                named_g.finish_node();
-            }
-            Some((kind @ S![']'], token)) => {
-                p.push_ast(kind, token);
-            }
-            _ => p.err("Internal apollo-parser error: unexpected when creating a Type"),
-        }
+    if let Some(T![!]) = p.peek() {
+        let _g = p.start_node_at(checkpoint, SyntaxKind::NON_NULL_TYPE);
+        p.bump(S![!]);
     }
 }
 
@@ -97,9 +46,60 @@ pub(crate) fn named_type(p: &mut Parser) {
     name::name(p);
 }
 
-fn peek<T>(target: &VecDeque<T>) -> Option<&T> {
-    match target.len() {
-        0 => None,
-        len => target.get(len - 1),
+#[cfg(test)]
+mod test {
+    use crate::{ast, ast::AstNode, Parser};
+
+    #[test]
+    fn it_wraps_non_null_types_around_their_inner_type() {
+        let input = "type Query { a: [Int!]! }";
+        let ast = Parser::new(input).parse();
+        assert_eq!(ast.errors().len(), 0);
+
+        let doc = ast.document();
+        let ast::Definition::ObjectTypeDefinition(def) = doc.definitions().next().unwrap() else {
+            panic!("expected an object type definition");
+        };
+        let field = def
+            .fields_definition()
+            .unwrap()
+            .field_definitions()
+            .next()
+            .unwrap();
+        let ast::Type::NonNullType(non_null) = field.ty().unwrap() else {
+            panic!("expected a non-null type");
+        };
+        assert_eq!(non_null.excl_token().unwrap().text(), "!");
+        let list = non_null.list_type().unwrap();
+        let ast::Type::NonNullType(item) = list.ty().unwrap() else {
+            panic!("expected a non-null item type");
+        };
+        assert_eq!(item.excl_token().unwrap().text(), "!");
+        assert_eq!(item.named_type().unwrap().name().unwrap().text(), "Int");
+    }
+
+    #[test]
+    fn it_attaches_trailing_whitespace_to_the_type() {
+        let input = "query($a: Int = 1) { a }";
+        let ast = Parser::new(input).parse();
+        assert_eq!(ast.errors().len(), 0);
+        assert_eq!(ast.document().to_string(), input);
+
+        let doc = ast.document();
+        let ast::Definition::OperationDefinition(op) = doc.definitions().next().unwrap() else {
+            panic!("expected an operation definition");
+        };
+        let var = op
+            .variable_definitions()
+            .unwrap()
+            .variable_definitions()
+            .next()
+            .unwrap();
+        let ast::Type::NamedType(named) = var.ty().unwrap() else {
+            panic!("expected a named type");
+        };
+        // The type starts at its name, and the whitespace that follows it is
+        // part of the type node.
+        assert_eq!(named.syntax().text().to_string(), "Int ");
     }
 }
@@ -16,13 +16,13 @@ pub(crate) fn union_type_definition(p: &mut Parser) {
         description::description(p);
     }
 
-    if let Some("union") = p.peek_data().as_deref() {
+    if let Some("union") = p.peek_data() {
         p.bump(SyntaxKind::union_KW);
     }
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -48,7 +48,7 @@ pub(crate) fn union_type_extension(p: &mut Parser) {
 
     match p.peek() {
         Some(TokenKind::Name) => name::name(p),
-        _ => p.err("expected a Name"),
+        _ => p.err_expected(&[TokenKind::Name], "expected a Name"),
     }
 
     if let Some(T![@]) = p.peek() {
@@ -62,7 +62,7 @@ pub(crate) fn union_type_extension(p: &mut Parser) {
     }
 
     if !meets_requirements {
-        p.err("expected Directives or Union Member Types");
+        p.err_expected(&[T![@], T![=]], "expected Directives or Union Member Types");
     }
 }
 
@@ -78,25 +78,24 @@ pub(crate) fn union_member_types(p: &mut Parser) {
     union_member_type(p, false);
 }
 
-fn union_member_type(p: &mut Parser, is_union: bool) {
-    match p.peek() {
-        Some(T![|]) => {
-            p.bump(S![|]);
-            union_member_type(p, is_union);
-        }
-        Some(TokenKind::Name) => {
-            ty::named_type(p);
-            if let Some(node) = p.peek_data() {
-                if !is_definition(node) {
-                    union_member_type(p, true);
+fn union_member_type(p: &mut Parser, mut is_union: bool) {
+    loop {
+        match p.peek() {
+            Some(T![|]) => p.bump(S![|]),
+            Some(TokenKind::Name) => {
+                ty::named_type(p);
+                is_union = true;
+                if let Some(node) = p.peek_data() {
+                    if is_definition(node) {
+                        break;
+                    }
                 }
-
-                return;
             }
-        }
-        _ => {
-            if !is_union {
-                p.err("expected Union Member Types");
+            _ => {
+                if !is_union {
+                    p.err_expected(&[TokenKind::Name], "expected Union Member Types");
+                }
+                break;
             }
         }
     }
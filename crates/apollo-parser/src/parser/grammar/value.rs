@@ -1,6 +1,6 @@
 use crate::{
     parser::grammar::{name, variable},
-    Parser, SyntaxKind, TokenKind, S, T,
+    ErrorKind, Parser, SyntaxKind, TokenKind, S, T,
 };
 
 /// See: https://spec.graphql.org/October2021/#Value
@@ -32,7 +32,7 @@ pub(crate) fn value(p: &mut Parser) {
         }
         Some(TokenKind::Name) => {
             let node = p.peek_data().unwrap();
-            match node.as_str() {
+            match node {
                 "true" => {
                     let _g = p.start_node(SyntaxKind::BOOLEAN_VALUE);
                     p.bump(SyntaxKind::true_KW);
@@ -50,7 +50,18 @@ pub(crate) fn value(p: &mut Parser) {
         }
         Some(T!['[']) => list_value(p),
         Some(T!['{']) => object_value(p),
-        _ => p.err("expected a valid Value"),
+        _ => p.err_expected(
+            &[
+                T![$],
+                TokenKind::Int,
+                TokenKind::Float,
+                TokenKind::StringValue,
+                TokenKind::Name,
+                T!['['],
+                T!['{'],
+            ],
+            "expected a valid Value",
+        ),
     }
 }
 /// See: https://spec.graphql.org/October2021/#EnumValue
@@ -61,8 +72,8 @@ pub(crate) fn enum_value(p: &mut Parser) {
     let _g = p.start_node(SyntaxKind::ENUM_VALUE);
     let name = p.peek_data().unwrap();
 
-    if matches!(name.as_str(), "true" | "false" | "null") {
-        p.err("unexpected Enum Value");
+    if matches!(name, "true" | "false" | "null") {
+        p.err(ErrorKind::InvalidName, "unexpected Enum Value");
     }
 
     name::name(p);
@@ -74,17 +85,33 @@ pub(crate) fn enum_value(p: &mut Parser) {
 ///     **[** **]**
 ///     **[** Value* **]**
 pub(crate) fn list_value(p: &mut Parser) {
+    if p.recursion_limit.check_and_increment() {
+        p.limit_err("parser recursion limit reached");
+        return;
+    }
     let _g = p.start_node(SyntaxKind::LIST_VALUE);
     p.bump(S!['[']);
 
     while let Some(node) = p.peek() {
-        if node == T![']'] {
-            p.bump(S![']']);
-            break;
-        } else {
-            value(p);
+        match node {
+            T![']'] => {
+                p.bump(S![']']);
+                break;
+            }
+            T![$]
+            | TokenKind::Int
+            | TokenKind::Float
+            | TokenKind::StringValue
+            | TokenKind::Name
+            | T!['[']
+            | T!['{'] => value(p),
+            _ => {
+                p.expect_closing(T![']'], S![']']);
+                break;
+            }
         }
     }
+    p.recursion_limit.decrement();
 }
 
 /// See: https://spec.graphql.org/October2021/#ObjectValue
@@ -93,23 +120,24 @@ pub(crate) fn list_value(p: &mut Parser) {
 ///     **{** **}**
 ///     **{** ObjectField* **}**
 pub(crate) fn object_value(p: &mut Parser) {
+    if p.recursion_limit.check_and_increment() {
+        p.limit_err("parser recursion limit reached");
+        return;
+    }
     let _g = p.start_node(SyntaxKind::OBJECT_VALUE);
     p.bump(S!['{']);
 
     match p.peek() {
         Some(TokenKind::Name) => {
             object_field(p);
-            if let Some(T!['}']) = p.peek() {
-                p.bump(S!['}']);
-            } else {
-                p.err("expected }");
-            }
+            p.expect_closing(T!['}'], S!['}']);
         }
         Some(T!['}']) => {
             p.bump(S!['}']);
         }
-        _ => p.err("expected Object Value"),
+        _ => p.err_expected(&[T!['{']], "expected Object Value"),
     }
+    p.recursion_limit.decrement();
 }
 
 /// See: https://spec.graphql.org/October2021/#ObjectField
@@ -117,17 +145,15 @@ pub(crate) fn object_value(p: &mut Parser) {
 /// *ObjectField*:
 ///     Name **:** Value
 pub(crate) fn object_field(p: &mut Parser) {
-    if let Some(TokenKind::Name) = p.peek() {
-        let guard = p.start_node(SyntaxKind::OBJECT_FIELD);
+    while let Some(TokenKind::Name) = p.peek() {
+        let _g = p.start_node(SyntaxKind::OBJECT_FIELD);
         name::name(p);
 
         if let Some(T![:]) = p.peek() {
             p.bump(S![:]);
             value(p);
-            if p.peek().is_some() {
-                guard.finish_node();
-                object_field(p)
-            }
+        } else {
+            break;
         }
     }
 }
@@ -192,7 +218,7 @@ enum Test @dir__one(int_value: -10) {
                         if let ast::Value::IntValue(val) =
                             argument.value().expect("Cannot get argument value.")
                         {
-                            let i: i64 = val.into();
+                            let i = i64::try_from(val).unwrap();
                             assert_eq!(i, -10);
                         }
                     }
@@ -223,7 +249,7 @@ enum Test @dir__one(float_value: -1.123E4) {
                         if let ast::Value::FloatValue(val) =
                             argument.value().expect("Cannot get argument value.")
                         {
-                            let f: f64 = val.into();
+                            let f = f64::try_from(val).unwrap();
                             assert_eq!(f, -1.123E4);
                         }
                     }
@@ -284,8 +310,7 @@ query GraphQuery($graph_id: ID!, $variant: String) {
                 let variable_defs = op_def.variable_definitions();
                 let variables: Vec<String> = variable_defs
                     .iter()
-                    .map(|v| v.variable_definitions())
-                    .flatten()
+                    .flat_map(|v| v.variable_definitions())
                     .filter_map(|v| Some(v.variable()?.text().to_string()))
                     .collect();
                 assert_eq!(
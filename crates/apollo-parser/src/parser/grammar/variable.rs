@@ -15,21 +15,21 @@ pub(crate) fn variable_definitions(p: &mut Parser) {
     if let Some(T![$]) = p.peek() {
         variable_definition(p, false);
     }
-    p.expect(T![')'], S![')']);
+    p.expect_closing(T![')'], S![')']);
 }
 
 /// See: https://spec.graphql.org/October2021/#VariableDefinition
 ///
 /// *VariableDefinition*:
 ///     Variable **:** Type DefaultValue? Directives?
-pub(crate) fn variable_definition(p: &mut Parser, is_variable: bool) {
-    if let Some(T![$]) = p.peek() {
-        let guard = p.start_node(SyntaxKind::VARIABLE_DEFINITION);
+pub(crate) fn variable_definition(p: &mut Parser, mut is_variable: bool) {
+    while let Some(T![$]) = p.peek() {
+        let _g = p.start_node(SyntaxKind::VARIABLE_DEFINITION);
         variable(p);
 
         if let Some(T![:]) = p.peek() {
             p.bump(S![:]);
-            if let Some(TokenKind::Name) = p.peek() {
+            if let Some(TokenKind::Name | T!['[']) = p.peek() {
                 ty::ty(p);
                 if let Some(T![=]) = p.peek() {
                     value::default_value(p);
@@ -37,19 +37,18 @@ pub(crate) fn variable_definition(p: &mut Parser, is_variable: bool) {
                 if let Some(T![@]) = p.peek() {
                     directive::directives(p)
                 }
-                if p.peek().is_some() {
-                    guard.finish_node();
-                    return variable_definition(p, true);
-                }
+                is_variable = true;
+                continue;
             }
-            p.err("expected a Type");
+            p.err_expected(&[TokenKind::Name, T!['[']], "expected a Type");
         } else {
-            p.err("expected a Name");
+            p.err_expected(&[TokenKind::Name], "expected a Name");
         }
+        break;
     }
 
     if !is_variable {
-        p.err("expected a Variable Definition");
+        p.err_expected(&[T![$]], "expected a Variable Definition");
     }
 }
 
@@ -64,7 +63,6 @@ pub(crate) fn variable(p: &mut Parser) {
 }
 
 #[cfg(test)]
-
 mod test {
     use crate::{ast, Parser};
 
@@ -101,4 +99,34 @@ query GroceryStoreTrip($budget: Int) {
             }
         }
     }
+
+    #[test]
+    fn it_accesses_variable_directives() {
+        let gql = r#"
+query GroceryStoreTrip($budget: Int = 10 @deprecated @tag(name: "cost")) {
+    name
+}
+        "#;
+
+        let ast = Parser::new(gql).parse();
+        assert_eq!(ast.errors().len(), 0);
+
+        let doc = ast.document();
+        let Some(ast::Definition::OperationDefinition(op_def)) = doc.definitions().next() else {
+            panic!("expected an operation definition");
+        };
+        let var = op_def
+            .variable_definitions()
+            .unwrap()
+            .variable_definitions()
+            .next()
+            .unwrap();
+        let names: Vec<_> = var
+            .directives()
+            .unwrap()
+            .directives()
+            .map(|directive| directive.name().unwrap().text().to_string())
+            .collect();
+        assert_eq!(names, ["deprecated", "tag"]);
+    }
 }
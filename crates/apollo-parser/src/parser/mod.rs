@@ -1,22 +1,59 @@
 mod generated;
 mod language;
+mod reparsing;
 mod syntax_tree;
 mod token_text;
 
 pub(crate) mod grammar;
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
-use crate::{lexer::Lexer, Error, Token, TokenKind};
+use crate::{
+    ast, lexer::Lexer, Error, ErrorKind, LimitTracker, LineIndex, ParserLimits, Token, TokenKind,
+    S, T,
+};
 
 pub use generated::syntax_kind::SyntaxKind;
 pub use language::{SyntaxElement, SyntaxNodeChildren, SyntaxToken};
+pub use reparsing::TextEdit;
 pub use syntax_tree::SyntaxTree;
 
 pub(crate) use language::{GraphQLLanguage, SyntaxNode};
 pub(crate) use syntax_tree::SyntaxTreeBuilder;
 pub(crate) use token_text::TokenText;
 
+/// The kinds of definitions a document may contain.
+///
+/// GraphQL services execute documents that only contain operations and
+/// fragments, while schemas only contain type system definitions and
+/// extensions. A definition of the wrong kind is still parsed and kept in the
+/// tree, but reported with [`ErrorKind::UnexpectedDefinition`].
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{ErrorKind, ParseMode, Parser};
+///
+/// let input = "query { me } type Query { me: String }";
+/// let ast = Parser::new(input).mode(ParseMode::Executable).parse();
+///
+/// let err = ast.errors().next().unwrap();
+/// assert_eq!(err.kind(), &ErrorKind::UnexpectedDefinition);
+/// assert_eq!(err.data(), "type");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Only operations and fragments are allowed, as described by the
+    /// [Executable Definitions] validation rule.
+    ///
+    /// [Executable Definitions]: https://spec.graphql.org/October2021/#sec-Executable-Definitions
+    Executable,
+    /// Only type system definitions and extensions are allowed.
+    TypeSystem,
+    /// Any definition is allowed.
+    #[default]
+    Mixed,
+}
+
 /// Parse GraphQL schemas or queries into a typed AST.
 ///
 /// ## Example
@@ -70,49 +107,199 @@ pub(crate) use token_text::TokenText;
 /// let document = ast.document();
 /// ```
 #[derive(Debug)]
-pub struct Parser {
-    /// Input tokens, including whitespace, in *reverse* order.
-    tokens: Vec<Token>,
+pub struct Parser<'a> {
+    /// The lexer that produces input tokens on demand.
+    lexer: Lexer<'a>,
+    /// Tokens, including whitespace, that were lexed for lookahead but not
+    /// consumed yet. Input that could not be lexed is kept as `Err` with its
+    /// text, so that it still ends up in the tree as an ERROR token.
+    lookahead: VecDeque<Result<Token<'a>, &'a str>>,
     /// The in-progress tree.
     builder: Rc<RefCell<SyntaxTreeBuilder>>,
     /// The list of syntax errors we've accumulated so far.
     errors: Vec<crate::Error>,
+    /// The input, kept by the resulting `SyntaxTree` for reparsing.
+    input: &'a str,
+    /// Tracks the nesting depth of recursive grammar rules.
+    pub(crate) recursion_limit: LimitTracker,
+    /// Whether errors are still recorded. Once a limit is exceeded, parsing
+    /// stops and the errors that unwinding produces are not meaningful.
+    accept_errors: bool,
+    /// The kinds of definitions the document may contain.
+    pub(crate) mode: ParseMode,
 }
 
-impl Parser {
+impl<'a> Parser<'a> {
     /// Create a new instance of a parser given an input string.
-    pub fn new(input: &str) -> Self {
-        let lexer = Lexer::new(input);
-
-        let mut tokens = Vec::new();
-        let mut errors = Vec::new();
-
-        for s in lexer.tokens().to_owned() {
-            tokens.push(s);
-        }
-
-        for e in lexer.errors().cloned() {
-            errors.push(e);
-        }
-
-        tokens.reverse();
-        errors.reverse();
+    ///
+    /// This uses the default `ParserLimits`, which do not limit recursion or
+    /// the number of tokens. Use [`Parser::with_limits`] to parse untrusted
+    /// input.
+    pub fn new(input: &'a str) -> Self {
+        Self::with_limits(input, ParserLimits::default())
+    }
 
+    /// Create a new instance of a parser given an input string and the
+    /// resource limits to parse it with.
+    ///
+    /// Use this to parse untrusted input, such as operations sent to a
+    /// server. When a limit is exceeded, parsing stops and an error with
+    /// `ErrorKind::LimitExceeded` is returned alongside the partial tree.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ErrorKind, Parser, ParserLimits};
+    ///
+    /// let limits = ParserLimits {
+    ///     recursion_limit: 10,
+    ///     token_limit: 5,
+    /// };
+    /// let ast = Parser::with_limits("query { me { name } }", limits).parse();
+    ///
+    /// let err = ast.errors().next().unwrap();
+    /// assert_eq!(err.kind(), &ErrorKind::LimitExceeded);
+    /// assert!(ast.token_limit().limited());
+    /// ```
+    pub fn with_limits(input: &'a str, limits: ParserLimits) -> Self {
         Self {
-            tokens,
+            lexer: Lexer::with_limit(input, limits.token_limit),
+            lookahead: VecDeque::new(),
             builder: Rc::new(RefCell::new(SyntaxTreeBuilder::new())),
-            errors,
+            errors: Vec::new(),
+            input,
+            recursion_limit: LimitTracker::new(limits.recursion_limit),
+            accept_errors: true,
+            mode: ParseMode::default(),
         }
     }
 
+    /// Set the kinds of definitions the parsed document may contain. This is
+    /// `ParseMode::Mixed` by default.
+    pub fn mode(mut self, mode: ParseMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     /// Parse the current tokens.
     pub fn parse(mut self) -> SyntaxTree {
         grammar::document::document(&mut self);
+        self.finish()
+    }
+
+    /// Parse the input as a single Type, like `[String!]!`.
+    ///
+    /// Any input after the Type is reported as an error.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ast, Parser};
+    ///
+    /// let ast = Parser::new("[String!]!").parse_type();
+    /// assert_eq!(0, ast.errors().len());
+    ///
+    /// let ty = ast.root().unwrap();
+    /// assert_eq!(ty.to_string(), "[String!]!");
+    /// ```
+    pub fn parse_type(self) -> SyntaxTree<ast::Type> {
+        self.parse_fragment(grammar::ty::ty)
+    }
+
+    /// Parse the input as a single Value, like the default value of an
+    /// argument. Values may contain variables.
+    ///
+    /// Any input after the Value is reported as an error.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ast, Parser};
+    ///
+    /// let ast = Parser::new(r#"{ name: "Pixel", age: 3 }"#).parse_value();
+    /// assert_eq!(0, ast.errors().len());
+    ///
+    /// let value = ast.root().unwrap();
+    /// assert!(matches!(value, ast::Value::ObjectValue(_)));
+    /// ```
+    pub fn parse_value(self) -> SyntaxTree<ast::Value> {
+        self.parse_fragment(grammar::value::value)
+    }
+
+    /// Parse the input as a single SelectionSet, including its braces.
+    ///
+    /// Any input after the SelectionSet is reported as an error.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::Parser;
+    ///
+    /// let ast = Parser::new("{ pet { name } }").parse_selection_set();
+    /// assert_eq!(0, ast.errors().len());
+    ///
+    /// let selection_set = ast.root().unwrap();
+    /// assert_eq!(selection_set.selections().count(), 1);
+    /// ```
+    pub fn parse_selection_set(self) -> SyntaxTree<ast::SelectionSet> {
+        self.parse_fragment(|p| {
+            if p.at(T!['{']) {
+                grammar::selection::selection_set(p);
+            } else {
+                p.err_expected(&[T!['{']], "expected a Selection Set");
+            }
+        })
+    }
+
+    /// Parse the input as a field set: the selections of a SelectionSet
+    /// without the enclosing braces, like the `fields` argument of the
+    /// `@key` and `@requires` federation directives.
+    ///
+    /// The resulting SELECTION_SET node has no brace tokens. Any input after
+    /// the selections is reported as an error.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::Parser;
+    ///
+    /// let ast = Parser::new("id organization { id }").parse_field_set();
+    /// assert_eq!(0, ast.errors().len());
+    ///
+    /// let selection_set = ast.root().unwrap();
+    /// assert_eq!(selection_set.selections().count(), 2);
+    /// ```
+    pub fn parse_field_set(self) -> SyntaxTree<ast::SelectionSet> {
+        self.parse_fragment(grammar::selection::field_set)
+    }
+
+    /// Parse the input with a grammar `rule` for a fragment of GraphQL,
+    /// wrapped in a DOCUMENT node. Any input the rule does not consume is
+    /// skipped into an ERROR node.
+    fn parse_fragment<R>(mut self, rule: impl FnOnce(&mut Parser<'a>)) -> SyntaxTree<R> {
+        let doc = self.start_node(SyntaxKind::DOCUMENT);
+        rule(&mut self);
+        if !matches!(self.peek(), None | Some(TokenKind::Eof)) {
+            self.err_and_skip(
+                ErrorKind::UnexpectedToken {
+                    expected: vec![TokenKind::Eof],
+                },
+                "expected the end of the input",
+                |_| false,
+            );
+        }
+        doc.finish_node();
+        self.finish()
+    }
 
+    /// Finish parsing and build the resulting tree.
+    fn finish<R>(self) -> SyntaxTree<R> {
         let builder = Rc::try_unwrap(self.builder)
             .expect("More than one reference to builder left")
             .into_inner();
-        builder.finish(self.errors)
+        builder.finish(
+            self.errors,
+            LineIndex::new(self.input),
+            self.input.to_string(),
+            self.recursion_limit,
+            self.lexer.token_limit(),
+            self.mode,
+        )
     }
 
     /// Check if the current token is `kind`.
@@ -133,60 +320,106 @@ impl Parser {
         self.bump_ignored();
     }
 
-    /// Consume ignored tokens and add them to the AST.
+    /// Consume ignored tokens and input that could not be lexed, and add them
+    /// to the AST.
     fn bump_ignored(&mut self) {
-        while let Some(TokenKind::Comment | TokenKind::Whitespace | TokenKind::Comma) = self.peek()
-        {
-            if let Some(TokenKind::Comment) = self.peek() {
-                self.bump(SyntaxKind::COMMENT);
-            }
-            if let Some(TokenKind::Whitespace) = self.peek() {
-                self.bump(SyntaxKind::WHITESPACE);
-            }
-            if let Some(TokenKind::Comma) = self.peek() {
-                self.bump(SyntaxKind::COMMA);
+        while self.fill(1) {
+            match self.lookahead[0] {
+                Ok(token) => match token.kind() {
+                    TokenKind::Comment => self.eat(SyntaxKind::COMMENT),
+                    TokenKind::Whitespace => self.eat(SyntaxKind::WHITESPACE),
+                    TokenKind::Comma => self.eat(SyntaxKind::COMMA),
+                    _ => break,
+                },
+                Err(text) => {
+                    self.lookahead.pop_front();
+                    self.builder.borrow_mut().token(SyntaxKind::ERROR, text);
+                }
             }
         }
     }
 
     /// Get current token's data.
-    pub(crate) fn current(&mut self) -> &Token {
+    pub(crate) fn current(&mut self) -> Token<'a> {
         self.peek_token()
             .expect("Could not peek at the current token")
     }
 
     /// Consume a token from the lexer and add it to the AST.
     fn eat(&mut self, kind: SyntaxKind) {
-        let token = self
-            .tokens
-            .pop()
-            .expect("Could not eat a token from the AST");
+        let token = self.pop();
         self.builder.borrow_mut().token(kind, token.data());
     }
 
     /// Create a parser error and push it into the error vector.
-    pub(crate) fn err(&mut self, message: &str) {
-        let current = self.current().clone();
+    pub(crate) fn err(&mut self, kind: ErrorKind, message: &str) {
+        let current = self.current();
         // this needs to be the computed location
-        let err = Error::with_loc(message, current.data().to_string(), current.index());
+        let err = Error::with_loc(message, current.data().to_string(), current.index())
+            .with_kind(kind)
+            .with_eof(current.kind() == TokenKind::Eof);
         self.push_err(err);
     }
 
-    /// Create a parser error and push it into the error vector.
-    pub(crate) fn err_and_pop(&mut self, message: &str) {
-        let current = self.pop();
-        // we usually bump ignored after we pop a token, so make sure we also do
-        // this when we create an error and pop.
-        self.bump_ignored();
-        // this needs to be the computed location
-        let err = Error::with_loc(message, current.data().to_string(), current.index());
-        self.push_err(err);
+    /// Create a parser error and push it into the error vector, then skip
+    /// tokens until `recover` returns `true` or the input ends. Skipped tokens
+    /// are wrapped in a single ERROR node. At least one token is skipped.
+    pub(crate) fn err_and_skip(
+        &mut self,
+        kind: ErrorKind,
+        message: &str,
+        mut recover: impl FnMut(&mut Parser) -> bool,
+    ) {
+        self.err(kind, message);
+        let _g = self.start_node(SyntaxKind::ERROR);
+        self.bump_any();
+        while !matches!(self.peek(), None | Some(TokenKind::Eof)) && !recover(self) {
+            self.bump_any();
+        }
+    }
+
+    /// Consume the current token, whatever it is, and add it to the AST.
+    fn bump_any(&mut self) {
+        let kind = match self.current().kind() {
+            TokenKind::Whitespace => SyntaxKind::WHITESPACE,
+            TokenKind::Comment => SyntaxKind::COMMENT,
+            TokenKind::Bang => S![!],
+            TokenKind::Dollar => S![$],
+            TokenKind::Amp => S![&],
+            TokenKind::Spread => S![...],
+            TokenKind::Comma => S![,],
+            TokenKind::Colon => S![:],
+            TokenKind::Eq => S![=],
+            TokenKind::At => S![@],
+            TokenKind::LParen => S!['('],
+            TokenKind::RParen => S![')'],
+            TokenKind::LBracket => S!['['],
+            TokenKind::RBracket => S![']'],
+            TokenKind::LCurly => S!['{'],
+            TokenKind::RCurly => S!['}'],
+            TokenKind::Pipe => S![|],
+            TokenKind::Eof => SyntaxKind::EOF,
+            TokenKind::Name => SyntaxKind::IDENT,
+            TokenKind::StringValue => SyntaxKind::STRING,
+            TokenKind::Int => SyntaxKind::INT,
+            TokenKind::Float => SyntaxKind::FLOAT,
+        };
+        self.bump(kind);
+    }
+
+    /// Create an error for an unexpected token when any of the `expected`
+    /// tokens would have been valid, and push it into the error vector.
+    pub(crate) fn err_expected(&mut self, expected: &[TokenKind], message: &str) {
+        let kind = ErrorKind::UnexpectedToken {
+            expected: expected.to_vec(),
+        };
+        self.err(kind, message);
     }
 
     /// Consume the next token if it is `kind` or emit an error
     /// otherwise.
     pub(crate) fn expect(&mut self, token: TokenKind, kind: SyntaxKind) {
-        let current = self.current().clone();
+        let current = self.current();
         let data = current.data().to_string();
 
         if self.at(token) {
@@ -198,26 +431,121 @@ impl Parser {
             format!("expected {:?}, got {}", kind, data),
             data,
             current.index(),
-        );
+        )
+        .with_kind(ErrorKind::UnexpectedToken {
+            expected: vec![token],
+        })
+        .with_eof(current.kind() == TokenKind::Eof);
 
         self.push_err(err);
     }
 
+    /// Consume the closing bracket `token` of a grammar rule, or emit an
+    /// error and skip ahead to it otherwise.
+    ///
+    /// Skipping stops early at a closing bracket that belongs to an enclosing
+    /// rule and at the start of a new definition, so that a single typo does
+    /// not swallow the rest of the document.
+    pub(crate) fn expect_closing(&mut self, token: TokenKind, kind: SyntaxKind) {
+        if self.at(token) {
+            self.bump(kind);
+            return;
+        }
+
+        self.expect(token, kind);
+        if self.at_recovery_point(token) {
+            return;
+        }
+
+        let guard = self.start_node(SyntaxKind::ERROR);
+        // Nested brackets are skipped as a whole.
+        let mut depth = 0;
+        loop {
+            match self.peek() {
+                Some(T!['{'] | T!['('] | T!['[']) => depth += 1,
+                Some(T!['}'] | T![')'] | T![']']) => depth -= 1,
+                _ => {}
+            }
+            self.bump_any();
+            if matches!(self.peek(), None | Some(TokenKind::Eof))
+                || depth == 0 && self.at_recovery_point(token)
+            {
+                break;
+            }
+        }
+        guard.finish_node();
+
+        if self.at(token) {
+            self.bump(kind);
+        }
+    }
+
+    /// Check whether skipping tokens to find the closing bracket `token`
+    /// should stop at the current token.
+    fn at_recovery_point(&mut self, token: TokenKind) -> bool {
+        match self.peek() {
+            None | Some(TokenKind::Eof | T!['}'] | T![')'] | T![']']) => true,
+            Some(TokenKind::Name) => grammar::document::at_definition_start(self),
+            Some(kind) => kind == token,
+        }
+    }
+
+    /// Create an error for an exceeded limit and stop parsing.
+    ///
+    /// All remaining tokens are skipped, so that every grammar rule that is
+    /// still in progress finishes at EOF without descending any further.
+    pub(crate) fn limit_err(&mut self, message: &str) {
+        self.err(ErrorKind::LimitExceeded, message);
+        self.accept_errors = false;
+        self.lookahead.clear();
+        self.lexer.stop();
+    }
+
     /// Push an error to parser's error Vec.
     pub(crate) fn push_err(&mut self, err: crate::error::Error) {
-        self.errors.push(err);
+        if self.accept_errors {
+            self.errors.push(err);
+        }
     }
 
     /// Consume a token from the lexer.
-    pub(crate) fn pop(&mut self) -> Token {
-        self.tokens
-            .pop()
-            .expect("Could not pop a token from the AST")
+    ///
+    /// Input that could not be lexed before the token is added to the AST.
+    pub(crate) fn pop(&mut self) -> Token<'a> {
+        loop {
+            self.fill(1);
+            match self
+                .lookahead
+                .pop_front()
+                .expect("Could not pop a token from the AST")
+            {
+                Ok(token) => return token,
+                Err(text) => self.builder.borrow_mut().token(SyntaxKind::ERROR, text),
+            }
+        }
     }
 
-    /// Insert a token into the AST.
-    pub(crate) fn push_ast(&mut self, kind: SyntaxKind, token: Token) {
-        self.builder.borrow_mut().token(kind, token.data())
+    /// Lex tokens until there are at least `n` tokens to look ahead at, or
+    /// the input is exhausted. Returns whether there are `n` tokens.
+    fn fill(&mut self, n: usize) -> bool {
+        while self.lookahead.len() < n {
+            match self.lexer.next() {
+                Some(Ok(token)) => self.lookahead.push_back(Ok(token)),
+                Some(Err(err)) => {
+                    // The lexer stops at its token limit, and so does parsing.
+                    if err.kind() == &ErrorKind::LimitExceeded {
+                        self.push_err(err);
+                        self.accept_errors = false;
+                        continue;
+                    }
+                    let text = &self.input[err.index()..err.index() + err.data().len()];
+                    self.lookahead.push_back(Err(text));
+                    self.push_err(err);
+                }
+                None => return false,
+            }
+        }
+        true
     }
 
     /// Start a node and make it current.
@@ -234,39 +562,70 @@ impl Parser {
         guard
     }
 
+    /// Create a checkpoint that a node can later be started at, wrapping
+    /// everything that was added to the AST since.
+    pub(crate) fn checkpoint(&self) -> rowan::Checkpoint {
+        self.builder.borrow().checkpoint()
+    }
+
+    /// Start a node at a previously created checkpoint and make it current.
+    pub(crate) fn start_node_at(
+        &mut self,
+        checkpoint: rowan::Checkpoint,
+        kind: SyntaxKind,
+    ) -> NodeGuard {
+        self.builder.borrow_mut().start_node_at(checkpoint, kind);
+        NodeGuard::new(self.builder.clone())
+    }
+
     /// Peek the next Token and return its TokenKind.
-    pub(crate) fn peek(&self) -> Option<TokenKind> {
-        self.tokens.last().map(|token| token.kind())
+    pub(crate) fn peek(&mut self) -> Option<TokenKind> {
+        self.peek_token().map(|token| token.kind())
     }
 
     /// Peek the next Token and return it.
-    pub(crate) fn peek_token(&self) -> Option<&Token> {
-        self.tokens.last()
+    pub(crate) fn peek_token(&mut self) -> Option<Token<'a>> {
+        let mut i = 0;
+        while self.fill(i + 1) {
+            if let Ok(token) = self.lookahead[i] {
+                return Some(token);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Peek Token `n`, skipping whitespace and comments, and return it.
+    fn peek_token_n(&mut self, n: usize) -> Option<Token<'a>> {
+        let mut i = 0;
+        let mut remaining = n;
+        while self.fill(i + 1) {
+            if let Ok(token) = self.lookahead[i] {
+                if !matches!(token.kind(), TokenKind::Whitespace | TokenKind::Comment) {
+                    remaining -= 1;
+                    if remaining == 0 {
+                        return Some(token);
+                    }
+                }
+            }
+            i += 1;
+        }
+        None
     }
 
     /// Peek Token `n` and return its TokenKind.
-    pub(crate) fn peek_n(&self, n: usize) -> Option<TokenKind> {
-        self.tokens
-            .iter()
-            .rev()
-            .filter(|token| !matches!(token.kind(), TokenKind::Whitespace | TokenKind::Comment))
-            .nth(n - 1)
-            .map(|token| token.kind())
+    pub(crate) fn peek_n(&mut self, n: usize) -> Option<TokenKind> {
+        self.peek_token_n(n).map(|token| token.kind())
     }
 
     /// Peek next Token's `data` property.
-    pub(crate) fn peek_data(&self) -> Option<String> {
-        self.tokens.last().map(|token| token.data().to_string())
+    pub(crate) fn peek_data(&mut self) -> Option<&'a str> {
+        self.peek_token().map(|token| token.data())
     }
 
     /// Peek `n` Token's `data` property.
-    pub(crate) fn peek_data_n(&self, n: usize) -> Option<String> {
-        self.tokens
-            .iter()
-            .rev()
-            .filter(|token| !matches!(token.kind(), TokenKind::Whitespace | TokenKind::Comment))
-            .nth(n - 1)
-            .map(|token| token.data().to_string())
+    pub(crate) fn peek_data_n(&mut self, n: usize) -> Option<&'a str> {
+        self.peek_token_n(n).map(|token| token.data())
     }
 }
 
@@ -0,0 +1,521 @@
+//! Incremental reparsing of a `SyntaxTree` after a `TextEdit`.
+//!
+//! The approach is the same as rust-analyzer's: we first try to relex the
+//! single token an edit falls into, and if that doesn't work out we try to
+//! reparse the smallest enclosing block delimited by `{` and `}`. In both
+//! cases the new piece of the tree is spliced into the old one, reusing all
+//! unchanged green nodes. If neither strategy applies, we fall back to parsing
+//! the whole input again.
+//!
+//! Both strategies are conservative: they only apply to trees without errors,
+//! and only when the new piece lexes and parses without errors on its own.
+//! Because the grammar rules for the blocks we reparse only ever look at the
+//! tokens between their braces, the result is guaranteed to be the same as a
+//! full reparse.
+
+use std::{marker::PhantomData, ops::Range};
+
+use rowan::{GreenNode, GreenToken, NodeOrToken, TextRange, TextSize, WalkEvent};
+
+use crate::{
+    lexer::Lexer,
+    parser::grammar::{enum_, field, input, selection},
+    LimitTracker, LineIndex, Parser, ParserLimits, SyntaxElement, SyntaxKind, SyntaxNode,
+    SyntaxToken, SyntaxTree, TokenKind, T,
+};
+
+/// A change to the input of a `SyntaxTree`: the bytes in the `delete` range
+/// are replaced with the `insert` text.
+///
+/// ## Example
+/// ```rust
+/// use apollo_parser::{Parser, TextEdit};
+///
+/// let input = "query { me { name } }";
+/// let ast = Parser::new(input).parse();
+///
+/// // Rename the `name` field to `email`.
+/// let start = input.find("name").unwrap();
+/// let ast = ast.reparse(TextEdit::new(start..start + 4, "email"));
+///
+/// assert_eq!(0, ast.errors().len());
+/// assert_eq!(ast.document().to_string(), "query { me { email } }");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub(crate) delete: Range<usize>,
+    pub(crate) insert: String,
+}
+
+impl TextEdit {
+    /// Create a new `TextEdit` replacing `delete` with `insert`.
+    pub fn new<S: Into<String>>(delete: Range<usize>, insert: S) -> Self {
+        Self {
+            delete,
+            insert: insert.into(),
+        }
+    }
+
+    /// Create a new `TextEdit` inserting text at a given offset.
+    pub fn insert<S: Into<String>>(offset: usize, insert: S) -> Self {
+        Self::new(offset..offset, insert)
+    }
+
+    /// Create a new `TextEdit` deleting a range.
+    pub fn delete(delete: Range<usize>) -> Self {
+        Self::new(delete, String::new())
+    }
+
+    /// Apply the edit to a given text.
+    ///
+    /// ## Panics
+    /// Panics if the deleted range is out of bounds or does not lie on `char`
+    /// boundaries.
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        text.replace_range(self.delete.clone(), &self.insert);
+        text
+    }
+
+    fn text_range(&self) -> TextRange {
+        TextRange::new(
+            TextSize::from(self.delete.start as u32),
+            TextSize::from(self.delete.end as u32),
+        )
+    }
+}
+
+/// Reparse a tree after an edit, reusing as much of the old tree as possible.
+pub(crate) fn reparse(tree: &SyntaxTree, edit: &TextEdit) -> SyntaxTree {
+    let text = edit.apply(&tree.input);
+
+    match incremental_reparse(tree, edit) {
+        Some((green, recursion_limit, token_limit)) => SyntaxTree {
+            ast: SyntaxNode::new_root(green),
+            errors: Vec::new(),
+            line_index: LineIndex::new(&text),
+            input: text,
+            recursion_limit,
+            token_limit,
+            mode: tree.mode,
+            _root: PhantomData,
+        },
+        None => Parser::with_limits(&text, tree.limits())
+            .mode(tree.mode)
+            .parse(),
+    }
+}
+
+/// Try to produce the new root green node by only relexing a token or
+/// reparsing a block, along with the limit trackers that a full parse would
+/// report. Returns `None` if a full reparse is required.
+pub(crate) fn incremental_reparse(
+    tree: &SyntaxTree,
+    edit: &TextEdit,
+) -> Option<(GreenNode, LimitTracker, LimitTracker)> {
+    // Trees with errors are not lossless, and their errors would need to be
+    // recomputed, so those always get reparsed in full.
+    if !tree.errors.is_empty() || edit.delete.end > tree.input.len() {
+        return None;
+    }
+
+    // Relexing a token changes neither the nesting nor the number of tokens.
+    if let Some(green) = reparse_token(&tree.ast, edit) {
+        return Some((green, tree.recursion_limit, tree.token_limit));
+    }
+
+    let (node, block) = reparse_block(&tree.ast, edit, tree.limits())?;
+    let (recursion_limit, token_limit) = track_limits(tree, &node, &block)?;
+    Some((node.replace_with(block), recursion_limit, token_limit))
+}
+
+/// Node kinds whose grammar rules count towards the recursion limit.
+fn is_nested(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::SELECTION_SET
+            | SyntaxKind::LIST_VALUE
+            | SyntaxKind::OBJECT_VALUE
+            | SyntaxKind::LIST_TYPE
+    )
+}
+
+/// Compute the limit trackers that a full parse would report after `node`
+/// is replaced with `block`. Only the old and new blocks are walked, using
+/// the nesting of the block's ancestors as an offset. Returns `None` if a
+/// limit is exceeded, as a full parse then stops early with an error.
+fn track_limits(
+    tree: &SyntaxTree,
+    node: &SyntaxNode,
+    block: &GreenNode,
+) -> Option<(LimitTracker, LimitTracker)> {
+    let limits = tree.limits();
+    let offset = node
+        .ancestors()
+        .skip(1)
+        .filter(|node| is_nested(node.kind()))
+        .count();
+    let (old_high, old_tokens) = subtree_limits(node);
+    let (new_high, new_tokens) = subtree_limits(&SyntaxNode::new_root(block.clone()));
+
+    let mut high = tree.recursion_limit.high();
+    if offset + new_high >= high {
+        high = offset + new_high;
+    } else if offset + old_high == high {
+        // The edit removed nesting from what may have been the deepest
+        // block, so the rest of the tree needs to be checked.
+        high = subtree_limits(&SyntaxNode::new_root(node.replace_with(block.clone()))).0;
+    }
+    let tokens = tree.token_limit.high() - old_tokens + new_tokens;
+
+    if high > limits.recursion_limit || tokens > limits.token_limit {
+        return None;
+    }
+
+    Some((
+        LimitTracker::with_high(limits.recursion_limit, high),
+        LimitTracker::with_high(limits.token_limit, tokens),
+    ))
+}
+
+/// Get the deepest nesting and the number of tokens in a lossless subtree,
+/// including the subtree's root.
+fn subtree_limits(root: &SyntaxNode) -> (usize, usize) {
+    let (mut depth, mut high, mut tokens) = (0, 0, 0);
+    for event in root.preorder_with_tokens() {
+        match event {
+            WalkEvent::Enter(NodeOrToken::Node(node)) if is_nested(node.kind()) => {
+                depth += 1;
+                high = usize::max(high, depth);
+            }
+            WalkEvent::Leave(NodeOrToken::Node(node)) if is_nested(node.kind()) => depth -= 1,
+            WalkEvent::Enter(NodeOrToken::Token(_)) => tokens += 1,
+            _ => {}
+        }
+    }
+    (high, tokens)
+}
+
+/// Relex a single token that fully contains the edit. This handles the most
+/// common case of typing inside a name, a string, a number or whitespace.
+fn reparse_token(root: &SyntaxNode, edit: &TextEdit) -> Option<GreenNode> {
+    let range = edit.text_range();
+    let candidates = match root.covering_element(range) {
+        NodeOrToken::Token(token) => vec![token],
+        // An insertion at the boundary of two tokens can extend either of them.
+        NodeOrToken::Node(_) if range.is_empty() => root.token_at_offset(range.start()).collect(),
+        NodeOrToken::Node(_) => return None,
+    };
+
+    candidates
+        .into_iter()
+        .find_map(|token| relex_token(&token, edit))
+}
+
+fn relex_token(token: &SyntaxToken, edit: &TextEdit) -> Option<GreenNode> {
+    if !matches!(
+        token.kind(),
+        SyntaxKind::IDENT
+            | SyntaxKind::WHITESPACE
+            | SyntaxKind::COMMENT
+            | SyntaxKind::STRING
+            | SyntaxKind::STRING_VALUE
+            | SyntaxKind::INT
+            | SyntaxKind::FLOAT
+    ) {
+        return None;
+    }
+
+    let token_range = token.text_range();
+    let token_start: usize = token_range.start().into();
+    if edit.delete.start < token_start || edit.delete.end > token_range.end().into() {
+        return None;
+    }
+
+    let mut new_text = token.text().to_string();
+    new_text.replace_range(
+        edit.delete.start - token_start..edit.delete.end - token_start,
+        &edit.insert,
+    );
+
+    // Names are checked by the grammar to decide which rule to apply, so
+    // changing a name to or from a keyword can change the shape of the tree.
+    if token.kind() == SyntaxKind::IDENT
+        && (SyntaxKind::from_keyword(token.text()).is_some()
+            || SyntaxKind::from_keyword(&new_text).is_some())
+    {
+        return None;
+    }
+
+    let old_kind = single_token_kind(token.text())?;
+    if single_token_kind(&new_text)? != old_kind {
+        return None;
+    }
+
+    // Make sure the new token doesn't merge with its neighbours, e.g. when
+    // whitespace between two names gets deleted.
+    let prev = token.prev_token();
+    let next = token.next_token();
+    let mut context = String::new();
+    let mut expected_lens = Vec::new();
+    if let Some(prev) = &prev {
+        context.push_str(prev.text());
+        expected_lens.push(prev.text().len());
+    }
+    context.push_str(&new_text);
+    expected_lens.push(new_text.len());
+    if let Some(next) = &next {
+        context.push_str(next.text());
+        expected_lens.push(next.text().len());
+    }
+
+    let (tokens, errors) = Lexer::new(&context).lex();
+    if !errors.is_empty() {
+        return None;
+    }
+    let lens: Vec<usize> = tokens
+        .iter()
+        .filter(|token| token.kind() != TokenKind::Eof)
+        .map(|token| token.data().len())
+        .collect();
+    if lens != expected_lens {
+        return None;
+    }
+
+    let new_token = GreenToken::new(rowan::SyntaxKind(token.kind() as u16), &new_text);
+    Some(token.replace_with(new_token))
+}
+
+/// Lex `text` and return its token kind if it is exactly one token.
+fn single_token_kind(text: &str) -> Option<TokenKind> {
+    let (tokens, errors) = Lexer::new(text).lex();
+    match (tokens.as_slice(), errors.len()) {
+        ([token, eof], 0) if eof.kind() == TokenKind::Eof => Some(token.kind()),
+        _ => None,
+    }
+}
+
+/// Reparse the smallest block delimited by `{` and `}` that contains the
+/// edit. Returns the old block and the green node that replaces it.
+fn reparse_block(
+    root: &SyntaxNode,
+    edit: &TextEdit,
+    limits: ParserLimits,
+) -> Option<(SyntaxNode, GreenNode)> {
+    let (node, grammar) = find_reparsable_block(root, edit)?;
+
+    let node_start: usize = node.text_range().start().into();
+    let mut new_text = node.text().to_string();
+    new_text.replace_range(
+        edit.delete.start - node_start..edit.delete.end - node_start,
+        &edit.insert,
+    );
+
+    let mut p = Parser::with_limits(&new_text, limits);
+    if !p.errors.is_empty() || p.peek() != Some(T!['{']) {
+        return None;
+    }
+    grammar(&mut p);
+    if !p.errors.is_empty() || p.peek() != Some(TokenKind::Eof) {
+        return None;
+    }
+
+    let green = std::rc::Rc::try_unwrap(p.builder)
+        .ok()?
+        .into_inner()
+        .finish_green();
+    if green.kind() != rowan::SyntaxKind(node.kind() as u16) {
+        return None;
+    }
+
+    Some((node, green))
+}
+
+/// A grammar rule that parses a block from its opening brace.
+type BlockGrammar = fn(&mut Parser);
+
+fn find_reparsable_block(root: &SyntaxNode, edit: &TextEdit) -> Option<(SyntaxNode, BlockGrammar)> {
+    let node = match root.covering_element(edit.text_range()) {
+        SyntaxElement::Node(node) => node,
+        SyntaxElement::Token(token) => token.parent()?,
+    };
+
+    node.ancestors().find_map(|node| {
+        let grammar: BlockGrammar = match node.kind() {
+            SyntaxKind::SELECTION_SET => selection::selection_set,
+            SyntaxKind::FIELDS_DEFINITION => field::fields_definition,
+            SyntaxKind::INPUT_FIELDS_DEFINITION => input::input_fields_definition,
+            SyntaxKind::ENUM_VALUES_DEFINITION => enum_::enum_values_definition,
+            _ => return None,
+        };
+
+        // The edit has to be strictly between the braces, so that the block
+        // keeps its boundaries.
+        let l_curly = node.first_token()?;
+        let r_curly = node
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| it.kind() == SyntaxKind::R_CURLY)
+            .last()?;
+        let contains_edit = l_curly.kind() == SyntaxKind::L_CURLY
+            && usize::from(l_curly.text_range().end()) <= edit.delete.start
+            && edit.delete.end <= usize::from(r_curly.text_range().start());
+
+        contains_edit.then_some((node, grammar))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::AstNode;
+
+    fn check_reparse(input: &str, edit: TextEdit, incremental: bool) {
+        let tree = Parser::new(input).parse();
+        assert_eq!(
+            incremental_reparse(&tree, &edit).is_some(),
+            incremental,
+            "unexpected reparse strategy for {:?}",
+            edit
+        );
+
+        let reparsed = tree.reparse(edit.clone());
+        let full = Parser::new(&edit.apply(input)).parse();
+        assert_eq!(format!("{:?}", reparsed), format!("{:?}", full));
+        assert_eq!(reparsed.line_index(), full.line_index());
+        assert_eq!(reparsed.recursion_limit(), full.recursion_limit());
+        assert_eq!(reparsed.token_limit(), full.token_limit());
+    }
+
+    #[test]
+    fn it_relexes_a_name() {
+        let input = "query { me { name } }";
+        let name = input.find("name").unwrap();
+
+        check_reparse(input, TextEdit::insert(name + 4, "s"), true);
+        check_reparse(input, TextEdit::new(name..name + 4, "email"), true);
+        check_reparse(input, TextEdit::delete(name..name + 2), true);
+    }
+
+    #[test]
+    fn it_relexes_whitespace_strings_and_numbers() {
+        let input = r#"
+type Query {
+  "the current user"
+  me: User
+}
+
+query { me(first: 10) { name } }
+"#;
+        let desc = input.find("current").unwrap();
+        check_reparse(input, TextEdit::new(desc..desc + 7, "logged in"), true);
+
+        let int = input.find("10").unwrap();
+        check_reparse(input, TextEdit::insert(int + 2, "0"), true);
+
+        let space = input.find("  me").unwrap();
+        check_reparse(input, TextEdit::insert(space, "  "), true);
+    }
+
+    #[test]
+    fn it_reparses_a_selection_set() {
+        let input = "query { me { name } } type User { name: String }";
+        let name = input.find("name").unwrap();
+
+        check_reparse(input, TextEdit::insert(name + 4, " email"), true);
+        check_reparse(input, TextEdit::insert(name + 4, " friends { name }"), true);
+    }
+
+    #[test]
+    fn it_reparses_type_system_blocks() {
+        let input = "type User { name: String } enum Pet { CAT } input Filter { id: ID }";
+
+        let name = input.find("name").unwrap();
+        check_reparse(input, TextEdit::insert(name, "id: ID! "), true);
+
+        let cat = input.find("CAT").unwrap();
+        check_reparse(input, TextEdit::insert(cat + 3, " DOG"), true);
+
+        let id = input.find("id: ID }").unwrap();
+        check_reparse(input, TextEdit::insert(id, "first: Int "), true);
+    }
+
+    #[test]
+    fn it_tracks_limits_of_the_reparsed_block() {
+        let input = "query { a { b { c } } d { e } }";
+
+        // Nesting deeper than the rest of the tree.
+        let e = input.find("e }").unwrap();
+        check_reparse(input, TextEdit::insert(e + 1, " { f { g } }"), true);
+
+        // Nesting that is not the deepest.
+        check_reparse(input, TextEdit::insert(e + 1, " { f }"), true);
+
+        // Removing the deepest nesting.
+        let c = input.find(" { c }").unwrap();
+        check_reparse(input, TextEdit::delete(c..c + 6), true);
+
+        // Adding and removing tokens.
+        check_reparse(input, TextEdit::delete(e..e + 2), true);
+    }
+
+    #[test]
+    fn it_reparses_in_full_when_a_limit_is_exceeded() {
+        let input = "query { a { b } }";
+        let limits = ParserLimits {
+            recursion_limit: 2,
+            token_limit: usize::MAX,
+        };
+        let tree = Parser::with_limits(input, limits).parse();
+        assert_eq!(tree.errors().len(), 0);
+
+        let b = input.find('b').unwrap();
+        let edit = TextEdit::insert(b + 1, " { c }");
+        assert!(incremental_reparse(&tree, &edit).is_none());
+
+        let reparsed = tree.reparse(edit.clone());
+        let full = Parser::with_limits(&edit.apply(input), limits).parse();
+        assert_eq!(format!("{:?}", reparsed), format!("{:?}", full));
+        assert!(reparsed.recursion_limit().limited());
+    }
+
+    #[test]
+    fn it_falls_back_to_full_reparse() {
+        // Turning a name into a keyword.
+        let input = "type User { id: ID }";
+        let user = input.find("User").unwrap();
+        check_reparse(input, TextEdit::new(user..user + 4, "type"), false);
+
+        // Merging two names outside of a block.
+        let space = input.find(" User").unwrap();
+        check_reparse(input, TextEdit::delete(space..space + 1), false);
+
+        // Unbalanced braces.
+        let input = "query { me { name } }";
+        let name = input.find("name").unwrap();
+        check_reparse(input, TextEdit::insert(name, "} "), false);
+
+        // Edits outside of any block.
+        check_reparse(input, TextEdit::insert(input.len(), " type Query"), false);
+    }
+
+    #[test]
+    fn it_reparses_trees_with_errors_in_full() {
+        let input = "query { me { name } } type Query {";
+        let name = input.find("name").unwrap();
+
+        check_reparse(input, TextEdit::insert(name + 4, "s"), false);
+    }
+
+    #[test]
+    fn it_reuses_unchanged_nodes() {
+        let input = "type User { name: String } query { me { name } }";
+        let tree = Parser::new(input).parse();
+        let name = input.rfind("name").unwrap();
+        let reparsed = tree.reparse(TextEdit::insert(name + 4, " email"));
+
+        let old_def = tree.document().definitions().next().unwrap();
+        let new_def = reparsed.document().definitions().next().unwrap();
+        assert_eq!(old_def.syntax().green(), new_def.syntax().green());
+    }
+}
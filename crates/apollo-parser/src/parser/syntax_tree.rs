@@ -1,10 +1,13 @@
-use std::{fmt, slice::Iter};
+use std::{fmt, marker::PhantomData, slice::Iter};
 
 use rowan::GreenNodeBuilder;
 
-use crate::{ast::Document, Error, SyntaxElement, SyntaxKind};
+use crate::{
+    ast::{AstNode, Document},
+    Error, LimitTracker, LineIndex, ParseMode, ParserLimits, SyntaxElement, SyntaxKind, TextEdit,
+};
 
-use super::GraphQLLanguage;
+use super::{reparsing, GraphQLLanguage};
 
 /// An AST generated by the parser. Consists of a syntax tree and a `Vec<Error>`
 /// if any.
@@ -37,24 +40,120 @@ use super::GraphQLLanguage;
 /// let nodes: Vec<_> = doc.definitions().into_iter().collect();
 /// assert_eq!(nodes.len(), 1);
 /// ```
-pub struct SyntaxTree {
+///
+/// Trees returned by the parser's entry points for fragments of GraphQL, like
+/// `Parser::parse_type`, are typed by the node at their root. See
+/// [`SyntaxTree::root`].
+pub struct SyntaxTree<T = Document> {
     pub(crate) ast: rowan::SyntaxNode<GraphQLLanguage>,
     pub(crate) errors: Vec<crate::Error>,
+    pub(crate) line_index: LineIndex,
+    /// The input this tree was parsed from. Trees with errors are not
+    /// lossless, so this is needed to reparse them after an edit.
+    pub(crate) input: String,
+    pub(crate) recursion_limit: LimitTracker,
+    pub(crate) token_limit: LimitTracker,
+    pub(crate) mode: ParseMode,
+    pub(crate) _root: PhantomData<T>,
 }
 
-impl SyntaxTree {
+impl<T> SyntaxTree<T> {
     /// Get a reference to the syntax tree's errors.
     pub fn errors(&self) -> Iter<'_, crate::Error> {
         self.errors.iter()
     }
 
+    /// Get a reference to the line index of the input this tree was parsed
+    /// from. Use this to convert the text ranges of nodes to line and column
+    /// positions.
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+
+    /// Get the recursion limit this tree was parsed with, and the deepest
+    /// nesting of selection sets, list and object values, and list types
+    /// that was reached.
+    pub fn recursion_limit(&self) -> LimitTracker {
+        self.recursion_limit
+    }
+
+    /// Get the token limit this tree was parsed with, and the number of
+    /// tokens that were lexed.
+    pub fn token_limit(&self) -> LimitTracker {
+        self.token_limit
+    }
+
     /// Return the root typed `Document` node.
-    pub fn document(self) -> Document {
-        Document { syntax: self.ast }
+    ///
+    /// For fragments of GraphQL, this is the node wrapping the fragment and
+    /// any trivia and invalid input around it.
+    pub fn document(&self) -> Document {
+        Document {
+            syntax: self.ast.clone(),
+        }
+    }
+
+    /// Get the limits this tree was parsed with.
+    pub(crate) fn limits(&self) -> ParserLimits {
+        ParserLimits {
+            recursion_limit: self.recursion_limit.limit(),
+            token_limit: self.token_limit.limit(),
+        }
     }
 }
 
-impl fmt::Debug for SyntaxTree {
+impl<T: AstNode> SyntaxTree<T> {
+    /// Return the typed root node, or `None` if the input does not contain
+    /// one, like an empty input to `Parser::parse_type`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{ast, Parser};
+    ///
+    /// let ast = Parser::new("[String!]!").parse_type();
+    /// assert_eq!(0, ast.errors().len());
+    ///
+    /// let ty = ast.root().unwrap();
+    /// assert!(matches!(ty, ast::Type::NonNullType(_)));
+    /// ```
+    pub fn root(&self) -> Option<T> {
+        T::cast(self.ast.clone()).or_else(|| self.ast.children().find_map(T::cast))
+    }
+}
+
+impl SyntaxTree<Document> {
+    /// Reparse the tree after a text edit.
+    ///
+    /// Only the token or the smallest block containing the edit are parsed
+    /// again where possible, reusing all unchanged nodes of this tree. The
+    /// result is always the same as parsing the edited input from scratch.
+    ///
+    /// ## Panics
+    /// Panics if the edit's range is out of bounds of the input or does not
+    /// lie on `char` boundaries.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use apollo_parser::{Parser, TextEdit};
+    ///
+    /// let input = "type Query {\n  me: User\n}";
+    /// let ast = Parser::new(input).parse();
+    ///
+    /// let offset = input.find('}').unwrap();
+    /// let ast = ast.reparse(TextEdit::insert(offset, "  users: [User]\n"));
+    ///
+    /// assert_eq!(0, ast.errors().len());
+    /// assert_eq!(
+    ///     ast.document().to_string(),
+    ///     "type Query {\n  me: User\n  users: [User]\n}"
+    /// );
+    /// ```
+    pub fn reparse(&self, edit: TextEdit) -> SyntaxTree {
+        reparsing::reparse(self, &edit)
+    }
+}
+
+impl<T> fmt::Debug for SyntaxTree<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fn print(f: &mut fmt::Formatter<'_>, indent: usize, element: SyntaxElement) -> fmt::Result {
             let kind: SyntaxKind = element.kind();
@@ -111,6 +210,18 @@ impl SyntaxTreeBuilder {
         self.builder.start_node(rowan::SyntaxKind(kind as u16));
     }
 
+    /// Prepare for maybe wrapping the next node with a surrounding node.
+    pub(crate) fn checkpoint(&self) -> rowan::Checkpoint {
+        self.builder.checkpoint()
+    }
+
+    /// Wrap the previous nodes since the checkpoint in a new node and make it
+    /// current.
+    pub(crate) fn start_node_at(&mut self, checkpoint: rowan::Checkpoint, kind: SyntaxKind) {
+        self.builder
+            .start_node_at(checkpoint, rowan::SyntaxKind(kind as u16));
+    }
+
     /// Finish current branch and restore previous branch as current.
     pub(crate) fn finish_node(&mut self) {
         self.builder.finish_node();
@@ -121,19 +232,43 @@ impl SyntaxTreeBuilder {
         self.builder.token(rowan::SyntaxKind(kind as u16), text);
     }
 
-    pub(crate) fn finish(self, errors: Vec<Error>) -> SyntaxTree {
+    /// Finish the tree and return its root green node.
+    pub(crate) fn finish_green(self) -> rowan::GreenNode {
+        self.builder.finish()
+    }
+
+    pub(crate) fn finish<T>(
+        self,
+        errors: Vec<Error>,
+        line_index: LineIndex,
+        input: String,
+        recursion_limit: LimitTracker,
+        token_limit: LimitTracker,
+        mode: ParseMode,
+    ) -> SyntaxTree<T> {
+        let errors = errors
+            .into_iter()
+            .map(|err| err.with_line_index(&line_index))
+            .collect();
+
         SyntaxTree {
             ast: rowan::SyntaxNode::new_root(self.builder.finish()),
             // TODO: keep the errors in the builder rather than pass it in here?
             errors,
+            line_index,
+            input,
+            recursion_limit,
+            token_limit,
+            mode,
+            _root: PhantomData,
         }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::ast::Definition;
-    use crate::Parser;
+    use crate::ast::{self, AstNode, Definition};
+    use crate::{ErrorKind, Parser, TokenKind};
 
     #[test]
     fn directive_name() {
@@ -172,4 +307,71 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn it_parses_types() {
+        let ast = Parser::new(" [String!]! ").parse_type();
+        assert_eq!(0, ast.errors().len());
+        let ty = ast.root().unwrap();
+        assert_eq!(ty.syntax().to_string(), "[String!]! ");
+        assert_eq!(ast.document().syntax().to_string(), " [String!]! ");
+
+        let ast = Parser::new("").parse_type();
+        assert_eq!(1, ast.errors().len());
+        assert!(ast.root().is_none());
+    }
+
+    #[test]
+    fn it_parses_values() {
+        let ast = Parser::new("[1, $var, { a: ENUM }]").parse_value();
+        assert_eq!(0, ast.errors().len());
+        if let ast::Value::ListValue(list) = ast.root().unwrap() {
+            assert_eq!(list.values().count(), 3);
+        } else {
+            panic!("expected a list value");
+        }
+    }
+
+    #[test]
+    fn it_parses_selection_sets() {
+        let ast = Parser::new("{ a b { c } }").parse_selection_set();
+        assert_eq!(0, ast.errors().len());
+        assert_eq!(ast.root().unwrap().selections().count(), 2);
+
+        let ast = Parser::new("a b").parse_selection_set();
+        let errors: Vec<_> = ast.errors().collect();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message(), "expected a Selection Set");
+        assert!(ast.root().is_none());
+        assert_eq!(ast.document().syntax().to_string(), "a b");
+    }
+
+    #[test]
+    fn it_parses_field_sets() {
+        let ast = Parser::new("id organization { id } ... on User { name }").parse_field_set();
+        assert_eq!(0, ast.errors().len());
+        assert_eq!(ast.root().unwrap().selections().count(), 3);
+
+        let ast = Parser::new("{ id }").parse_field_set();
+        assert_eq!(2, ast.errors().len());
+    }
+
+    #[test]
+    fn it_reports_trailing_input() {
+        let ast = Parser::new("Int! String").parse_type();
+        let errors: Vec<_> = ast.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].kind(),
+            &ErrorKind::UnexpectedToken {
+                expected: vec![TokenKind::Eof]
+            }
+        );
+        assert_eq!(errors[0].data(), "String");
+        assert_eq!(ast.root().unwrap().syntax().to_string(), "Int! ");
+        assert_eq!(ast.document().syntax().to_string(), "Int! String");
+
+        let ast = Parser::new("{ a } }").parse_selection_set();
+        assert_eq!(1, ast.errors().len());
+    }
 }
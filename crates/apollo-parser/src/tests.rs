@@ -11,7 +11,7 @@ use std::{
 
 use expect_test::expect_file;
 
-use crate::{Error, Lexer, Parser, Token};
+use crate::{ast::AstNode, Error, Lexer, Parser, SyntaxTree, Token};
 
 // To run these tests and update files:
 // ```bash
@@ -24,15 +24,15 @@ use crate::{Error, Lexer, Parser, Token};
 #[test]
 fn lexer_tests() {
     dir_tests(&test_data_dir(), &["lexer/ok"], "txt", |text, path| {
-        let tokens = Lexer::new(text);
-        assert_errors_are_absent(tokens.errors(), path);
-        dump_tokens_and_errors(tokens.tokens(), tokens.errors())
+        let (tokens, errors) = Lexer::new(text).lex();
+        assert_errors_are_absent(errors.iter(), path);
+        dump_tokens_and_errors(&tokens, errors.iter())
     });
 
     dir_tests(&test_data_dir(), &["lexer/err"], "txt", |text, path| {
-        let tokens = Lexer::new(text);
-        assert_errors_are_present(tokens.errors(), path);
-        dump_tokens_and_errors(tokens.tokens(), tokens.errors())
+        let (tokens, errors) = Lexer::new(text).lex();
+        assert_errors_are_present(errors.iter(), path);
+        dump_tokens_and_errors(&tokens, errors.iter())
     });
 }
 
@@ -42,6 +42,7 @@ fn parser_tests() {
         let parser = Parser::new(text);
         let ast = parser.parse();
         assert_errors_are_absent(ast.errors(), path);
+        assert_lossless(&ast, text, path);
         format!("{:?}", ast)
     });
 
@@ -49,10 +50,20 @@ fn parser_tests() {
         let parser = Parser::new(text);
         let ast = parser.parse();
         assert_errors_are_present(ast.errors(), path);
+        assert_lossless(&ast, text, path);
         format!("{:?}", ast)
     });
 }
 
+fn assert_lossless(ast: &SyntaxTree, text: &str, path: &Path) {
+    assert_eq!(
+        ast.document().syntax().to_string(),
+        text,
+        "The tree should cover the whole input in the file {:?}",
+        path.display()
+    );
+}
+
 fn assert_errors_are_present(errors: Iter<'_, Error>, path: &Path) {
     assert!(
         errors.len() != 0,
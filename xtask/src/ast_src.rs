@@ -149,6 +149,8 @@ pub(crate) const KINDS_SRC: KindsSrc = KindsSrc {
         "DIRECTIVE_LOCATION",
         "EXECUTABLE_DIRECTIVE_LOCATION",
         "TYPE_SYSTEM_DIRECTIVE_LOCATION",
+        // Wraps tokens that the parser skipped while recovering from an error.
+        "ERROR",
     ],
 };
 